@@ -1,29 +1,17 @@
 use bevy::{
-    app::{App, Plugin, Startup, Update},
-    ecs::{
-        event::EventReader,
-        schedule::IntoSystemConfigs,
-        system::{Res, ResMut, Resource},
-    },
+    app::{App, Plugin},
     log::{error, info},
-    state::condition::in_state,
-    state::state::{OnEnter, OnExit},
-};
-use bevy_serialport::{
-    DataBits, FlowControl, Parity, SerialPortPlugin, SerialPortRuntime, SerialPortSetting,
-    SerialResource, StopBits,
 };
 
 use derive_more::{Deref, DerefMut, From};
 use serialport::{available_ports, SerialPortType};
-use thetawave_interface::states;
 
 /// Environment variable name of the serial port that handles lights.
 const THETAWAVE_ARCADE_LIGHT_SERIAL_PORT_NAME: &'static str =
     "THETAWAVE_ARCADE_LIGHT_SERIAL_PORT_NAME";
 
 /// The port for the Arduino that controls the lights.
-#[derive(Resource, Deref, DerefMut, From, Debug)]
+#[derive(Deref, DerefMut, From, Debug)]
 struct ArduinoSerialPort(String);
 impl ArduinoSerialPort {
     fn first_port_matching_manufacturer_product() -> Option<Self> {
@@ -59,90 +47,44 @@ fn first_usb_port_name_matching_str(lowercase_pattern: &str) -> Option<String> {
     }
 }
 use bytes::Bytes;
-use thetawave_interface::character_selection::PlayerJoinEvent;
+
+use crate::{
+    light_controller::{LightControllerResource, SerialLightController},
+    lights::ArcadeLightsPlugin,
+};
 
 /// Features specific to an Arduino that is only running on custom-made arcade machines. Mostly
-/// lighting. The plugin no-ops if an Arduino serial port cannot be accessed.
+/// lighting. The plugin no-ops if an Arduino serial port cannot be found or opened.
 pub struct ArcadeArduinoPlugin;
 
 impl Plugin for ArcadeArduinoPlugin {
     fn build(&self, app: &mut App) {
-        if let Some(res) = ArduinoSerialPort::first_port_matching_manufacturer_product()
+        let Some(arduino_port) = ArduinoSerialPort::first_port_matching_manufacturer_product()
             .or_else(ArduinoSerialPort::from_envvar)
-        {
-            info!("arduino serial port: {:?}", &res);
-            app.add_plugins(SerialPortPlugin).insert_resource(res);
-
-            app.add_systems(
-                Startup,
-                (setup_serial_system, enter_main_menu_button_leds_system),
-            );
-
-            app.add_systems(
-                OnEnter(states::AppStates::MainMenu),
-                enter_main_menu_button_leds_system,
-            );
-
-            app.add_systems(
-                OnEnter(states::AppStates::CharacterSelection),
-                enter_character_selection_button_leds_system,
-            );
-
-            app.add_systems(
-                Update,
-                character_selection_button_leds_system
-                    .run_if(in_state(states::AppStates::CharacterSelection)),
-            );
-
-            app.add_systems(
-                OnEnter(thetawave_interface::states::AppStates::Game),
-                enter_game_button_leds_system,
-            );
-
-            app.add_systems(
-                OnEnter(states::GameStates::Paused),
-                enter_pause_button_leds_system,
-            );
-
-            app.add_systems(
-                OnExit(states::GameStates::Paused),
-                enter_game_button_leds_system,
-            );
-
-            app.add_systems(
-                OnEnter(states::AppStates::Victory),
-                enter_victory_button_leds_system,
-            );
-
-            app.add_systems(
-                OnEnter(states::AppStates::GameOver),
-                enter_gameover_button_leds_system,
-            );
-        } else {
+        else {
             error!("Failed to find the arduino port for arcade lighting. no-op for the plugin. Enter the environment variable {} or compile without the --arcade feature", THETAWAVE_ARCADE_LIGHT_SERIAL_PORT_NAME);
+            return;
+        };
+
+        info!("arduino serial port: {:?}", &arduino_port);
+        match SerialLightController::open(&arduino_port) {
+            Ok(controller) => {
+                app.insert_resource(LightControllerResource::new(controller))
+                    .add_plugins(ArcadeLightsPlugin);
+            }
+            Err(e) => {
+                error!(
+                    "Failed to open the arduino port {:?} for arcade lighting. no-op for the plugin. {}",
+                    &arduino_port, e
+                );
+            }
         }
     }
 }
-fn setup_serial_system(
-    mut serial_resource: ResMut<SerialResource>,
-    arduino_port: Res<ArduinoSerialPort>,
-    runtime: Res<SerialPortRuntime>,
-) {
-    let serial_setting = SerialPortSetting {
-        port_name: arduino_port.clone(),
-        baud_rate: 115200,
-        data_bits: DataBits::Eight,
-        flow_control: FlowControl::None,
-        parity: Parity::None,
-        stop_bits: StopBits::One,
-        timeout: Default::default(),
-    };
-    serial_resource
-        .open_with_setting(runtime.clone(), serial_setting)
-        .expect(&format!("Error opening serial port {:?}", &arduino_port));
-}
 
-enum ButtonLEDByte {
+/// Byte sent for each button LED in a frame, followed by an end marker.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ButtonLEDByte {
     EndMarker = 255,
     Off = 0,
     On = 1,
@@ -150,155 +92,23 @@ enum ButtonLEDByte {
 }
 
 impl ButtonLEDByte {
-    fn enter_main_menu() -> Bytes {
-        vec![
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Fade as u8,
-            ButtonLEDByte::EndMarker as u8,
-        ]
-        .into()
-    }
-
-    fn enter_character_selection() -> Bytes {
-        vec![
-            ButtonLEDByte::Fade as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::EndMarker as u8,
-        ]
-        .into()
-    }
-
-    fn player_one_joined() -> Bytes {
-        vec![
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Fade as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Fade as u8,
-            ButtonLEDByte::EndMarker as u8,
-        ]
-        .into()
-    }
-
-    fn player_two_joined() -> Bytes {
-        vec![
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Fade as u8,
-            ButtonLEDByte::EndMarker as u8,
-        ]
-        .into()
-    }
-
-    fn enter_game() -> Bytes {
-        vec![
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::On as u8,
-            ButtonLEDByte::EndMarker as u8,
-        ]
-        .into()
-    }
-
-    fn enter_pause() -> Bytes {
-        vec![
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Fade as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Fade as u8,
-            ButtonLEDByte::Fade as u8,
-            ButtonLEDByte::EndMarker as u8,
-        ]
-        .into()
-    }
-
-    fn enter_gameover() -> Bytes {
-        vec![
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Fade as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Fade as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::EndMarker as u8,
-        ]
-        .into()
-    }
-
-    fn enter_victory() -> Bytes {
-        vec![
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Fade as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::Fade as u8,
-            ButtonLEDByte::Off as u8,
-            ButtonLEDByte::EndMarker as u8,
-        ]
-        .into()
+    /// Frame with a byte for each button, in order, followed by the end marker.
+    pub(crate) fn frame(buttons: impl IntoIterator<Item = ButtonLEDByte>) -> Bytes {
+        buttons
+            .into_iter()
+            .chain([ButtonLEDByte::EndMarker])
+            .map(|button| button as u8)
+            .collect::<Vec<u8>>()
+            .into()
     }
 }
 
-fn enter_main_menu_button_leds_system(
-    mut serial_resource: ResMut<SerialResource>,
-    arduino_port: Res<ArduinoSerialPort>,
-) {
-    serial_resource.send_message(&(*arduino_port), ButtonLEDByte::enter_main_menu());
-}
-
-fn enter_character_selection_button_leds_system(
-    mut serial_resource: ResMut<SerialResource>,
-    arduino_port: Res<ArduinoSerialPort>,
-) {
-    serial_resource.send_message(&(*arduino_port), ButtonLEDByte::enter_character_selection());
-}
+#[cfg(test)]
+mod test {
+    use crate::light_controller::SerialLightController;
 
-fn character_selection_button_leds_system(
-    mut serial_resource: ResMut<SerialResource>,
-    arduino_port: Res<ArduinoSerialPort>,
-    mut player_join_event: EventReader<PlayerJoinEvent>,
-) {
-    for event in player_join_event.read() {
-        if event.player_idx == 0 {
-            serial_resource.send_message(&(*arduino_port), ButtonLEDByte::player_one_joined());
-        } else if event.player_idx == 1 {
-            serial_resource.send_message(&(*arduino_port), ButtonLEDByte::player_two_joined());
-        }
+    #[test]
+    fn test_opening_missing_serial_port_fails() {
+        assert!(SerialLightController::open("/dev/thetawave-missing-light-port").is_err());
     }
 }
-
-fn enter_game_button_leds_system(
-    mut serial_resource: ResMut<SerialResource>,
-    arduino_port: Res<ArduinoSerialPort>,
-) {
-    serial_resource.send_message(&(*arduino_port), ButtonLEDByte::enter_game());
-}
-
-fn enter_pause_button_leds_system(
-    mut serial_resource: ResMut<SerialResource>,
-    arduino_port: Res<ArduinoSerialPort>,
-) {
-    serial_resource.send_message(&(*arduino_port), ButtonLEDByte::enter_pause());
-}
-
-fn enter_gameover_button_leds_system(
-    mut serial_resource: ResMut<SerialResource>,
-    arduino_port: Res<ArduinoSerialPort>,
-) {
-    serial_resource.send_message(&(*arduino_port), ButtonLEDByte::enter_gameover());
-}
-
-fn enter_victory_button_leds_system(
-    mut serial_resource: ResMut<SerialResource>,
-    arduino_port: Res<ArduinoSerialPort>,
-) {
-    serial_resource.send_message(&(*arduino_port), ButtonLEDByte::enter_victory());
-}
@@ -1,2 +1,6 @@
 /// Exposes a single Bevy plugin for integrating the arcade IO into the game systems.
 pub mod arduino;
+/// Transports for sending frames to the arcade lights, including an in-memory mock for tests.
+pub mod light_controller;
+/// LED scenes for the arcade buttons, read from a data file and triggered by the game.
+pub mod lights;
@@ -0,0 +1,93 @@
+//! Transports for sending frames of button LED bytes to the lights of an arcade cabinet.
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bevy::{ecs::system::Resource, log::error};
+use bytes::Bytes;
+use serialport::{DataBits, FlowControl, Parity, SerialPort, StopBits};
+
+/// Baud rate of the serial port of the Arduino that controls the lights.
+const SERIAL_BAUD_RATE: u32 = 115200;
+
+/// Time to wait for a frame to be written to the serial port before giving up.
+const SERIAL_WRITE_TIMEOUT: Duration = Duration::from_millis(10);
+
+/// Sends frames of button LED bytes to the lights.
+pub trait LightController: Send + Sync + 'static {
+    /// Send a complete frame, including its end marker.
+    fn send_frame(&mut self, frame: &[u8]) -> io::Result<()>;
+}
+
+/// The light controller used by the arcade light systems.
+#[derive(Resource)]
+pub struct LightControllerResource(Box<dyn LightController>);
+
+impl LightControllerResource {
+    pub fn new(controller: impl LightController) -> Self {
+        Self(Box::new(controller))
+    }
+
+    /// Send a frame, logging failures instead of interrupting the game.
+    pub fn send(&mut self, frame: Bytes) {
+        if let Err(e) = self.0.send_frame(&frame) {
+            error!("Failed to send frame to the arcade lights. {}", e);
+        }
+    }
+}
+
+/// Writes frames to an Arduino over a serial port.
+pub struct SerialLightController {
+    port: Mutex<Box<dyn SerialPort>>,
+}
+
+impl SerialLightController {
+    /// Open the serial port with the given name, failing if the port can't be opened.
+    pub fn open(port_name: &str) -> Result<Self, serialport::Error> {
+        serialport::new(port_name, SERIAL_BAUD_RATE)
+            .data_bits(DataBits::Eight)
+            .flow_control(FlowControl::None)
+            .parity(Parity::None)
+            .stop_bits(StopBits::One)
+            .timeout(SERIAL_WRITE_TIMEOUT)
+            .open()
+            .map(|port| Self {
+                port: Mutex::new(port),
+            })
+    }
+}
+
+impl LightController for SerialLightController {
+    fn send_frame(&mut self, frame: &[u8]) -> io::Result<()> {
+        self.port
+            .get_mut()
+            .map_err(|_| io::Error::other("serial port lock was poisoned"))?
+            .write_all(frame)
+    }
+}
+
+/// Records frames in memory instead of sending them, for checking the lights without a cabinet.
+/// Clones share the same recorded frames.
+#[derive(Clone, Default)]
+pub struct MockLightController {
+    frames: Arc<Mutex<Vec<Bytes>>>,
+}
+
+impl MockLightController {
+    /// All frames sent so far, oldest first.
+    pub fn frames(&self) -> Vec<Bytes> {
+        self.frames.lock().unwrap().clone()
+    }
+}
+
+impl LightController for MockLightController {
+    fn send_frame(&mut self, frame: &[u8]) -> io::Result<()> {
+        self.frames
+            .lock()
+            .map_err(|_| io::Error::other("mock frames lock was poisoned"))?
+            .push(Bytes::copy_from_slice(frame));
+        Ok(())
+    }
+}
@@ -0,0 +1,692 @@
+//! LED scenes for the buttons of an arcade cabinet, defined in `arcade_lights.ron` and triggered by
+//! app states and gameplay events.
+use std::collections::HashMap;
+
+use bevy::{
+    app::{App, Plugin, Startup, Update},
+    ecs::{
+        event::EventReader,
+        query::{With, Without},
+        schedule::IntoSystemConfigs,
+        system::{Query, Res, ResMut, Resource},
+    },
+    state::{
+        condition::in_state,
+        state::{OnEnter, OnExit},
+    },
+    time::Time,
+};
+use bytes::Bytes;
+use ron::de::from_bytes;
+use serde::Deserialize;
+use thetawave_interface::{
+    character_selection::PlayerJoinEvent,
+    health::{DamageDealtEvent, HealthComponent},
+    player::{PlayerComponent, PlayerDownedComponent},
+    spawnable::BossesDestroyedEvent,
+    states::{AppStates, GameStates},
+};
+
+use crate::{arduino::ButtonLEDByte, light_controller::LightControllerResource};
+
+/// How the LED of a single button is lit.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ButtonLightMode {
+    #[default]
+    Off,
+    On,
+    /// Fades in and out, timed by the Arduino.
+    Fade,
+    /// Turns on and off `rate` times per second, starting on.
+    Blink {
+        rate: f32,
+    },
+}
+
+impl ButtonLightMode {
+    /// Byte sent for the button after the step has been shown for `step_time` seconds.
+    fn led_byte(&self, step_time: f32) -> ButtonLEDByte {
+        match self {
+            Self::Off => ButtonLEDByte::Off,
+            Self::On => ButtonLEDByte::On,
+            Self::Fade => ButtonLEDByte::Fade,
+            Self::Blink { rate } => {
+                if (step_time * rate).fract() < 0.5 {
+                    ButtonLEDByte::On
+                } else {
+                    ButtonLEDByte::Off
+                }
+            }
+        }
+    }
+}
+
+/// Modes of all buttons for part of a scene.
+#[derive(Deserialize, Clone, Debug)]
+pub struct LightStep {
+    /// Modes of the buttons in order, buttons past the end of the list are off.
+    pub buttons: Vec<ButtonLightMode>,
+    /// Seconds until the next step, the step is held if `None`.
+    #[serde(default)]
+    pub duration: Option<f32>,
+}
+
+/// Sequence of steps played when the scene is triggered.
+#[derive(Deserialize, Clone, Debug)]
+pub struct LightScene {
+    pub steps: Vec<LightStep>,
+    /// Restart from the first step after the last step ends, instead of finishing.
+    #[serde(default)]
+    pub repeat: bool,
+}
+
+/// What starts a scene.
+#[derive(Deserialize, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum LightSceneTrigger {
+    MainMenu,
+    CharacterSelection,
+    PlayerOneJoined,
+    PlayerTwoJoined,
+    Game,
+    /// Played instead of `Game` while any player has low health.
+    LowHealth,
+    Paused,
+    GameOver,
+    Victory,
+    /// Played over the current scene when a player takes damage.
+    PlayerDamaged,
+    /// Played over the current scene when the last boss is destroyed.
+    BossesDestroyed,
+}
+
+/// Scenes for each trigger, read from `arcade_lights.ron`. Triggers without a scene leave the
+/// lights unchanged.
+#[derive(Resource, Deserialize)]
+pub struct LightScenesResource {
+    /// Number of buttons with LEDs, the length of every frame before its end marker.
+    pub button_count: usize,
+    /// Fraction of health at or below which a player has low health.
+    pub low_health_threshold: f32,
+    pub scenes: HashMap<LightSceneTrigger, LightScene>,
+}
+
+impl LightScenesResource {
+    /// Frame with the modes of the step at `step_idx` of the trigger's scene.
+    fn frame(&self, playback: &ScenePlayback) -> Option<Bytes> {
+        let step = self
+            .scenes
+            .get(&playback.trigger)?
+            .steps
+            .get(playback.step_idx)?;
+
+        Some(ButtonLEDByte::frame((0..self.button_count).map(|idx| {
+            step.buttons
+                .get(idx)
+                .copied()
+                .unwrap_or_default()
+                .led_byte(playback.step_time)
+        })))
+    }
+}
+
+/// Progress through the steps of a scene.
+#[derive(Clone, Copy, Debug)]
+struct ScenePlayback {
+    trigger: LightSceneTrigger,
+    step_idx: usize,
+    /// Seconds since the current step started.
+    step_time: f32,
+}
+
+impl ScenePlayback {
+    fn new(trigger: LightSceneTrigger) -> Self {
+        Self {
+            trigger,
+            step_idx: 0,
+            step_time: 0.0,
+        }
+    }
+
+    /// Move through the timed steps of the scene. Returns false once the last step of a scene
+    /// that doesn't repeat has ended, the last step is kept.
+    fn advance(&mut self, scene: &LightScene, delta: f32) -> bool {
+        self.step_time += delta;
+
+        loop {
+            let Some(duration) = scene
+                .steps
+                .get(self.step_idx)
+                .and_then(|step| step.duration)
+                .filter(|duration| *duration > 0.0)
+            else {
+                return true;
+            };
+
+            if self.step_time < duration {
+                return true;
+            }
+
+            if self.step_idx + 1 < scene.steps.len() {
+                self.step_idx += 1;
+            } else if scene.repeat {
+                self.step_idx = 0;
+            } else {
+                return false;
+            }
+            self.step_time -= duration;
+        }
+    }
+}
+
+/// Scenes that are playing on the lights.
+#[derive(Resource, Default)]
+pub struct LightScenePlaybackResource {
+    /// Scene for the current state, kept on its last step when it ends.
+    scene: Option<ScenePlayback>,
+    /// Scene for a gameplay event, shown over `scene` until it ends.
+    overlay: Option<ScenePlayback>,
+    /// Whether any player has low health.
+    low_health: bool,
+    /// Frame last sent to the lights, `None` after a scene starts so that its frame is sent.
+    sent_frame: Option<Bytes>,
+}
+
+impl LightScenePlaybackResource {
+    /// Start the scene for a state, stopping the scene for any gameplay event.
+    pub fn play(&mut self, trigger: LightSceneTrigger) {
+        self.overlay = None;
+        self.replace_scene(trigger);
+    }
+
+    /// Start the scene for a gameplay event over the scene for the current state.
+    pub fn play_overlay(&mut self, trigger: LightSceneTrigger) {
+        self.overlay = Some(ScenePlayback::new(trigger));
+        self.sent_frame = None;
+    }
+
+    fn replace_scene(&mut self, trigger: LightSceneTrigger) {
+        self.scene = Some(ScenePlayback::new(trigger));
+        self.sent_frame = None;
+    }
+
+    /// Scene for playing the game, depending on whether any player has low health.
+    fn game_trigger(&self) -> LightSceneTrigger {
+        if self.low_health {
+            LightSceneTrigger::LowHealth
+        } else {
+            LightSceneTrigger::Game
+        }
+    }
+}
+
+/// Sends button LED frames for the scenes in `arcade_lights.ron` through the
+/// `LightControllerResource`, which must be inserted before the plugin is added.
+pub struct ArcadeLightsPlugin;
+
+impl Plugin for ArcadeLightsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(
+            from_bytes::<LightScenesResource>(include_bytes!(
+                "../../../assets/data/arcade_lights.ron"
+            ))
+            .unwrap(),
+        )
+        .insert_resource(LightScenePlaybackResource::default());
+
+        app.add_systems(Startup, enter_main_menu_lights_system);
+
+        app.add_systems(OnEnter(AppStates::MainMenu), enter_main_menu_lights_system);
+
+        app.add_systems(
+            OnEnter(AppStates::CharacterSelection),
+            enter_character_selection_lights_system,
+        );
+
+        app.add_systems(
+            Update,
+            player_join_lights_system.run_if(in_state(AppStates::CharacterSelection)),
+        );
+
+        app.add_systems(OnEnter(AppStates::Game), enter_game_lights_system);
+
+        app.add_systems(OnEnter(GameStates::Paused), enter_pause_lights_system);
+
+        app.add_systems(OnExit(GameStates::Paused), exit_pause_lights_system);
+
+        app.add_systems(OnEnter(AppStates::Victory), enter_victory_lights_system);
+
+        app.add_systems(OnEnter(AppStates::GameOver), enter_gameover_lights_system);
+
+        app.add_systems(
+            Update,
+            (
+                player_damaged_lights_system,
+                bosses_destroyed_lights_system,
+                low_health_lights_system,
+            )
+                .before(play_light_scenes_system)
+                .run_if(in_state(AppStates::Game))
+                .run_if(in_state(GameStates::Playing)),
+        );
+
+        app.add_systems(Update, play_light_scenes_system);
+    }
+}
+
+fn enter_main_menu_lights_system(mut playback: ResMut<LightScenePlaybackResource>) {
+    playback.play(LightSceneTrigger::MainMenu);
+}
+
+fn enter_character_selection_lights_system(mut playback: ResMut<LightScenePlaybackResource>) {
+    playback.play(LightSceneTrigger::CharacterSelection);
+}
+
+fn player_join_lights_system(
+    mut playback: ResMut<LightScenePlaybackResource>,
+    mut player_join_event: EventReader<PlayerJoinEvent>,
+) {
+    for event in player_join_event.read() {
+        if event.player_idx == 0 {
+            playback.play(LightSceneTrigger::PlayerOneJoined);
+        } else if event.player_idx == 1 {
+            playback.play(LightSceneTrigger::PlayerTwoJoined);
+        }
+    }
+}
+
+/// Players start each game with full health.
+fn enter_game_lights_system(mut playback: ResMut<LightScenePlaybackResource>) {
+    playback.low_health = false;
+    playback.play(LightSceneTrigger::Game);
+}
+
+fn enter_pause_lights_system(mut playback: ResMut<LightScenePlaybackResource>) {
+    playback.play(LightSceneTrigger::Paused);
+}
+
+fn exit_pause_lights_system(mut playback: ResMut<LightScenePlaybackResource>) {
+    let trigger = playback.game_trigger();
+    playback.play(trigger);
+}
+
+fn enter_victory_lights_system(mut playback: ResMut<LightScenePlaybackResource>) {
+    playback.play(LightSceneTrigger::Victory);
+}
+
+fn enter_gameover_lights_system(mut playback: ResMut<LightScenePlaybackResource>) {
+    playback.play(LightSceneTrigger::GameOver);
+}
+
+/// Play the player damaged scene when damage is dealt to a player.
+fn player_damaged_lights_system(
+    mut playback: ResMut<LightScenePlaybackResource>,
+    mut damage_dealt_event_reader: EventReader<DamageDealtEvent>,
+    player_query: Query<(), With<PlayerComponent>>,
+) {
+    if damage_dealt_event_reader
+        .read()
+        .any(|event| event.damage > 0 && player_query.contains(event.target))
+    {
+        playback.play_overlay(LightSceneTrigger::PlayerDamaged);
+    }
+}
+
+fn bosses_destroyed_lights_system(
+    mut playback: ResMut<LightScenePlaybackResource>,
+    mut bosses_destroyed_event_reader: EventReader<BossesDestroyedEvent>,
+) {
+    if bosses_destroyed_event_reader.read().count() > 0 {
+        playback.play_overlay(LightSceneTrigger::BossesDestroyed);
+    }
+}
+
+/// Switch between the game and low health scenes when any player's health crosses the threshold.
+/// Downed players aren't counted.
+fn low_health_lights_system(
+    mut playback: ResMut<LightScenePlaybackResource>,
+    scenes: Res<LightScenesResource>,
+    player_query: Query<&HealthComponent, (With<PlayerComponent>, Without<PlayerDownedComponent>)>,
+) {
+    let low_health = player_query.iter().any(|health| {
+        !health.is_dead() && health.get_health_percentage() <= scenes.low_health_threshold
+    });
+
+    if low_health != playback.low_health {
+        playback.low_health = low_health;
+
+        let is_playing_game_scene = playback.scene.is_some_and(|scene| {
+            matches!(
+                scene.trigger,
+                LightSceneTrigger::Game | LightSceneTrigger::LowHealth
+            )
+        });
+        if is_playing_game_scene {
+            let trigger = playback.game_trigger();
+            playback.replace_scene(trigger);
+        }
+    }
+}
+
+/// Move through the steps of the playing scenes, sending a frame whenever the lights change.
+fn play_light_scenes_system(
+    mut playback: ResMut<LightScenePlaybackResource>,
+    mut lights: ResMut<LightControllerResource>,
+    scenes: Res<LightScenesResource>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds();
+    let playback = &mut *playback;
+
+    if let Some(overlay) = playback.overlay.as_mut() {
+        let is_playing = scenes
+            .scenes
+            .get(&overlay.trigger)
+            .is_some_and(|scene| overlay.advance(scene, delta));
+
+        if !is_playing {
+            playback.overlay = None;
+            playback.sent_frame = None;
+        }
+    }
+
+    if let Some(scene_playback) = playback.scene.as_mut() {
+        if let Some(scene) = scenes.scenes.get(&scene_playback.trigger) {
+            scene_playback.advance(scene, delta);
+        }
+    }
+
+    let frame = playback
+        .overlay
+        .iter()
+        .chain(playback.scene.iter())
+        .find_map(|scene_playback| scenes.frame(scene_playback));
+
+    if let Some(frame) = frame {
+        if playback.sent_frame.as_ref() != Some(&frame) {
+            lights.send(frame.clone());
+            playback.sent_frame = Some(frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use bevy::{
+        app::App,
+        state::{
+            app::{AppExtStates, StatesPlugin},
+            state::NextState,
+        },
+        time::TimeUpdateStrategy,
+        MinimalPlugins,
+    };
+    use bytes::Bytes;
+    use thetawave_interface::{
+        character_selection::PlayerJoinEvent,
+        health::{DamageDealtEvent, DamageType, HealthComponent},
+        player::{PlayerComponent, PlayerInput},
+        spawnable::BossesDestroyedEvent,
+        states::{AppStates, GameStates},
+    };
+
+    use super::{ArcadeLightsPlugin, LightScenesResource};
+    use crate::{
+        arduino::ButtonLEDByte::{self, Fade, Off, On},
+        light_controller::{LightControllerResource, MockLightController},
+    };
+
+    /// Seconds between updates in tests that play timed steps
+    const UPDATE_SECONDS: f32 = 0.03;
+
+    fn frame(buttons: [ButtonLEDByte; 5]) -> Bytes {
+        ButtonLEDByte::frame(buttons)
+    }
+
+    fn app_with_mock_lights(mock: &MockLightController, update_duration: Duration) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(update_duration))
+            .init_state::<AppStates>()
+            .init_state::<GameStates>()
+            .add_event::<PlayerJoinEvent>()
+            .add_event::<DamageDealtEvent>()
+            .add_event::<BossesDestroyedEvent>()
+            .insert_resource(LightControllerResource::new(mock.clone()))
+            .add_plugins(ArcadeLightsPlugin);
+        app
+    }
+
+    fn set_app_state(app: &mut App, state: AppStates) {
+        app.world_mut()
+            .resource_mut::<NextState<AppStates>>()
+            .set(state);
+        app.update();
+    }
+
+    fn set_game_state(app: &mut App, state: GameStates) {
+        app.world_mut()
+            .resource_mut::<NextState<GameStates>>()
+            .set(state);
+        app.update();
+    }
+
+    fn run_for(app: &mut App, seconds: f32) {
+        for _ in 0..(seconds / UPDATE_SECONDS).ceil() as usize {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn test_scenes_fit_button_count() {
+        let scenes = ron::de::from_bytes::<LightScenesResource>(include_bytes!(
+            "../../../assets/data/arcade_lights.ron"
+        ))
+        .unwrap();
+
+        for (trigger, scene) in scenes.scenes.iter() {
+            assert!(!scene.steps.is_empty(), "{:?} has no steps", trigger);
+            for step in scene.steps.iter() {
+                assert_eq!(
+                    step.buttons.len(),
+                    scenes.button_count,
+                    "{:?} has a step with the wrong number of buttons",
+                    trigger
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_state_transitions_send_frames() {
+        let mock = MockLightController::default();
+        let mut app = app_with_mock_lights(&mock, Duration::ZERO);
+
+        app.update();
+        set_app_state(&mut app, AppStates::MainMenu);
+        set_app_state(&mut app, AppStates::CharacterSelection);
+        set_app_state(&mut app, AppStates::Game);
+        set_game_state(&mut app, GameStates::Paused);
+        set_game_state(&mut app, GameStates::Playing);
+        set_app_state(&mut app, AppStates::GameOver);
+        set_app_state(&mut app, AppStates::MainMenu);
+        set_app_state(&mut app, AppStates::Game);
+        set_app_state(&mut app, AppStates::Victory);
+
+        let expected: Vec<Bytes> = vec![
+            frame([Off, Off, Off, Off, Fade]),
+            frame([Off, Off, Off, Off, Fade]),
+            frame([Fade, Off, Off, Off, Off]),
+            frame([Off, Off, Off, Off, On]),
+            frame([Off, Fade, Off, Fade, Fade]),
+            frame([Off, Off, Off, Off, On]),
+            frame([Off, Fade, Off, Fade, Off]),
+            frame([Off, Off, Off, Off, Fade]),
+            frame([Off, Off, Off, Off, On]),
+            frame([On, Off, On, Off, On]),
+        ];
+        assert_eq!(mock.frames(), expected);
+    }
+
+    #[test]
+    fn test_player_joins_send_frames() {
+        let mock = MockLightController::default();
+        let mut app = app_with_mock_lights(&mock, Duration::ZERO);
+
+        app.update();
+        set_app_state(&mut app, AppStates::CharacterSelection);
+
+        app.world_mut().send_event(PlayerJoinEvent {
+            player_idx: 0,
+            input: PlayerInput::Keyboard,
+        });
+        app.update();
+        app.world_mut().send_event(PlayerJoinEvent {
+            player_idx: 1,
+            input: PlayerInput::Gamepad(0),
+        });
+        app.update();
+
+        let expected: Vec<Bytes> = vec![
+            frame([Off, Off, Off, Off, Fade]),
+            frame([Fade, Off, Off, Off, Off]),
+            frame([Off, Off, Fade, Off, Fade]),
+            frame([Off, Off, Off, Off, Fade]),
+        ];
+        assert_eq!(mock.frames(), expected);
+    }
+
+    #[test]
+    fn test_player_joins_outside_character_selection_send_no_frames() {
+        let mock = MockLightController::default();
+        let mut app = app_with_mock_lights(&mock, Duration::ZERO);
+
+        app.update();
+        app.world_mut().send_event(PlayerJoinEvent {
+            player_idx: 0,
+            input: PlayerInput::Keyboard,
+        });
+        app.update();
+
+        assert_eq!(mock.frames(), vec![frame([Off, Off, Off, Off, Fade])]);
+    }
+
+    #[test]
+    fn test_timed_scene_repeats() {
+        let mock = MockLightController::default();
+        let mut app = app_with_mock_lights(&mock, Duration::from_secs_f32(UPDATE_SECONDS));
+
+        app.update();
+        set_app_state(&mut app, AppStates::Victory);
+        run_for(&mut app, 0.6);
+
+        let expected: Vec<Bytes> = vec![
+            frame([Off, Off, Off, Off, Fade]),
+            frame([On, Off, On, Off, On]),
+            frame([Off, On, Off, On, Off]),
+            frame([On, Off, On, Off, On]),
+        ];
+        assert_eq!(mock.frames(), expected);
+    }
+
+    #[test]
+    fn test_player_damaged_plays_over_game_scene() {
+        let mock = MockLightController::default();
+        let mut app = app_with_mock_lights(&mock, Duration::from_secs_f32(UPDATE_SECONDS));
+
+        app.update();
+        set_app_state(&mut app, AppStates::Game);
+
+        let player = app
+            .world_mut()
+            .spawn((PlayerComponent, HealthComponent::new(100, 0, 1.0)))
+            .id();
+        let mob = app
+            .world_mut()
+            .spawn(HealthComponent::new(100, 0, 1.0))
+            .id();
+
+        // damage to entities other than players doesn't change the lights
+        app.world_mut().send_event(DamageDealtEvent {
+            damage: 10,
+            target: mob,
+            source: Some(player),
+            damage_type: DamageType::Kinetic,
+            is_critical: false,
+        });
+        app.update();
+
+        app.world_mut().send_event(DamageDealtEvent {
+            damage: 10,
+            target: player,
+            source: Some(mob),
+            damage_type: DamageType::Kinetic,
+            is_critical: false,
+        });
+        run_for(&mut app, 0.5);
+
+        let expected: Vec<Bytes> = vec![
+            frame([Off, Off, Off, Off, Fade]),
+            frame([Off, Off, Off, Off, On]),
+            frame([On, On, On, On, On]),
+            frame([Off, Off, Off, Off, Off]),
+            frame([On, On, On, On, On]),
+            frame([Off, Off, Off, Off, On]),
+        ];
+        assert_eq!(mock.frames(), expected);
+    }
+
+    #[test]
+    fn test_low_health_blinks_until_healed() {
+        let mock = MockLightController::default();
+        let mut app = app_with_mock_lights(&mock, Duration::from_secs_f32(UPDATE_SECONDS));
+
+        app.update();
+        set_app_state(&mut app, AppStates::Game);
+
+        let player = app
+            .world_mut()
+            .spawn((PlayerComponent, HealthComponent::new(100, 0, 1.0)))
+            .id();
+        app.update();
+
+        app.world_mut()
+            .get_mut::<HealthComponent>(player)
+            .unwrap()
+            .take_damage(80, DamageType::Kinetic);
+        run_for(&mut app, 0.6);
+
+        app.world_mut()
+            .get_mut::<HealthComponent>(player)
+            .unwrap()
+            .heal(80);
+        run_for(&mut app, 0.3);
+
+        let expected: Vec<Bytes> = vec![
+            frame([Off, Off, Off, Off, Fade]),
+            frame([Off, Off, Off, Off, On]),
+            frame([Off, Off, Off, Off, On]),
+            frame([Off, Off, Off, Off, Off]),
+            frame([Off, Off, Off, Off, On]),
+            frame([Off, Off, Off, Off, On]),
+        ];
+        assert_eq!(mock.frames(), expected);
+    }
+
+    #[test]
+    fn test_bosses_destroyed_returns_to_game_scene() {
+        let mock = MockLightController::default();
+        let mut app = app_with_mock_lights(&mock, Duration::from_secs_f32(UPDATE_SECONDS));
+
+        app.update();
+        set_app_state(&mut app, AppStates::Game);
+
+        app.world_mut().send_event(BossesDestroyedEvent);
+        app.update();
+        assert_eq!(mock.frames().last(), Some(&frame([On, On, On, On, On])));
+
+        run_for(&mut app, 2.1);
+        assert_eq!(mock.frames().last(), Some(&frame([Off, Off, Off, Off, On])));
+    }
+}
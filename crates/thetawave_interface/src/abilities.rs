@@ -1,12 +1,15 @@
 use std::collections::HashMap;
 
 use bevy_ecs::{bundle::Bundle, component::Component, event::Event, system::Resource};
+use bevy_math::Vec2;
 use bevy_time::{Timer, TimerMode};
 use serde::Deserialize;
 
 use crate::{
-    audio::SoundEffectType, player::PlayerIDComponent, spawnable::ProjectileType,
-    weapon::SpreadPattern,
+    audio::SoundEffectType,
+    player::PlayerIDComponent,
+    spawnable::ProjectileType,
+    weapon::{BeamData, HomingData, SpreadPattern},
 };
 
 /// Identifier for slot one abilities
@@ -15,6 +18,8 @@ use crate::{
 pub enum SlotOneAbilityType {
     StandardBlast,
     StandardBullet,
+    PiercingBlast,
+    WaveBullet,
 }
 
 /// Identifier for slot two abilities
@@ -23,6 +28,11 @@ pub enum SlotOneAbilityType {
 pub enum SlotTwoAbilityType {
     Charge,
     MegaBlast,
+    Beam,
+    ShieldBubble,
+    Dash,
+    HomingMissileSalvo,
+    Decoy,
 }
 
 /// Hashmaps of ability types to descriptions
@@ -40,17 +50,152 @@ pub struct ActivateAbilityEvent {
     pub player_id: PlayerIDComponent,
     /// Slot of the ability that was activated
     pub ability_slot_id: AbilitySlotIDComponent,
+    /// Scales the strength of the activation, above 1.0 for charged hold-to-charge abilities
+    pub power: f32,
 }
 
 impl ActivateAbilityEvent {
-    pub fn new(player_id: PlayerIDComponent, ability_slot_id: AbilitySlotIDComponent) -> Self {
+    pub fn new(
+        player_id: PlayerIDComponent,
+        ability_slot_id: AbilitySlotIDComponent,
+        power: f32,
+    ) -> Self {
         Self {
             player_id,
             ability_slot_id,
+            power,
         }
     }
 }
 
+/// How the player's input activates an ability
+#[derive(Deserialize, Clone, Copy, Debug, Default)]
+pub enum AbilityInputMode {
+    /// Activates while the slot input is pressed and the cooldown is finished
+    #[default]
+    Press,
+    /// Charges while the slot input is held, and activates when it is released
+    HoldToCharge(HoldToChargeData),
+}
+
+/// Attributes of abilities that charge while their input is held
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct HoldToChargeData {
+    /// Time in seconds that the input must be held to fully charge the ability
+    pub max_charge_time: f32,
+    /// Power of the activation when fully charged, scaling linearly from 1.0 when uncharged
+    pub max_power: f32,
+}
+
+impl HoldToChargeData {
+    /// Fraction of a full charge after holding the input for `charge_time` seconds
+    pub fn get_charge_fraction(&self, charge_time: f32) -> f32 {
+        if self.max_charge_time > 0.0 {
+            (charge_time / self.max_charge_time).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Power of the activation after holding the input for `charge_time` seconds
+    pub fn get_power(&self, charge_time: f32) -> f32 {
+        1.0 + (self.max_power - 1.0) * self.get_charge_fraction(charge_time)
+    }
+}
+
+/// Tracks how the player's input activates an ability, and the charge of hold-to-charge abilities
+#[derive(Component, Clone)]
+pub struct AbilityInputComponent {
+    /// How the player's input activates the ability
+    pub mode: AbilityInputMode,
+    /// Time in seconds that the input has been held, `None` when the ability isn't charging
+    pub charge_time: Option<f32>,
+}
+
+impl AbilityInputComponent {
+    pub fn new(mode: AbilityInputMode) -> Self {
+        Self {
+            mode,
+            charge_time: None,
+        }
+    }
+
+    /// Fraction of a full charge, 0.0 when the ability isn't charging
+    pub fn get_charge_fraction(&self) -> f32 {
+        match (&self.mode, self.charge_time) {
+            (AbilityInputMode::HoldToCharge(data), Some(charge_time)) => {
+                data.get_charge_fraction(charge_time)
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// Event for leveling up an ability of a player, applying the upgrades of its next level
+#[derive(Event, Debug)]
+pub struct UpgradeAbilityEvent {
+    /// ID of the player that owns the ability
+    pub player_id: PlayerIDComponent,
+    /// Slot of the ability that is upgraded
+    pub ability_slot_id: AbilitySlotIDComponent,
+}
+
+/// A single change applied to an ability when it levels up
+#[derive(Deserialize, Clone, Debug)]
+pub enum AbilityUpgrade {
+    /// Multiplies the base cooldown time of the ability
+    CooldownMultiplier(f32),
+    /// Multiplies the damage multiplier of weapon and beam abilities
+    DamageMultiplier(f32),
+    /// Adds to the number of projectiles fired by weapon abilities, after the count multiplier is applied
+    AddProjectileCount(usize),
+    /// Adds to the number of targets that projectiles of weapon abilities pierce through
+    AddPierce(usize),
+    /// Multiplies the projectile size of weapon abilities, enlarging their explosions
+    SizeMultiplier(f32),
+    /// Multiplies how long charges, dashes, and shield bubbles last, and the lifetime of decoys
+    DurationMultiplier(f32),
+    /// Adds to the collision damage that the player deals while charging
+    AddChargeDamage(usize),
+}
+
+/// One level of an ability's upgrade tree
+#[derive(Deserialize, Clone, Debug)]
+pub struct AbilityUpgradeLevel {
+    /// Describes the upgrades to players in the HUD and on the character selection screen
+    pub description: String,
+    /// Upgrades applied to the ability when it reaches this level
+    pub upgrades: Vec<AbilityUpgrade>,
+}
+
+/// Tracks the level of an ability, and the upgrades applied at each following level
+#[derive(Component, Clone)]
+pub struct AbilityLevelComponent {
+    /// Current level of the ability, starting at 1
+    pub level: usize,
+    /// Upgrades for each level after the first, in order
+    pub upgrade_tree: Vec<AbilityUpgradeLevel>,
+}
+
+impl AbilityLevelComponent {
+    pub fn new(upgrade_tree: &[AbilityUpgradeLevel]) -> Self {
+        Self {
+            level: 1,
+            upgrade_tree: upgrade_tree.to_vec(),
+        }
+    }
+
+    /// Highest level that the ability can reach
+    pub fn max_level(&self) -> usize {
+        self.upgrade_tree.len() + 1
+    }
+
+    /// Upgrades for the level after the current one, `None` if the ability is at its max level
+    pub fn next_level(&self) -> Option<&AbilityUpgradeLevel> {
+        self.upgrade_tree.get(self.level - 1)
+    }
+}
+
 /// Stores the attributes for all abilities in the game.
 /// Each ability should be directly convertible into a component bundle
 #[derive(Resource, Deserialize)]
@@ -63,6 +208,53 @@ pub struct AbilitiesResource {
     pub standard_blast_ability: StandardWeaponAbilityData,
     /// Fires standard bullet projectiles
     pub standard_bullet_ability: StandardWeaponAbilityData,
+    /// Fires fast, narrow blast projectiles that pierce through targets
+    pub piercing_blast_ability: StandardWeaponAbilityData,
+    /// Fires bullet projectiles in an arc that sweeps back and forth
+    pub wave_bullet_ability: StandardWeaponAbilityData,
+    /// Fires a continuous beam
+    pub beam_ability: BeamAbilityData,
+    /// Surrounds the player with a bubble that absorbs hostile projectiles
+    pub shield_bubble_ability: ShieldBubbleAbilityData,
+    /// Player dashes in a direction, passing through mobs and hostile projectiles while dashing
+    pub dash_ability: DashAbilityData,
+    /// Fires a salvo of projectiles that track hostile targets
+    pub homing_missile_salvo_ability: StandardWeaponAbilityData,
+    /// Spawns a decoy drone that mobs target
+    pub decoy_ability: DecoyAbilityData,
+}
+
+impl AbilitiesResource {
+    /// Upgrade tree of a slot one ability
+    pub fn get_slot_1_upgrade_tree(
+        &self,
+        ability_type: &SlotOneAbilityType,
+    ) -> &[AbilityUpgradeLevel] {
+        match ability_type {
+            SlotOneAbilityType::StandardBlast => &self.standard_blast_ability.upgrade_tree,
+            SlotOneAbilityType::StandardBullet => &self.standard_bullet_ability.upgrade_tree,
+            SlotOneAbilityType::PiercingBlast => &self.piercing_blast_ability.upgrade_tree,
+            SlotOneAbilityType::WaveBullet => &self.wave_bullet_ability.upgrade_tree,
+        }
+    }
+
+    /// Upgrade tree of a slot two ability
+    pub fn get_slot_2_upgrade_tree(
+        &self,
+        ability_type: &SlotTwoAbilityType,
+    ) -> &[AbilityUpgradeLevel] {
+        match ability_type {
+            SlotTwoAbilityType::Charge => &self.charge_ability.upgrade_tree,
+            SlotTwoAbilityType::MegaBlast => &self.mega_blast_ability.upgrade_tree,
+            SlotTwoAbilityType::Beam => &self.beam_ability.upgrade_tree,
+            SlotTwoAbilityType::ShieldBubble => &self.shield_bubble_ability.upgrade_tree,
+            SlotTwoAbilityType::Dash => &self.dash_ability.upgrade_tree,
+            SlotTwoAbilityType::HomingMissileSalvo => {
+                &self.homing_missile_salvo_ability.upgrade_tree
+            }
+            SlotTwoAbilityType::Decoy => &self.decoy_ability.upgrade_tree,
+        }
+    }
 }
 
 /// Identifier for ability slots
@@ -98,6 +290,10 @@ pub struct ChargeAbilityBundle {
     slot: AbilitySlotIDComponent,
     /// Tracks cooldown time
     cooldown: AbilityCooldownComponent,
+    /// Tracks the level of the ability and its upgrades
+    level: AbilityLevelComponent,
+    /// Tracks how the player's input activates the ability
+    input: AbilityInputComponent,
     /// Core attributes of the charge ability, such as impulse, damage reduction
     ability: ChargeAbilityComponent,
 }
@@ -107,6 +303,8 @@ impl From<&ChargeAbilityData> for ChargeAbilityBundle {
         Self {
             slot: data.slot,
             cooldown: AbilityCooldownComponent::new(data.base_cooldown_time),
+            level: AbilityLevelComponent::new(&data.upgrade_tree),
+            input: AbilityInputComponent::new(data.input_mode),
             ability: ChargeAbilityComponent::from(data.ability),
         }
     }
@@ -114,12 +312,18 @@ impl From<&ChargeAbilityData> for ChargeAbilityBundle {
 
 /// Deserializable data for `ChargeAbilityBundle`
 /// Stores minimum data required to instantiate
-#[derive(Deserialize, Clone, Copy)]
+#[derive(Deserialize, Clone)]
 pub struct ChargeAbilityData {
     /// Slot ID that that the ability occupies
     slot: AbilitySlotIDComponent,
     /// Base cooldown duration, before player's multiplier
     base_cooldown_time: f32,
+    /// Upgrades for each level of the ability after the first
+    #[serde(default)]
+    upgrade_tree: Vec<AbilityUpgradeLevel>,
+    /// How the player's input activates the ability, pressing by default
+    #[serde(default)]
+    input_mode: AbilityInputMode,
     /// Core attributes of the charge ability, such as impulse, damage reduction
     ability: ChargeAbilityComponentData,
 }
@@ -134,14 +338,21 @@ pub struct ChargeAbilityComponent {
     pub incoming_damage_multiplier: f32,
     /// External impulse that is applied to the player, in the input direction when used
     pub impulse: f32,
+    /// Added to the player's collision damage while charging
+    pub collision_damage_bonus: usize,
 }
 
 impl From<ChargeAbilityComponentData> for ChargeAbilityComponent {
     fn from(data: ChargeAbilityComponentData) -> Self {
+        // start finished so that the charge doesn't end before the ability is first used
+        let mut action_timer = Timer::from_seconds(data.action_time, TimerMode::Once);
+        action_timer.tick(action_timer.duration());
+
         Self {
-            action_timer: Timer::from_seconds(data.action_time, TimerMode::Once),
+            action_timer,
             incoming_damage_multiplier: data.incoming_damage_multiplier,
             impulse: data.impulse,
+            collision_damage_bonus: data.collision_damage_bonus,
         }
     }
 }
@@ -156,6 +367,9 @@ struct ChargeAbilityComponentData {
     incoming_damage_multiplier: f32,
     /// External impulse that is applied to the player, in the input direction when used
     impulse: f32,
+    /// Added to the player's collision damage while charging
+    #[serde(default)]
+    collision_damage_bonus: usize,
 }
 
 /// Standard weapon bundle for spawning entity as a child of player component
@@ -165,6 +379,10 @@ pub struct StandardWeaponAbilityBundle {
     slot: AbilitySlotIDComponent,
     /// Tracks cooldown time
     cooldown: AbilityCooldownComponent,
+    /// Tracks the level of the ability and its upgrades
+    level: AbilityLevelComponent,
+    /// Tracks how the player's input activates the ability
+    input: AbilityInputComponent,
     /// Core attributes of the standard weapon ability, ammunition, multipliers, etc
     ability: StandardWeaponAbilityComponent,
 }
@@ -174,6 +392,8 @@ impl From<&StandardWeaponAbilityData> for StandardWeaponAbilityBundle {
         Self {
             slot: data.slot,
             cooldown: AbilityCooldownComponent::new(data.base_cooldown_time),
+            level: AbilityLevelComponent::new(&data.upgrade_tree),
+            input: AbilityInputComponent::new(data.input_mode),
             ability: data.ability.clone(),
         }
     }
@@ -187,6 +407,12 @@ pub struct StandardWeaponAbilityData {
     slot: AbilitySlotIDComponent,
     /// Base cooldown duration, before player's multiplier
     base_cooldown_time: f32,
+    /// Upgrades for each level of the ability after the first
+    #[serde(default)]
+    upgrade_tree: Vec<AbilityUpgradeLevel>,
+    /// How the player's input activates the ability, pressing by default
+    #[serde(default)]
+    input_mode: AbilityInputMode,
     /// Core attributes of the standard weapon ability, ammunition, multipliers, etc
     ability: StandardWeaponAbilityComponent,
 }
@@ -212,6 +438,368 @@ pub struct StandardWeaponAbilityComponent {
     /// Multiplied by the the player's projectile_count and rounded to nearest integer
     /// to get number of projetctiles fired
     pub count_multiplier: f32,
+    /// Projectiles fired in addition to the multiplied count
+    #[serde(default)]
+    pub extra_count: usize,
     /// Sound that plays when the ability is activated
     pub sound: SoundEffectType,
+    /// Makes fired projectiles steer toward hostile targets
+    #[serde(default)]
+    pub homing: Option<HomingData>,
+    /// Additional targets that fired projectiles pierce through
+    #[serde(default)]
+    pub extra_pierce: usize,
+    /// Additional times that fired projectiles ricochet
+    #[serde(default)]
+    pub extra_ricochet: usize,
+    /// Number of times the ability has been used, used by spread patterns that change every shot
+    #[serde(skip)]
+    pub shots_fired: usize,
+}
+
+/// Beam ability bundle for spawning entity as a child of player component
+#[derive(Bundle, Clone)]
+pub struct BeamAbilityBundle {
+    /// Slot ID that that the ability occupies
+    slot: AbilitySlotIDComponent,
+    /// Tracks cooldown time
+    cooldown: AbilityCooldownComponent,
+    /// Tracks the level of the ability and its upgrades
+    level: AbilityLevelComponent,
+    /// Tracks how the player's input activates the ability
+    input: AbilityInputComponent,
+    /// Core attributes of the beam ability, damage multiplier and beam data
+    ability: BeamAbilityComponent,
+}
+
+impl From<&BeamAbilityData> for BeamAbilityBundle {
+    fn from(data: &BeamAbilityData) -> Self {
+        Self {
+            slot: data.slot,
+            cooldown: AbilityCooldownComponent::new(data.base_cooldown_time),
+            level: AbilityLevelComponent::new(&data.upgrade_tree),
+            input: AbilityInputComponent::new(data.input_mode),
+            ability: data.ability.clone(),
+        }
+    }
+}
+
+/// Deserializable data for `BeamAbilityBundle`
+/// Stores minimum data required to instantiate
+#[derive(Deserialize)]
+pub struct BeamAbilityData {
+    /// Slot ID that that the ability occupies
+    slot: AbilitySlotIDComponent,
+    /// Base cooldown duration, before player's multiplier
+    base_cooldown_time: f32,
+    /// Upgrades for each level of the ability after the first
+    #[serde(default)]
+    upgrade_tree: Vec<AbilityUpgradeLevel>,
+    /// How the player's input activates the ability, pressing by default
+    #[serde(default)]
+    input_mode: AbilityInputMode,
+    /// Core attributes of the beam ability, damage multiplier and beam data
+    ability: BeamAbilityComponent,
+}
+
+/// Stores ability values unique to the beam ability
+/// This ability fires a beam that damages the first enemy in front of the player
+#[derive(Component, Deserialize, Clone)]
+pub struct BeamAbilityComponent {
+    /// Multiplied by the player's weapon damage, to get the damage of each tick of the beam
+    pub damage_multiplier: f32,
+    /// Beam that is fired, damage is replaced using the damage multiplier
+    pub beam: BeamData,
+}
+
+/// Shield bubble ability bundle for spawning entity as a child of player component
+#[derive(Bundle, Clone)]
+pub struct ShieldBubbleAbilityBundle {
+    /// Slot ID that that the ability occupies
+    slot: AbilitySlotIDComponent,
+    /// Tracks cooldown time
+    cooldown: AbilityCooldownComponent,
+    /// Tracks the level of the ability and its upgrades
+    level: AbilityLevelComponent,
+    /// Tracks how the player's input activates the ability
+    input: AbilityInputComponent,
+    /// Core attributes of the shield bubble ability, such as duration and radius
+    ability: ShieldBubbleAbilityComponent,
+}
+
+impl From<&ShieldBubbleAbilityData> for ShieldBubbleAbilityBundle {
+    fn from(data: &ShieldBubbleAbilityData) -> Self {
+        Self {
+            slot: data.slot,
+            cooldown: AbilityCooldownComponent::new(data.base_cooldown_time),
+            level: AbilityLevelComponent::new(&data.upgrade_tree),
+            input: AbilityInputComponent::new(data.input_mode),
+            ability: ShieldBubbleAbilityComponent::from(data.ability),
+        }
+    }
+}
+
+/// Deserializable data for `ShieldBubbleAbilityBundle`
+/// Stores minimum data required to instantiate
+#[derive(Deserialize, Clone)]
+pub struct ShieldBubbleAbilityData {
+    /// Slot ID that that the ability occupies
+    slot: AbilitySlotIDComponent,
+    /// Base cooldown duration, before player's multiplier
+    base_cooldown_time: f32,
+    /// Upgrades for each level of the ability after the first
+    #[serde(default)]
+    upgrade_tree: Vec<AbilityUpgradeLevel>,
+    /// How the player's input activates the ability, pressing by default
+    #[serde(default)]
+    input_mode: AbilityInputMode,
+    /// Core attributes of the shield bubble ability, such as duration and radius
+    ability: ShieldBubbleAbilityComponentData,
+}
+
+/// Stores ability values unique to the shield bubble ability
+/// Which absorbs hostile projectiles that come near the player
+#[derive(Component, Deserialize, Clone)]
+pub struct ShieldBubbleAbilityComponent {
+    /// Tracks how long the bubble has been active, the bubble is gone when completed
+    pub action_timer: Timer,
+    /// Distance from the player within which hostile projectiles are absorbed
+    pub radius: f32,
+}
+
+impl From<ShieldBubbleAbilityComponentData> for ShieldBubbleAbilityComponent {
+    fn from(data: ShieldBubbleAbilityComponentData) -> Self {
+        // start finished so that the bubble is inactive until the ability is used
+        let mut action_timer = Timer::from_seconds(data.action_time, TimerMode::Once);
+        action_timer.tick(action_timer.duration());
+
+        Self {
+            action_timer,
+            radius: data.radius,
+        }
+    }
+}
+
+/// Deserializable data for `ShieldBubbleAbilityComponent`
+/// Stores minimum data required to instantiate
+#[derive(Deserialize, Clone, Copy)]
+struct ShieldBubbleAbilityComponentData {
+    /// How long in seconds the bubble lasts when the ability is used
+    action_time: f32,
+    /// Distance from the player within which hostile projectiles are absorbed
+    radius: f32,
+}
+
+/// Dash ability bundle for spawning entity as a child of player component
+#[derive(Bundle, Clone)]
+pub struct DashAbilityBundle {
+    /// Slot ID that that the ability occupies
+    slot: AbilitySlotIDComponent,
+    /// Tracks cooldown time
+    cooldown: AbilityCooldownComponent,
+    /// Tracks the level of the ability and its upgrades
+    level: AbilityLevelComponent,
+    /// Tracks how the player's input activates the ability
+    input: AbilityInputComponent,
+    /// Core attributes of the dash ability, such as duration and speed
+    ability: DashAbilityComponent,
+}
+
+impl From<&DashAbilityData> for DashAbilityBundle {
+    fn from(data: &DashAbilityData) -> Self {
+        Self {
+            slot: data.slot,
+            cooldown: AbilityCooldownComponent::new(data.base_cooldown_time),
+            level: AbilityLevelComponent::new(&data.upgrade_tree),
+            input: AbilityInputComponent::new(data.input_mode),
+            ability: DashAbilityComponent::from(data.ability),
+        }
+    }
+}
+
+/// Deserializable data for `DashAbilityBundle`
+/// Stores minimum data required to instantiate
+#[derive(Deserialize, Clone)]
+pub struct DashAbilityData {
+    /// Slot ID that that the ability occupies
+    slot: AbilitySlotIDComponent,
+    /// Base cooldown duration, before player's multiplier
+    base_cooldown_time: f32,
+    /// Upgrades for each level of the ability after the first
+    #[serde(default)]
+    upgrade_tree: Vec<AbilityUpgradeLevel>,
+    /// How the player's input activates the ability, pressing by default
+    #[serde(default)]
+    input_mode: AbilityInputMode,
+    /// Core attributes of the dash ability, such as duration and speed
+    ability: DashAbilityComponentData,
+}
+
+/// Stores ability values unique to the dash ability
+/// Which sets the player's velocity and makes the player intangible for a short time
+#[derive(Component, Deserialize, Clone)]
+pub struct DashAbilityComponent {
+    /// Tracks how long the player has been dashing, stops dashing when completed
+    pub action_timer: Timer,
+    /// Speed of the player while dashing, in the input direction when used
+    pub speed: f32,
+}
+
+impl From<DashAbilityComponentData> for DashAbilityComponent {
+    fn from(data: DashAbilityComponentData) -> Self {
+        // start finished so that the player isn't dashing until the ability is used
+        let mut action_timer = Timer::from_seconds(data.action_time, TimerMode::Once);
+        action_timer.tick(action_timer.duration());
+
+        Self {
+            action_timer,
+            speed: data.speed,
+        }
+    }
+}
+
+/// Deserializable data for `DashAbilityComponent`
+/// Stores minimum data required to instantiate
+#[derive(Deserialize, Clone, Copy)]
+struct DashAbilityComponentData {
+    /// How long in seconds the player dashes when the ability is used
+    action_time: f32,
+    /// Speed of the player while dashing, in the input direction when used
+    speed: f32,
+}
+
+/// Decoy ability bundle for spawning entity as a child of player component
+#[derive(Bundle, Clone)]
+pub struct DecoyAbilityBundle {
+    /// Slot ID that that the ability occupies
+    slot: AbilitySlotIDComponent,
+    /// Tracks cooldown time
+    cooldown: AbilityCooldownComponent,
+    /// Tracks the level of the ability and its upgrades
+    level: AbilityLevelComponent,
+    /// Tracks how the player's input activates the ability
+    input: AbilityInputComponent,
+    /// Core attributes of the decoy ability, such as the lifetime of the decoy
+    ability: DecoyAbilityComponent,
+}
+
+impl From<&DecoyAbilityData> for DecoyAbilityBundle {
+    fn from(data: &DecoyAbilityData) -> Self {
+        Self {
+            slot: data.slot,
+            cooldown: AbilityCooldownComponent::new(data.base_cooldown_time),
+            level: AbilityLevelComponent::new(&data.upgrade_tree),
+            input: AbilityInputComponent::new(data.input_mode),
+            ability: data.ability.clone(),
+        }
+    }
+}
+
+/// Deserializable data for `DecoyAbilityBundle`
+/// Stores minimum data required to instantiate
+#[derive(Deserialize)]
+pub struct DecoyAbilityData {
+    /// Slot ID that that the ability occupies
+    slot: AbilitySlotIDComponent,
+    /// Base cooldown duration, before player's multiplier
+    base_cooldown_time: f32,
+    /// Upgrades for each level of the ability after the first
+    #[serde(default)]
+    upgrade_tree: Vec<AbilityUpgradeLevel>,
+    /// How the player's input activates the ability, pressing by default
+    #[serde(default)]
+    input_mode: AbilityInputMode,
+    /// Core attributes of the decoy ability, such as the lifetime of the decoy
+    ability: DecoyAbilityComponent,
+}
+
+/// Stores ability values unique to the decoy ability
+/// This ability spawns a drone that mobs target instead of players
+#[derive(Component, Deserialize, Clone)]
+pub struct DecoyAbilityComponent {
+    /// Time in seconds before the decoy despawns
+    pub lifetime: f32,
+    /// Health of the decoy, used by mobs that target the lowest health entity
+    pub health: usize,
+    /// Position of the decoy relative to the player when spawned
+    pub offset: Vec2,
+}
+
+/// Decoy drone spawned by the decoy ability, mobs target decoys over any other hostile entity
+#[derive(Component)]
+pub struct DecoyComponent {
+    /// Tracks time until the decoy despawns
+    pub lifetime_timer: Timer,
+}
+
+impl DecoyComponent {
+    pub fn new(lifetime: f32) -> Self {
+        Self {
+            lifetime_timer: Timer::from_seconds(lifetime, TimerMode::Once),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AbilityInputComponent, AbilityInputMode, HoldToChargeData};
+
+    fn hold_to_charge_data() -> HoldToChargeData {
+        HoldToChargeData {
+            max_charge_time: 2.0,
+            max_power: 3.0,
+        }
+    }
+
+    #[test]
+    fn test_charge_fraction_scales_with_charge_time() {
+        let data = hold_to_charge_data();
+
+        assert_eq!(data.get_charge_fraction(0.0), 0.0);
+        assert_eq!(data.get_charge_fraction(0.5), 0.25);
+        assert_eq!(data.get_charge_fraction(2.0), 1.0);
+    }
+
+    #[test]
+    fn test_charge_fraction_is_clamped() {
+        let data = hold_to_charge_data();
+
+        assert_eq!(data.get_charge_fraction(-1.0), 0.0);
+        assert_eq!(data.get_charge_fraction(10.0), 1.0);
+    }
+
+    #[test]
+    fn test_charge_fraction_without_charge_time_is_full() {
+        let data = HoldToChargeData {
+            max_charge_time: 0.0,
+            max_power: 3.0,
+        };
+
+        assert_eq!(data.get_charge_fraction(0.0), 1.0);
+        assert_eq!(data.get_power(0.0), 3.0);
+    }
+
+    #[test]
+    fn test_power_scales_from_one_to_max_power() {
+        let data = hold_to_charge_data();
+
+        assert_eq!(data.get_power(0.0), 1.0);
+        assert_eq!(data.get_power(1.0), 2.0);
+        assert_eq!(data.get_power(2.0), 3.0);
+        assert_eq!(data.get_power(10.0), 3.0);
+    }
+
+    #[test]
+    fn test_input_charge_fraction_only_while_charging() {
+        let mut ability_input =
+            AbilityInputComponent::new(AbilityInputMode::HoldToCharge(hold_to_charge_data()));
+        assert_eq!(ability_input.get_charge_fraction(), 0.0);
+
+        ability_input.charge_time = Some(1.0);
+        assert_eq!(ability_input.get_charge_fraction(), 0.5);
+
+        let mut ability_input = AbilityInputComponent::new(AbilityInputMode::Press);
+        ability_input.charge_time = Some(1.0);
+        assert_eq!(ability_input.get_charge_fraction(), 0.0);
+    }
 }
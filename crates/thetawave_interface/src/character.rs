@@ -4,16 +4,20 @@ use strum_macros::EnumIter;
 
 use crate::{
     abilities::{SlotOneAbilityType, SlotTwoAbilityType},
-    health::HealthComponent,
+    health::{DamageResistances, HealthComponent},
     spawnable::SpawnPosition,
 };
 
-/// The playable character types. To a player, these will have different appearances and abilities.
-#[derive(Deserialize, Clone, Debug, Hash, PartialEq, Eq, EnumIter, Default, Copy)]
-pub enum CharacterType {
-    #[default]
-    Captain,
-    Juggernaut,
+/// Identifies a playable character by its key in `characters.ron`. To a player, characters will have
+/// different appearances and abilities.
+#[derive(Deserialize, Clone, Debug, Hash, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct CharacterType(pub String);
+
+impl From<&str> for CharacterType {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
 }
 
 // Stats used to give the player a rough idea of the strengths and weaknesses of the character
@@ -45,8 +49,10 @@ pub struct Character {
     pub collider_dimensions: Vec2,
     /// Density of the collider (mass of collider is proportional to its size)
     pub collider_density: f32,
-    /// Character type
-    pub character_type: CharacterType,
+    /// Path of the character's sprite, listed in `player_assets.assets.ron`
+    pub sprite: String,
+    /// Path of the outline drawn around the character's sprite in multiplayer, listed in `player_assets.assets.ron`
+    pub outline_sprite: String,
     /// Health of the player
     pub health: usize,
     /// Shields of the player
@@ -79,14 +85,29 @@ pub struct Character {
     pub slot_2_ability: Option<SlotTwoAbilityType>,
     /// Multiplier for how long abilities take to be ready for use again
     pub cooldown_multiplier: f32,
+    /// Chance (0.0 to 1.0) for weapon ability projectiles to deal a critical hit
+    #[serde(default)]
+    pub critical_chance: f32,
+    /// Multiplier for the damage of critical hits
+    #[serde(default = "default_critical_multiplier")]
+    pub critical_multiplier: f32,
+    /// Fraction of damage resisted for each damage type
+    #[serde(default)]
+    pub resistances: DamageResistances,
+}
+
+fn default_critical_multiplier() -> f32 {
+    2.0
 }
 
 impl From<&Character> for HealthComponent {
     fn from(character: &Character) -> Self {
-        HealthComponent::new(
+        let mut health = HealthComponent::new(
             character.health,
             character.shields,
             character.shields_recharge_rate,
-        )
+        );
+        health.set_resistances(character.resistances.clone());
+        health
     }
 }
@@ -10,6 +10,10 @@ pub struct UserStat {
     pub total_shots_fired: usize,
     pub total_shots_hit: usize,
     pub total_games_lost: usize,
+    /// Times a player was downed in co-op
+    pub total_downs: usize,
+    /// Times a downed player was revived by a teammate
+    pub total_revives: usize,
 }
 pub type UserStatsByPlayerCacheT = HashMap<usize, UserStat>;
 pub type MobsKilledBy1PlayerCacheT = HashMap<EnemyMobType, usize>;
@@ -1,13 +1,49 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use bevy_ecs::prelude::{Component, Entity};
 use bevy_ecs_macros::Event;
 use bevy_time::{Timer, TimerMode};
+use serde::Deserialize;
+use strum_macros::Display;
+
+/// Types of damage, which are resisted separately and interact differently with shields
+#[derive(Deserialize, Debug, Hash, PartialEq, Eq, Clone, Copy, Display)]
+pub enum DamageType {
+    /// Bullets, other physical projectiles, and most collisions, fully absorbed by shields
+    Kinetic,
+    /// Blasts and beams, fully absorbed by shields
+    Energy,
+    /// Explosions, absorbed by shields at double the cost
+    Explosive,
+    /// Ramming into other entities, bypasses shields
+    Collision,
+}
+
+impl DamageType {
+    /// Shields used to absorb one point of damage, `None` if shields can't absorb the damage
+    pub fn shields_cost(&self) -> Option<f32> {
+        match self {
+            DamageType::Kinetic => Some(1.0),
+            DamageType::Energy => Some(1.0),
+            DamageType::Explosive => Some(2.0),
+            DamageType::Collision => None,
+        }
+    }
+}
+
+/// Fraction of damage resisted for each damage type
+/// 1.0 is immune, 0.0 (or missing) takes full damage, negative values take extra damage
+pub type DamageResistances = HashMap<DamageType, f32>;
 
 #[derive(Event)]
 pub struct DamageDealtEvent {
     pub damage: usize,
     pub target: Entity,
+    /// Entity responsible for the damage, if there is one
+    pub source: Option<Entity>,
+    pub damage_type: DamageType,
+    /// Whether the damage was increased by a critical hit
+    pub is_critical: bool,
 }
 
 /// Tracks health for an entity
@@ -25,6 +61,8 @@ pub struct HealthComponent {
     max_shields: usize,
     /// Time it takes to regenerate one unit of shields
     shields_recharge_timer: Timer,
+    /// Fraction of damage resisted for each damage type
+    resistances: DamageResistances,
 }
 
 impl HealthComponent {
@@ -41,6 +79,7 @@ impl HealthComponent {
                 shields_recharge_rate,
                 TimerMode::Repeating,
             ),
+            resistances: DamageResistances::new(),
         }
     }
 
@@ -57,17 +96,45 @@ impl HealthComponent {
     }
 
     /// Take damage (deplete armor, then shields, then health  in that order)
-    pub fn take_damage(&mut self, damage: usize) {
+    /// Damage is first reduced by the resistance to its type, then absorbed by shields according to its type
+    /// Returns the damage remaining after resistances
+    pub fn take_damage(&mut self, damage: usize, damage_type: DamageType) -> usize {
+        let damage = self.get_resisted_damage(damage, damage_type);
+
+        if damage == 0 {
+            return 0;
+        }
+
         if self.armor == 0 {
-            let damage_piercing_shields = damage.saturating_sub(self.shields);
-            self.shields = self.shields.saturating_sub(damage);
+            let (damage_absorbed, shields_used) = match damage_type.shields_cost() {
+                Some(cost) => {
+                    let damage_absorbed = damage.min((self.shields as f32 / cost) as usize);
+                    (
+                        damage_absorbed,
+                        (damage_absorbed as f32 * cost).ceil() as usize,
+                    )
+                }
+                None => (0, 0),
+            };
 
-            if damage_piercing_shields > 0 {
-                self.health = self.health.saturating_sub(damage_piercing_shields);
-            }
+            self.shields = self.shields.saturating_sub(shields_used);
+            self.health = self.health.saturating_sub(damage - damage_absorbed);
         } else {
             self.armor -= 1;
         }
+
+        damage
+    }
+
+    /// Damage after being reduced by the resistance to its type
+    pub fn get_resisted_damage(&self, damage: usize, damage_type: DamageType) -> usize {
+        let resistance = self.resistances.get(&damage_type).copied().unwrap_or(0.0);
+        (damage as f32 * (1.0 - resistance.min(1.0))).round() as usize
+    }
+
+    /// Set the fraction of damage resisted for each damage type
+    pub fn set_resistances(&mut self, resistances: DamageResistances) {
+        self.resistances = resistances;
     }
 
     #[allow(dead_code)]
@@ -127,7 +194,108 @@ impl HealthComponent {
     pub fn increase_max_health(&mut self, value: usize) {
         self.max_health += value;
     }
+
+    /// Set the maximum health, current health is clamped to the new maximum
+    pub fn set_max_health(&mut self, value: usize) {
+        self.max_health = value.max(1);
+        self.health = self.health.min(self.max_health);
+    }
+
+    /// Set the maximum shields, current shields are clamped to the new maximum
+    pub fn set_max_shields(&mut self, value: usize) {
+        self.max_shields = value;
+        self.shields = self.shields.min(self.max_shields);
+    }
+
+    /// Get the time it takes to regenerate one unit of shields
+    pub fn get_shields_recharge_time(&self) -> f32 {
+        self.shields_recharge_timer.duration().as_secs_f32()
+    }
+
+    /// Set the time it takes to regenerate one unit of shields
+    pub fn set_shields_recharge_time(&mut self, seconds: f32) {
+        self.shields_recharge_timer
+            .set_duration(Duration::from_secs_f32(seconds.max(0.0)));
+    }
+
     pub fn full_heal(&mut self) {
         self.health = self.max_health;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{DamageResistances, DamageType, HealthComponent};
+
+    #[test]
+    fn test_take_damage_depletes_shields_then_health() {
+        let mut health = HealthComponent::new(100, 5, 1.0);
+
+        assert_eq!(health.take_damage(10, DamageType::Kinetic), 10);
+        assert_eq!(health.get_shields(), 0);
+        assert_eq!(health.get_health(), 95);
+    }
+
+    #[test]
+    fn test_take_damage_shields_cost_depends_on_damage_type() {
+        let mut health = HealthComponent::new(100, 5, 1.0);
+        health.take_damage(4, DamageType::Energy);
+        assert_eq!(health.get_shields(), 1);
+        assert_eq!(health.get_health(), 100);
+
+        let mut health = HealthComponent::new(100, 5, 1.0);
+        health.take_damage(4, DamageType::Explosive);
+        assert_eq!(health.get_shields(), 1);
+        assert_eq!(health.get_health(), 98);
+
+        let mut health = HealthComponent::new(100, 5, 1.0);
+        health.take_damage(4, DamageType::Collision);
+        assert_eq!(health.get_shields(), 5);
+        assert_eq!(health.get_health(), 96);
+    }
+
+    #[test]
+    fn test_take_damage_armor_absorbs_a_full_hit() {
+        let mut health = HealthComponent::new(100, 5, 1.0);
+        health.gain_armor(1);
+
+        assert_eq!(health.take_damage(50, DamageType::Collision), 50);
+        assert_eq!(health.get_armor(), 0);
+        assert_eq!(health.get_shields(), 5);
+        assert_eq!(health.get_health(), 100);
+
+        health.take_damage(50, DamageType::Collision);
+        assert_eq!(health.get_health(), 50);
+    }
+
+    #[test]
+    fn test_take_damage_applies_resistances() {
+        let mut health = HealthComponent::new(100, 0, 1.0);
+        health.gain_armor(1);
+        health.set_resistances(DamageResistances::from([
+            (DamageType::Kinetic, 0.5),
+            (DamageType::Energy, 1.0),
+            (DamageType::Explosive, -0.5),
+        ]));
+
+        // fully resisted damage doesn't use up armor
+        assert_eq!(health.take_damage(10, DamageType::Energy), 0);
+        assert_eq!(health.get_armor(), 1);
+        health.take_damage(10, DamageType::Collision);
+        assert_eq!(health.get_health(), 100);
+
+        assert_eq!(health.take_damage(10, DamageType::Kinetic), 5);
+        assert_eq!(health.get_health(), 95);
+        assert_eq!(health.take_damage(10, DamageType::Explosive), 15);
+        assert_eq!(health.get_health(), 80);
+    }
+
+    #[test]
+    fn test_take_damage_does_not_go_below_zero_health() {
+        let mut health = HealthComponent::new(10, 0, 1.0);
+
+        assert_eq!(health.take_damage(25, DamageType::Kinetic), 25);
+        assert_eq!(health.get_health(), 0);
+        assert!(health.is_dead());
+    }
+}
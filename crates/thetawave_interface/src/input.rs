@@ -1,6 +1,7 @@
 //! The structures used for knowing which player inputs from controller/keyboard+mouse/etc map to
 //! known game actions.
 use bevy_ecs::{component::Component, system::Resource};
+use bevy_input::gamepad::GamepadButtonType;
 use bevy_reflect::Reflect;
 use leafwing_input_manager::{prelude::InputMap, Actionlike};
 use serde::Deserialize;
@@ -46,6 +47,9 @@ pub enum PlayerAction {
     MoveRight,
     SlotOneAbility,
     SlotTwoAbility,
+    /// Direction of slot one abilities in the free aim mode, from a gamepad stick
+    #[actionlike(DualAxis)]
+    Aim,
 }
 
 /// The parsed input/key bindings used for the life of the  entire game. This is read from files/
@@ -54,6 +58,8 @@ pub enum PlayerAction {
 #[derive(Resource, Debug)]
 pub struct InputsResource {
     pub menu: InputMap<MenuAction>,
+    /// Gamepad buttons bound to `MenuAction::JoinGamepad`, used to find which gamepad is joining
+    pub join_gamepad_buttons: Vec<GamepadButtonType>,
     pub player_keyboard: InputMap<PlayerAction>,
     pub player_gamepad: InputMap<PlayerAction>,
 }
@@ -8,8 +8,11 @@ pub mod game;
 pub mod health;
 pub mod input;
 pub mod objective;
+pub mod path;
 pub mod player;
 pub mod run;
 pub mod spawnable;
 pub mod states;
+pub mod status_effect;
+pub mod targeting;
 pub mod weapon;
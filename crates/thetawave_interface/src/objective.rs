@@ -46,6 +46,12 @@ impl DefenseData {
         self.defense = (self.defense + value).min(self.max_defense);
     }
 
+    /// Set the maximum defense, current defense is clamped to the new maximum
+    pub fn set_max_defense(&mut self, value: usize) {
+        self.max_defense = value.max(1);
+        self.defense = self.defense.min(self.max_defense);
+    }
+
     /// Decrement defense level
     pub fn take_damage(&mut self, value: usize) {
         self.defense = self.defense.saturating_sub(value);
@@ -0,0 +1,407 @@
+//! Paths that spawnables can follow, such as the entry patterns of formations
+use bevy_math::Vec2;
+use serde::Deserialize;
+
+/// Number of line segments used to approximate each curved segment of a path
+const CURVE_RESOLUTION: usize = 16;
+
+/// How the points of a path are connected
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum PathCurve {
+    /// Straight lines between the points
+    #[default]
+    Polyline,
+    /// A smooth curve that passes through every point
+    CatmullRom,
+    /// Chained cubic Bezier curves, every third point is on the path and the points between are control points
+    Bezier,
+}
+
+/// What happens when the end of a path is reached
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum PathMode {
+    /// Follow the path once, then continue in the direction of its end
+    #[default]
+    Once,
+    /// Start over from the beginning, polyline and Catmull-Rom paths are closed by connecting the last point to the first
+    Loop,
+    /// Reverse direction at each end of the path
+    PingPong,
+}
+
+/// Describes a path and the progress of a spawnable along it
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct PathData {
+    /// Points that define the path
+    pub points: Vec<Vec2>,
+    /// How the points are connected
+    #[serde(default)]
+    pub curve: PathCurve,
+    /// What happens when the end of the path is reached
+    #[serde(default)]
+    pub mode: PathMode,
+    /// Speed along the path
+    pub speed: f32,
+    /// Rotate to face the direction of the path
+    #[serde(default)]
+    pub face_direction: bool,
+    /// Added to every point of the path
+    #[serde(default)]
+    pub offset: Vec2,
+    /// Distance traveled along the path, negative distances delay the start
+    #[serde(default)]
+    pub distance: f32,
+    /// Whether the path is being followed from end to start
+    #[serde(skip)]
+    pub reversed: bool,
+}
+
+impl PathData {
+    /// Approximate the path with straight line segments
+    pub fn flattened(&self) -> FlattenedPath {
+        let closed = self.mode == PathMode::Loop;
+
+        let points = match self.curve {
+            PathCurve::Polyline => {
+                let mut points = self.points.clone();
+                if closed && points.len() > 1 {
+                    points.push(points[0]);
+                }
+                points
+            }
+            PathCurve::CatmullRom => flatten_catmull_rom(&self.points, closed),
+            PathCurve::Bezier => flatten_bezier(&self.points),
+        };
+
+        FlattenedPath::new(points)
+    }
+
+    /// Move along the path by the given distance
+    pub fn advance(&mut self, delta: f32, length: f32) {
+        match self.mode {
+            PathMode::Once => self.distance += delta,
+            PathMode::Loop => {
+                self.distance += delta;
+                // negative distances are left alone so that delayed starts are kept
+                if length > 0.0 && self.distance >= length {
+                    self.distance = self.distance.rem_euclid(length);
+                }
+            }
+            PathMode::PingPong => {
+                if self.reversed {
+                    self.distance -= delta;
+                    if self.distance <= 0.0 {
+                        self.distance = -self.distance;
+                        self.reversed = false;
+                    }
+                } else {
+                    self.distance += delta;
+                    if self.distance >= length {
+                        self.distance = (2.0 * length - self.distance).max(0.0);
+                        self.reversed = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A path being followed by a spawnable, flattened once when the spawnable starts following it
+#[derive(Clone, Debug, PartialEq)]
+pub struct FollowedPath {
+    pub data: PathData,
+    flattened: FlattenedPath,
+}
+
+impl FollowedPath {
+    pub fn new(data: PathData) -> Self {
+        FollowedPath {
+            flattened: data.flattened(),
+            data,
+        }
+    }
+
+    /// Move along the path by the given distance
+    pub fn advance(&mut self, delta: f32) {
+        self.data.advance(delta, self.flattened.length());
+    }
+
+    /// Current position on the path, including the offset, and the direction of travel
+    pub fn sample(&self) -> (Vec2, Vec2) {
+        let (position, direction) = self.flattened.sample(self.data.distance);
+        (
+            position + self.data.offset,
+            if self.data.reversed {
+                -direction
+            } else {
+                direction
+            },
+        )
+    }
+}
+
+/// A path approximated by straight line segments, used for sampling positions by distance
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlattenedPath {
+    points: Vec<Vec2>,
+    /// Distance along the path to each point
+    distances: Vec<f32>,
+}
+
+impl FlattenedPath {
+    fn new(points: Vec<Vec2>) -> Self {
+        let mut total = 0.0;
+        let distances = points
+            .iter()
+            .enumerate()
+            .map(|(i, point)| {
+                if i > 0 {
+                    total += point.distance(points[i - 1]);
+                }
+                total
+            })
+            .collect();
+
+        FlattenedPath { points, distances }
+    }
+
+    /// Total length of the path
+    pub fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    /// Position and direction at a distance along the path
+    /// Distances outside of the path extend along the first and last segments
+    pub fn sample(&self, distance: f32) -> (Vec2, Vec2) {
+        match self.points.len() {
+            0 => (Vec2::ZERO, Vec2::NEG_Y),
+            1 => (self.points[0], Vec2::NEG_Y),
+            len => {
+                let end = self
+                    .distances
+                    .partition_point(|point_distance| *point_distance <= distance)
+                    .clamp(1, len - 1);
+                let start = self.points[end - 1];
+                let direction = (self.points[end] - start).normalize_or_zero();
+
+                (
+                    start + direction * (distance - self.distances[end - 1]),
+                    direction,
+                )
+            }
+        }
+    }
+}
+
+/// Sample a Catmull-Rom curve through all of the points
+fn flatten_catmull_rom(points: &[Vec2], closed: bool) -> Vec<Vec2> {
+    let len = points.len();
+    if len < 2 {
+        return points.to_vec();
+    }
+
+    // the points at the ends are repeated for open paths, and wrap around for closed paths
+    let get = |i: isize| {
+        if closed {
+            points[i.rem_euclid(len as isize) as usize]
+        } else {
+            points[i.clamp(0, len as isize - 1) as usize]
+        }
+    };
+    let segments = if closed { len } else { len - 1 } as isize;
+
+    let mut flattened: Vec<Vec2> = (0..segments)
+        .flat_map(|i| {
+            let (p0, p1, p2, p3) = (get(i - 1), get(i), get(i + 1), get(i + 2));
+            (0..CURVE_RESOLUTION).map(move |step| {
+                let t = step as f32 / CURVE_RESOLUTION as f32;
+                let t2 = t * t;
+                let t3 = t2 * t;
+                0.5 * (2.0 * p1
+                    + (p2 - p0) * t
+                    + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+                    + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+            })
+        })
+        .collect();
+    flattened.push(get(segments));
+
+    flattened
+}
+
+/// Sample chained cubic Bezier curves, falling back to straight lines if there are too few points
+fn flatten_bezier(points: &[Vec2]) -> Vec<Vec2> {
+    let segments = points.len().saturating_sub(1) / 3;
+    if segments == 0 {
+        return points.to_vec();
+    }
+
+    let mut flattened: Vec<Vec2> = (0..segments)
+        .flat_map(|i| {
+            let (p0, p1, p2, p3) = (
+                points[3 * i],
+                points[3 * i + 1],
+                points[3 * i + 2],
+                points[3 * i + 3],
+            );
+            (0..CURVE_RESOLUTION).map(move |step| {
+                let t = step as f32 / CURVE_RESOLUTION as f32;
+                let u = 1.0 - t;
+                u * u * u * p0 + 3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t * p3
+            })
+        })
+        .collect();
+    flattened.push(points[3 * segments]);
+
+    flattened
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_math::Vec2;
+
+    use super::{
+        flatten_bezier, flatten_catmull_rom, FlattenedPath, FollowedPath, PathCurve, PathData,
+        PathMode, CURVE_RESOLUTION,
+    };
+
+    fn path(points: Vec<Vec2>, curve: PathCurve, mode: PathMode) -> PathData {
+        PathData {
+            points,
+            curve,
+            mode,
+            speed: 1.0,
+            face_direction: false,
+            offset: Vec2::ZERO,
+            distance: 0.0,
+            reversed: false,
+        }
+    }
+
+    fn square() -> Vec<Vec2> {
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(0.0, 10.0),
+        ]
+    }
+
+    #[test]
+    fn test_flatten_catmull_rom_passes_through_points() {
+        let points = square();
+
+        let open = flatten_catmull_rom(&points, false);
+        assert_eq!(open.len(), 3 * CURVE_RESOLUTION + 1);
+        for (i, point) in points.iter().enumerate() {
+            assert!(open[i * CURVE_RESOLUTION].distance(*point) < 1e-4);
+        }
+
+        // closed curves have a segment back to the first point
+        let closed = flatten_catmull_rom(&points, true);
+        assert_eq!(closed.len(), 4 * CURVE_RESOLUTION + 1);
+        assert_eq!(closed.last(), Some(&points[0]));
+
+        assert_eq!(flatten_catmull_rom(&points[..1], false), vec![points[0]]);
+    }
+
+    #[test]
+    fn test_flatten_bezier() {
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 10.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(10.0, 0.0),
+        ];
+
+        let flattened = flatten_bezier(&points);
+        assert_eq!(flattened.len(), CURVE_RESOLUTION + 1);
+        assert_eq!(flattened[0], points[0]);
+        assert_eq!(flattened[CURVE_RESOLUTION], points[3]);
+        // the curve is pulled towards, but doesn't reach, the control points
+        assert!(flattened[CURVE_RESOLUTION / 2].distance(Vec2::new(5.0, 7.5)) < 1e-4);
+
+        // extra points that don't complete a curve are ignored, too few points are a polyline
+        assert_eq!(
+            flatten_bezier(&[points.as_slice(), &points[..2]].concat()),
+            flattened
+        );
+        assert_eq!(flatten_bezier(&points[..3]), points[..3].to_vec());
+    }
+
+    #[test]
+    fn test_sample_flattened_path() {
+        let flattened = FlattenedPath::new(square());
+        assert_eq!(flattened.length(), 30.0);
+
+        assert_eq!(flattened.sample(5.0), (Vec2::new(5.0, 0.0), Vec2::X));
+        assert_eq!(flattened.sample(10.0), (Vec2::new(10.0, 0.0), Vec2::Y));
+        assert_eq!(flattened.sample(25.0), (Vec2::new(5.0, 10.0), Vec2::NEG_X));
+
+        // distances outside of the path extend along the first and last segments
+        assert_eq!(flattened.sample(-5.0), (Vec2::new(-5.0, 0.0), Vec2::X));
+        assert_eq!(flattened.sample(35.0), (Vec2::new(-5.0, 10.0), Vec2::NEG_X));
+
+        assert_eq!(
+            FlattenedPath::new(vec![]).sample(1.0),
+            (Vec2::ZERO, Vec2::NEG_Y)
+        );
+    }
+
+    #[test]
+    fn test_advance_once() {
+        let mut path = path(square(), PathCurve::Polyline, PathMode::Once);
+
+        path.advance(45.0, 30.0);
+        assert_eq!(path.distance, 45.0);
+        assert!(!path.reversed);
+    }
+
+    #[test]
+    fn test_advance_loop_wraps() {
+        let mut path = path(square(), PathCurve::Polyline, PathMode::Loop);
+        let length = path.flattened().length();
+        assert_eq!(length, 40.0);
+
+        path.advance(35.0, length);
+        assert_eq!(path.distance, 35.0);
+        path.advance(10.0, length);
+        assert_eq!(path.distance, 5.0);
+
+        // delayed starts aren't wrapped
+        path.distance = -10.0;
+        path.advance(5.0, length);
+        assert_eq!(path.distance, -5.0);
+    }
+
+    #[test]
+    fn test_advance_ping_pong_reverses() {
+        let mut path = path(square(), PathCurve::Polyline, PathMode::PingPong);
+
+        path.advance(35.0, 30.0);
+        assert_eq!(path.distance, 25.0);
+        assert!(path.reversed);
+
+        path.advance(20.0, 30.0);
+        assert_eq!(path.distance, 5.0);
+        assert!(path.reversed);
+
+        path.advance(10.0, 30.0);
+        assert_eq!(path.distance, 5.0);
+        assert!(!path.reversed);
+    }
+
+    #[test]
+    fn test_followed_path_sample() {
+        let mut data = path(square(), PathCurve::Polyline, PathMode::PingPong);
+        data.offset = Vec2::new(100.0, 0.0);
+        let mut followed_path = FollowedPath::new(data);
+
+        followed_path.advance(5.0);
+        assert_eq!(followed_path.sample(), (Vec2::new(105.0, 0.0), Vec2::X));
+
+        // the direction is flipped while following the path from end to start
+        followed_path.advance(30.0);
+        assert_eq!(followed_path.sample(), (Vec2::new(105.0, 10.0), Vec2::X));
+    }
+}
@@ -1,9 +1,13 @@
 use crate::character::{Character, CharacterType};
 use crate::spawnable::SpawnPosition;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::event::Event;
 use bevy_ecs::system::Resource;
 use bevy_ecs::{bundle::Bundle, prelude::Component};
 use bevy_math::Vec2;
+use bevy_time::{Timer, TimerMode};
 use derive_more::{Deref, DerefMut};
+use serde::Deserialize;
 
 /// Parameters for how to spawn new players. By default, the player can do anything.
 #[derive(Resource, Debug, Default, Deref, DerefMut)]
@@ -80,6 +84,11 @@ impl PlayerBundle {
     pub fn with_id(self, id: PlayerIDComponent) -> Self {
         Self { id, ..self }
     }
+
+    pub fn with_lives(mut self, lives: Option<usize>) -> Self {
+        self.inventory.lives = lives;
+        self
+    }
 }
 
 /// Identity of a player component, used for syncing UI
@@ -87,14 +96,26 @@ impl PlayerBundle {
 pub enum PlayerIDComponent {
     One,
     Two,
+    Three,
+    Four,
+}
+
+impl PlayerIDComponent {
+    /// Maximum number of players that can play at once
+    pub const MAX_PLAYERS: usize = 4;
 }
 
-/// Useful for mapping an index to a PlayerIDComponent
-impl From<usize> for PlayerIDComponent {
-    fn from(value: usize) -> Self {
+/// Useful for mapping an index to a PlayerIDComponent, fails for indices past the maximum number of players
+impl TryFrom<usize> for PlayerIDComponent {
+    type Error = usize;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
         match value {
-            0 => PlayerIDComponent::One,
-            _ => PlayerIDComponent::Two,
+            0 => Ok(PlayerIDComponent::One),
+            1 => Ok(PlayerIDComponent::Two),
+            2 => Ok(PlayerIDComponent::Three),
+            3 => Ok(PlayerIDComponent::Four),
+            _ => Err(value),
         }
     }
 }
@@ -105,6 +126,8 @@ impl From<PlayerIDComponent> for usize {
         match value {
             PlayerIDComponent::One => 0,
             PlayerIDComponent::Two => 1,
+            PlayerIDComponent::Three => 2,
+            PlayerIDComponent::Four => 3,
         }
     }
 }
@@ -153,6 +176,10 @@ pub struct PlayerOutgoingDamageComponent {
     pub base_cooldown_multiplier: f32,
     /// Multiplier for how long abilities take to be ready for use again
     pub cooldown_multiplier: f32,
+    /// Chance (0.0 to 1.0) for weapon ability projectiles to deal a critical hit
+    pub critical_chance: f32,
+    /// Multiplier for the damage of critical hits
+    pub critical_multiplier: f32,
 }
 
 /// Stores stats that effect damage incoming to the player
@@ -173,12 +200,158 @@ impl Default for PlayerIncomingDamageComponent {
 #[derive(Component)]
 pub struct PlayerInventoryComponent {
     pub money: usize,
+    /// Lives remaining before the player is downed or destroyed, `None` if the run doesn't use lives
+    pub lives: Option<usize>,
 }
 
 /// Flag for Player Entities
 #[derive(Component)]
 pub struct PlayerComponent;
 
+/// Added to a player whose health runs out while a teammate is still playing
+/// The player drifts as a wreck until a teammate revives it by staying nearby, or it bleeds out
+#[derive(Component)]
+pub struct PlayerDownedComponent {
+    /// Time until the downed player is destroyed
+    pub bleed_out_timer: Timer,
+    /// Time that a teammate has to stay nearby to revive the player, reset when no teammates are nearby
+    pub revive_timer: Timer,
+}
+
+impl PlayerDownedComponent {
+    pub fn new(bleed_out_time: f32, revive_time: f32) -> Self {
+        Self {
+            bleed_out_timer: Timer::from_seconds(bleed_out_time, TimerMode::Once),
+            revive_timer: Timer::from_seconds(revive_time, TimerMode::Once),
+        }
+    }
+}
+
+/// Added to a player that lost a life, the player is hidden until it respawns at the bottom of the arena
+#[derive(Component)]
+pub struct PlayerRespawnComponent {
+    /// Time until the player respawns
+    pub respawn_timer: Timer,
+}
+
+impl PlayerRespawnComponent {
+    pub fn new(respawn_time: f32) -> Self {
+        Self {
+            respawn_timer: Timer::from_seconds(respawn_time, TimerMode::Once),
+        }
+    }
+}
+
+/// Added to a player that just respawned, the player blinks and ignores all damage until the timer finishes
+#[derive(Component)]
+pub struct PlayerInvulnerableComponent {
+    pub invulnerable_timer: Timer,
+}
+
+impl PlayerInvulnerableComponent {
+    pub fn new(invulnerable_time: f32) -> Self {
+        Self {
+            invulnerable_timer: Timer::from_seconds(invulnerable_time, TimerMode::Once),
+        }
+    }
+}
+
+/// Added to a dashing player, the player passes through mobs and hostile projectiles and ignores all damage until
+/// the timer finishes
+#[derive(Component)]
+pub struct PlayerIntangibleComponent {
+    pub intangible_timer: Timer,
+}
+
+impl PlayerIntangibleComponent {
+    pub fn new(intangible_time: f32) -> Self {
+        Self {
+            intangible_timer: Timer::from_seconds(intangible_time, TimerMode::Once),
+        }
+    }
+}
+
+/// Tuning for the steering policy of AI controlled players
+#[derive(Deserialize, Clone, Debug)]
+pub struct PlayerAiData {
+    /// Distance within which hostile projectiles are dodged
+    pub dodge_distance: f32,
+    /// Distance within which consumables are collected
+    pub collect_distance: f32,
+    /// Vertical position the player returns to while lining up under targets
+    pub preferred_y: f32,
+    /// Distance from the destination at which the player stops moving
+    pub movement_tolerance: f32,
+    /// Horizontal distance from a target within which the player fires
+    pub fire_alignment: f32,
+}
+
+/// Drives a player's `ActionState<PlayerAction>` from a steering policy instead of an input device
+#[derive(Component)]
+pub struct PlayerAiComponent {
+    pub data: PlayerAiData,
+}
+
+impl From<&PlayerAiData> for PlayerAiComponent {
+    fn from(data: &PlayerAiData) -> Self {
+        Self { data: data.clone() }
+    }
+}
+
+/// Flag for an AI controlled ally that fights next to a solo player
+/// Wingmen don't keep the run going once all other players are gone
+#[derive(Component)]
+pub struct WingmanComponent;
+
+/// Input device that a player aims with in the free aim mode
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AimSource {
+    /// Aims toward the mouse cursor
+    Cursor,
+    /// Aims in the direction of the `Aim` stick
+    Stick,
+}
+
+/// Aim of a player in the free aim mode, sets the firing direction of slot one abilities
+#[derive(Component)]
+pub struct PlayerAimComponent {
+    /// Input device that the player aims with
+    pub source: AimSource,
+    /// Firing direction in radians, `None` when the player isn't aiming
+    pub direction: Option<f32>,
+    /// Position of the aim reticle, `None` when the player isn't aiming
+    pub reticle_position: Option<Vec2>,
+}
+
+impl PlayerAimComponent {
+    pub fn new(source: AimSource) -> Self {
+        Self {
+            source,
+            direction: None,
+            reticle_position: None,
+        }
+    }
+}
+
+/// Reticle showing where a player is aiming in the free aim mode
+#[derive(Component)]
+pub struct AimReticleComponent {
+    /// Player entity that the reticle belongs to
+    pub player: Entity,
+}
+
+/// Event for a player being downed
+#[derive(Event)]
+pub struct PlayerDownedEvent {
+    pub player_id: PlayerIDComponent,
+}
+
+/// Event for a downed player being revived by a teammate
+#[derive(Event)]
+pub struct PlayerRevivedEvent {
+    pub player_id: PlayerIDComponent,
+}
+
 impl From<&Character> for PlayerMovementComponent {
     fn from(character: &Character) -> Self {
         Self {
@@ -211,6 +384,8 @@ impl From<&Character> for PlayerOutgoingDamageComponent {
             projectile_count: character.projectile_count,
             cooldown_multiplier: character.cooldown_multiplier,
             base_cooldown_multiplier: character.cooldown_multiplier,
+            critical_chance: character.critical_chance,
+            critical_multiplier: character.critical_multiplier,
         }
     }
 }
@@ -219,6 +394,7 @@ impl From<&Character> for PlayerInventoryComponent {
     fn from(character: &Character) -> Self {
         Self {
             money: character.money,
+            lives: None,
         }
     }
 }
@@ -6,6 +6,8 @@ use bevy_math::{Quat, Vec2};
 use serde::Deserialize;
 use strum_macros::{Display, EnumString};
 
+use crate::{health::DamageType, path::PathData};
+
 /// Type that encompasses all spawnable enemy mobs
 #[derive(Deserialize, EnumString, Display, Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub enum EnemyMobType {
@@ -65,6 +67,16 @@ pub enum Faction {
     Neutral,
 }
 
+impl Faction {
+    /// Returns true if entities of this faction should attack entities of the other faction
+    pub fn is_hostile_to(&self, other: &Faction) -> bool {
+        matches!(
+            (self, other),
+            (Faction::Ally, Faction::Enemy) | (Faction::Enemy, Faction::Ally)
+        )
+    }
+}
+
 /// Type that encompasses all spawnable mobs
 #[derive(Deserialize, Debug, Hash, PartialEq, Eq, Clone, Display)]
 pub enum MobType {
@@ -100,6 +112,14 @@ impl MobType {
         }
         .to_string()
     }
+
+    pub fn get_faction(&self) -> Faction {
+        match self {
+            MobType::Enemy(_) => Faction::Enemy,
+            MobType::Ally(_) => Faction::Ally,
+            MobType::Neutral(_) => Faction::Neutral,
+        }
+    }
 }
 
 #[derive(Deserialize, Debug, Hash, PartialEq, Eq, Clone, Display)]
@@ -108,6 +128,15 @@ pub enum MobSegmentType {
     Enemy(EnemyMobSegmentType),
 }
 
+impl MobSegmentType {
+    pub fn get_faction(&self) -> Faction {
+        match self {
+            MobSegmentType::Neutral(_) => Faction::Neutral,
+            MobSegmentType::Enemy(_) => Faction::Enemy,
+        }
+    }
+}
+
 /// Type that encompasses all spawnable ally mobs
 #[derive(Deserialize, Debug, Hash, PartialEq, Eq, Clone, Display)]
 pub enum AllyMobType {
@@ -173,7 +202,6 @@ pub enum ConsumableType {
 #[derive(Deserialize, Debug, Hash, PartialEq, Eq, Clone, Display)]
 pub enum ItemType {
     EnhancedPlating,
-    /*
     SteelBarrel,
     PlasmaBlasts,
     HazardousReactor,
@@ -186,11 +214,22 @@ pub enum ItemType {
     StructureReinforcement,
     BlasterSizeEnhancer,
     FrequencyAugmentor,
+    SpareHull,
+    WeaponCalibrator,
+    AbilityCalibrator,
+    /*
     TractorBeam,
     BlastRepeller,
     */
 }
 
+impl ItemType {
+    /// Whether the item only does something in runs that use lives
+    pub fn requires_lives(&self) -> bool {
+        matches!(self, ItemType::SpareHull)
+    }
+}
+
 /// Type that encompasses all spawnable effects
 #[derive(Deserialize, Debug, Hash, PartialEq, Eq, Clone, Display, Default)]
 pub enum EffectType {
@@ -212,7 +251,8 @@ pub enum EffectType {
 /// Subtype of effect for text effects
 #[derive(Deserialize, Debug, Hash, PartialEq, Eq, Clone, Display)]
 pub enum TextEffectType {
-    DamageDealt,
+    DamageDealt(DamageType),
+    CriticalDamageDealt(DamageType),
     ConsumableCollected(ConsumableType),
 }
 
@@ -225,6 +265,19 @@ pub struct MobDestroyedEvent {
     pub is_boss: bool,
 }
 
+/// Sent the first time a projectile hits a target, projectiles that pierce or ricochet
+/// still only send one event
+#[derive(Event)]
+pub struct ProjectileHitEvent {
+    pub projectile: Entity,
+    /// Entity that fired the projectile
+    pub source: Entity,
+}
+
+/// Sent when the last boss mob is destroyed
+#[derive(Event)]
+pub struct BossesDestroyedEvent;
+
 #[derive(Event)]
 pub struct MobSegmentDestroyedEvent {
     pub mob_segment_type: MobSegmentType,
@@ -242,6 +295,10 @@ pub struct SpawnMobEvent {
     pub rotation: Quat,
 
     pub boss: bool,
+    /// Path for the mob to follow, replacing its position and movement behaviors
+    pub path: Option<PathData>,
+    /// Mob or mob segment that spawned the mob
+    pub parent: Option<Entity>,
 }
 
 #[derive(Component)]
@@ -0,0 +1,459 @@
+//! Timed status effects (burning, slows, freezes, stuns) that can be applied to players and mobs
+use std::time::Duration;
+
+use bevy_ecs::{component::Component, entity::Entity, event::Event};
+use bevy_time::{Timer, TimerMode};
+use serde::Deserialize;
+
+/// Types of status effects
+/// Ordered by priority, the first active effect determines the tint of the affected entity
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StatusEffectType {
+    /// Stops all movement and disables weapons and abilities
+    Freeze,
+    /// Disables weapons and abilities
+    Stun,
+    /// Deals damage every tick
+    Burn,
+    /// Multiplies maximum movement speed
+    Slow,
+    /// Multiplies incoming damage
+    Vulnerable,
+}
+
+/// How an effect behaves when it is applied to an entity that already has an effect of the same type
+#[derive(Deserialize, Clone, Copy, Debug, Default)]
+pub enum StatusEffectStacking {
+    /// Reset the duration of the existing effect
+    #[default]
+    Refresh,
+    /// Add the duration to the remaining duration of the existing effect
+    Extend,
+    /// Add a stack (up to the maximum) and reset the duration, the magnitude is applied once per stack
+    Stack { max_stacks: usize },
+    /// Do nothing while the existing effect is active
+    Ignore,
+}
+
+fn default_tick_period() -> f32 {
+    1.0
+}
+
+fn default_magnitude() -> f32 {
+    1.0
+}
+
+/// Data describing a status effect that can be applied to an entity
+#[derive(Deserialize, Clone, Debug)]
+pub struct StatusEffectData {
+    /// Type of the effect
+    pub effect_type: StatusEffectType,
+    /// Time in seconds that the effect lasts
+    pub duration: f32,
+    /// Strength of the effect
+    /// Damage per tick for burn, speed multiplier for slow, damage multiplier for vulnerable
+    /// Defaults to 1.0, which leaves speed and incoming damage unchanged
+    #[serde(default = "default_magnitude")]
+    pub magnitude: f32,
+    /// Time in seconds between damage ticks of burn effects
+    #[serde(default = "default_tick_period")]
+    pub tick_period: f32,
+    /// Rule for reapplying the effect
+    #[serde(default)]
+    pub stacking: StatusEffectStacking,
+}
+
+/// A status effect currently active on an entity
+#[derive(Clone, Debug)]
+pub struct StatusEffect {
+    /// Data that the effect was created from
+    pub data: StatusEffectData,
+    /// Number of times the effect has been stacked
+    pub stacks: usize,
+    /// Tracks the remaining time of the effect
+    pub duration_timer: Timer,
+    /// Tracks time between damage ticks
+    pub tick_timer: Timer,
+}
+
+impl From<&StatusEffectData> for StatusEffect {
+    fn from(data: &StatusEffectData) -> Self {
+        StatusEffect {
+            data: data.clone(),
+            stacks: 1,
+            duration_timer: Timer::from_seconds(data.duration, TimerMode::Once),
+            tick_timer: Timer::from_seconds(data.tick_period, TimerMode::Repeating),
+        }
+    }
+}
+
+impl StatusEffect {
+    /// Magnitude of the effect scaled additively by its stacks
+    fn additive_magnitude(&self) -> f32 {
+        self.data.magnitude * self.stacks as f32
+    }
+
+    /// Magnitude of the effect scaled multiplicatively by its stacks
+    fn multiplicative_magnitude(&self) -> f32 {
+        self.data.magnitude.powi(self.stacks as i32)
+    }
+}
+
+/// Stores all the status effects active on an entity
+#[derive(Component, Default, Debug)]
+pub struct StatusEffectsComponent {
+    effects: Vec<StatusEffect>,
+}
+
+impl StatusEffectsComponent {
+    /// Apply a new effect, following the stacking rule of the effect if one of the same type is active
+    pub fn apply(&mut self, data: &StatusEffectData) {
+        if let Some(effect) = self
+            .effects
+            .iter_mut()
+            .find(|effect| effect.data.effect_type == data.effect_type)
+        {
+            match data.stacking {
+                StatusEffectStacking::Refresh => {
+                    effect.data = data.clone();
+                    effect.duration_timer = Timer::from_seconds(data.duration, TimerMode::Once);
+                }
+                StatusEffectStacking::Extend => {
+                    let remaining = effect.duration_timer.remaining_secs();
+                    effect.duration_timer =
+                        Timer::from_seconds(remaining + data.duration, TimerMode::Once);
+                }
+                StatusEffectStacking::Stack { max_stacks } => {
+                    effect.stacks = (effect.stacks + 1).min(max_stacks.max(1));
+                    effect.duration_timer = Timer::from_seconds(data.duration, TimerMode::Once);
+                }
+                StatusEffectStacking::Ignore => {}
+            }
+        } else {
+            self.effects.push(StatusEffect::from(data));
+        }
+    }
+
+    /// Tick all effects, remove the ones that have expired, and return the damage dealt by burn effects
+    pub fn update(&mut self, delta_time: Duration) -> usize {
+        let mut damage = 0.0;
+
+        for effect in self.effects.iter_mut() {
+            effect.duration_timer.tick(delta_time);
+
+            if matches!(effect.data.effect_type, StatusEffectType::Burn) {
+                effect.tick_timer.tick(delta_time);
+                damage += effect.additive_magnitude()
+                    * effect.tick_timer.times_finished_this_tick() as f32;
+            }
+        }
+
+        self.effects
+            .retain(|effect| !effect.duration_timer.finished());
+
+        damage.round() as usize
+    }
+
+    /// Multiplier for maximum movement speed from slow and freeze effects
+    pub fn movement_multiplier(&self) -> f32 {
+        self.effects
+            .iter()
+            .map(|effect| match effect.data.effect_type {
+                StatusEffectType::Freeze => 0.0,
+                StatusEffectType::Slow => effect.multiplicative_magnitude(),
+                _ => 1.0,
+            })
+            .product()
+    }
+
+    /// Multiplier for incoming damage from vulnerable effects
+    pub fn damage_taken_multiplier(&self) -> f32 {
+        self.effects
+            .iter()
+            .filter(|effect| matches!(effect.data.effect_type, StatusEffectType::Vulnerable))
+            .map(|effect| effect.multiplicative_magnitude())
+            .product()
+    }
+
+    /// Returns true if weapons and abilities should be disabled
+    pub fn is_stunned(&self) -> bool {
+        self.effects.iter().any(|effect| {
+            matches!(
+                effect.data.effect_type,
+                StatusEffectType::Stun | StatusEffectType::Freeze
+            )
+        })
+    }
+
+    /// Types of all active effects, sorted by priority
+    pub fn active_types(&self) -> Vec<StatusEffectType> {
+        let mut types: Vec<StatusEffectType> = self
+            .effects
+            .iter()
+            .map(|effect| effect.data.effect_type)
+            .collect();
+        types.sort();
+        types
+    }
+
+    /// Remove all active effects
+    pub fn clear(&mut self) {
+        self.effects.clear();
+    }
+}
+
+/// Event for applying a status effect to an entity
+#[derive(Event)]
+pub struct ApplyStatusEffectEvent {
+    /// Entity to apply the effect to
+    pub target: Entity,
+    /// Effect to apply
+    pub status_effect: StatusEffectData,
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{StatusEffectData, StatusEffectStacking, StatusEffectType, StatusEffectsComponent};
+
+    fn effect(
+        effect_type: StatusEffectType,
+        duration: f32,
+        magnitude: f32,
+        stacking: StatusEffectStacking,
+    ) -> StatusEffectData {
+        StatusEffectData {
+            effect_type,
+            duration,
+            magnitude,
+            tick_period: 1.0,
+            stacking,
+        }
+    }
+
+    fn secs(seconds: f32) -> Duration {
+        Duration::from_secs_f32(seconds)
+    }
+
+    #[test]
+    fn test_effects_expire_after_duration() {
+        let mut status_effects = StatusEffectsComponent::default();
+        status_effects.apply(&effect(
+            StatusEffectType::Stun,
+            2.0,
+            1.0,
+            StatusEffectStacking::Refresh,
+        ));
+
+        status_effects.update(secs(1.5));
+        assert!(status_effects.is_stunned());
+
+        status_effects.update(secs(0.5));
+        assert!(!status_effects.is_stunned());
+        assert!(status_effects.active_types().is_empty());
+    }
+
+    #[test]
+    fn test_refresh_resets_duration_and_replaces_data() {
+        let mut status_effects = StatusEffectsComponent::default();
+        status_effects.apply(&effect(
+            StatusEffectType::Slow,
+            2.0,
+            0.5,
+            StatusEffectStacking::Refresh,
+        ));
+        status_effects.update(secs(1.5));
+        status_effects.apply(&effect(
+            StatusEffectType::Slow,
+            2.0,
+            0.8,
+            StatusEffectStacking::Refresh,
+        ));
+        assert_eq!(status_effects.movement_multiplier(), 0.8);
+
+        status_effects.update(secs(1.5));
+        assert_eq!(status_effects.movement_multiplier(), 0.8);
+        status_effects.update(secs(0.5));
+        assert_eq!(status_effects.movement_multiplier(), 1.0);
+    }
+
+    #[test]
+    fn test_extend_adds_to_remaining_duration() {
+        let mut status_effects = StatusEffectsComponent::default();
+        let stun = effect(
+            StatusEffectType::Stun,
+            2.0,
+            1.0,
+            StatusEffectStacking::Extend,
+        );
+        status_effects.apply(&stun);
+        status_effects.update(secs(1.5));
+        status_effects.apply(&stun);
+
+        // 0.5 seconds were remaining, plus 2.0 seconds from the extension
+        status_effects.update(secs(2.25));
+        assert!(status_effects.is_stunned());
+        status_effects.update(secs(0.25));
+        assert!(!status_effects.is_stunned());
+    }
+
+    #[test]
+    fn test_stack_multiplies_magnitude_up_to_max_stacks() {
+        let mut status_effects = StatusEffectsComponent::default();
+        let slow = effect(
+            StatusEffectType::Slow,
+            2.0,
+            0.5,
+            StatusEffectStacking::Stack { max_stacks: 2 },
+        );
+
+        status_effects.apply(&slow);
+        assert_eq!(status_effects.movement_multiplier(), 0.5);
+        status_effects.apply(&slow);
+        assert_eq!(status_effects.movement_multiplier(), 0.25);
+        status_effects.apply(&slow);
+        assert_eq!(status_effects.movement_multiplier(), 0.25);
+    }
+
+    #[test]
+    fn test_stack_resets_duration() {
+        let mut status_effects = StatusEffectsComponent::default();
+        let vulnerable = effect(
+            StatusEffectType::Vulnerable,
+            2.0,
+            1.5,
+            StatusEffectStacking::Stack { max_stacks: 3 },
+        );
+
+        status_effects.apply(&vulnerable);
+        status_effects.update(secs(1.5));
+        status_effects.apply(&vulnerable);
+        assert_eq!(status_effects.damage_taken_multiplier(), 2.25);
+
+        status_effects.update(secs(1.5));
+        assert_eq!(status_effects.damage_taken_multiplier(), 2.25);
+    }
+
+    #[test]
+    fn test_ignore_keeps_existing_effect() {
+        let mut status_effects = StatusEffectsComponent::default();
+        status_effects.apply(&effect(
+            StatusEffectType::Slow,
+            2.0,
+            0.5,
+            StatusEffectStacking::Ignore,
+        ));
+        status_effects.update(secs(1.5));
+        status_effects.apply(&effect(
+            StatusEffectType::Slow,
+            5.0,
+            0.1,
+            StatusEffectStacking::Ignore,
+        ));
+        assert_eq!(status_effects.movement_multiplier(), 0.5);
+
+        status_effects.update(secs(0.5));
+        assert_eq!(status_effects.movement_multiplier(), 1.0);
+    }
+
+    #[test]
+    fn test_burn_deals_damage_every_tick() {
+        let mut status_effects = StatusEffectsComponent::default();
+        let burn = effect(
+            StatusEffectType::Burn,
+            3.0,
+            2.0,
+            StatusEffectStacking::Stack { max_stacks: 5 },
+        );
+        status_effects.apply(&burn);
+
+        assert_eq!(status_effects.update(secs(0.5)), 0);
+        assert_eq!(status_effects.update(secs(0.5)), 2);
+
+        // each stack adds its magnitude to the damage of a tick
+        status_effects.apply(&burn);
+        assert_eq!(status_effects.update(secs(1.0)), 4);
+
+        // several ticks can happen in one update
+        assert_eq!(status_effects.update(secs(2.0)), 8);
+        assert!(status_effects.active_types().is_empty());
+    }
+
+    #[test]
+    fn test_movement_multiplier_combines_slows_and_freezes() {
+        let mut status_effects = StatusEffectsComponent::default();
+        assert_eq!(status_effects.movement_multiplier(), 1.0);
+
+        status_effects.apply(&effect(
+            StatusEffectType::Slow,
+            2.0,
+            0.5,
+            StatusEffectStacking::Refresh,
+        ));
+        status_effects.apply(&effect(
+            StatusEffectType::Burn,
+            2.0,
+            3.0,
+            StatusEffectStacking::Refresh,
+        ));
+        assert_eq!(status_effects.movement_multiplier(), 0.5);
+
+        status_effects.apply(&effect(
+            StatusEffectType::Freeze,
+            1.0,
+            1.0,
+            StatusEffectStacking::Refresh,
+        ));
+        assert_eq!(status_effects.movement_multiplier(), 0.0);
+    }
+
+    #[test]
+    fn test_stunned_by_stuns_and_freezes() {
+        let mut status_effects = StatusEffectsComponent::default();
+        status_effects.apply(&effect(
+            StatusEffectType::Slow,
+            2.0,
+            0.5,
+            StatusEffectStacking::Refresh,
+        ));
+        assert!(!status_effects.is_stunned());
+
+        status_effects.apply(&effect(
+            StatusEffectType::Freeze,
+            1.0,
+            1.0,
+            StatusEffectStacking::Refresh,
+        ));
+        assert!(status_effects.is_stunned());
+
+        status_effects.clear();
+        assert!(!status_effects.is_stunned());
+    }
+
+    #[test]
+    fn test_active_types_are_sorted_by_priority() {
+        let mut status_effects = StatusEffectsComponent::default();
+        for effect_type in [
+            StatusEffectType::Vulnerable,
+            StatusEffectType::Burn,
+            StatusEffectType::Freeze,
+        ] {
+            status_effects.apply(&effect(
+                effect_type,
+                1.0,
+                1.0,
+                StatusEffectStacking::Refresh,
+            ));
+        }
+
+        assert_eq!(
+            status_effects.active_types(),
+            vec![
+                StatusEffectType::Freeze,
+                StatusEffectType::Burn,
+                StatusEffectType::Vulnerable
+            ]
+        );
+    }
+}
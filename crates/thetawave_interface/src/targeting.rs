@@ -0,0 +1,47 @@
+//! Configuration for how mobs and projectiles choose which entity to target
+use serde::Deserialize;
+
+/// How a target is chosen from the entities of hostile factions
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum TargetingPolicy {
+    /// Target the closest hostile entity
+    #[default]
+    Nearest,
+    /// Target the hostile entity with the least health, the closest one breaks ties
+    LowestHealth,
+    /// Target a random hostile entity, kept until it is no longer targetable
+    Random,
+    /// Target the hostile entity that most recently dealt damage, starting with the closest one
+    LastAttacker,
+    /// Cycle through the hostile entities, switching targets every alternate period
+    Alternate,
+}
+
+fn default_alternate_period() -> f32 {
+    5.0
+}
+
+/// Describes how a mob chooses its target
+#[derive(Deserialize, Clone, Debug)]
+pub struct TargetingData {
+    /// How the target is chosen
+    #[serde(default)]
+    pub policy: TargetingPolicy,
+    /// Damage that another hostile entity has to deal to draw the mob's aggro and become its target,
+    /// never switches on damage if `None`
+    #[serde(default)]
+    pub aggro_threshold: Option<usize>,
+    /// Time in seconds between switching targets with the `Alternate` policy
+    #[serde(default = "default_alternate_period")]
+    pub alternate_period: f32,
+}
+
+impl Default for TargetingData {
+    fn default() -> Self {
+        Self {
+            policy: TargetingPolicy::default(),
+            aggro_threshold: None,
+            alternate_period: default_alternate_period(),
+        }
+    }
+}
@@ -5,7 +5,10 @@ use serde::Deserialize;
 
 use crate::{
     audio::SoundEffectType,
-    spawnable::{ProjectileType, SpawnPosition},
+    health::DamageType,
+    spawnable::{Faction, ProjectileType, SpawnPosition},
+    status_effect::StatusEffectData,
+    targeting::TargetingPolicy,
 };
 
 use std::{ops::Range, time::Duration};
@@ -20,6 +23,14 @@ pub enum FireMode {
 pub enum SpreadPattern {
     Arc(ArcPatternData),
     Random(RandomPatternData),
+    /// Projectiles are evenly spaced in a full circle
+    Ring,
+    /// Projectiles are evenly spaced in a full circle that rotates on every shot
+    Spiral(SpiralPatternData),
+    /// Projectiles are evenly spaced in an arc, with the angle and speed of each shot modulated by a sine wave
+    Wave(WavePatternData),
+    /// Projectiles are evenly spaced in an arc aimed at the nearest hostile target
+    AimedBurst(AimedBurstPatternData),
 }
 
 #[derive(Deserialize, Clone)]
@@ -38,6 +49,51 @@ pub struct RandomPatternData {
     pub angle_range: Range<f32>,
 }
 
+#[derive(Deserialize, Clone)]
+pub struct SpiralPatternData {
+    /// Angle in radians that the pattern rotates after each shot
+    pub angle_step: f32,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct WavePatternData {
+    /// Angle in radians between the first and last projectile of a shot
+    pub spread: f32,
+    /// Maximum angle in radians added to the direction of a shot
+    #[serde(default)]
+    pub angle_amplitude: f32,
+    /// Maximum fraction of the speed added to or removed from a shot
+    #[serde(default)]
+    pub speed_amplitude: f32,
+    /// Radians that the phase of the wave advances after each shot
+    pub phase_step: f32,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct AimedBurstPatternData {
+    /// Angle in radians between the first and last projectile of a shot
+    pub spread: f32,
+}
+
+/// Describes how a projectile steers toward a hostile target
+#[derive(Deserialize, Clone, Debug)]
+pub struct HomingData {
+    /// How the target is chosen from the hostile entities in range
+    /// `LastAttacker` and `Alternate` have no effect on projectiles and target the nearest entity
+    #[serde(default)]
+    pub policy: TargetingPolicy,
+    /// Maximum angle in radians per second that the projectile can turn
+    pub turn_rate: f32,
+    /// Maximum distance to a target that can be acquired
+    pub acquisition_range: f32,
+    /// Time in seconds between searches for a closer target, never retargets if `None`
+    #[serde(default)]
+    pub retarget_period: Option<f32>,
+    /// Time in seconds after spawning before the projectile starts homing
+    #[serde(default)]
+    pub delay: f32,
+}
+
 /// Stores data about about a Weapon using minimal defining characteristics
 #[derive(Deserialize, Clone)]
 pub struct WeaponData {
@@ -75,12 +131,88 @@ pub struct WeaponProjectileData {
     pub size: f32,
     /// Sound that the weapon makes when fired
     pub sound: SoundEffectType,
+    /// Makes spawned projectiles steer toward hostile targets
+    #[serde(default)]
+    pub homing: Option<HomingData>,
+    /// Additional targets that spawned projectiles pierce through
+    #[serde(default)]
+    pub extra_pierce: usize,
+    /// Additional times that spawned projectiles ricochet
+    #[serde(default)]
+    pub extra_ricochet: usize,
+    /// Status effects applied to targets hit by spawned projectiles
+    #[serde(default)]
+    pub status_effects: Vec<StatusEffectData>,
+}
+
+fn default_beam_damage_type() -> DamageType {
+    DamageType::Energy
+}
+
+/// Describes a continuous beam that damages the first target along its length
+#[derive(Deserialize, Clone, Debug)]
+pub struct BeamData {
+    /// Faction of the beam, the beam passes through entities of the same faction
+    pub faction: Faction,
+    /// Damage dealt every tick to the target hit by the beam
+    pub damage: usize,
+    /// Type of the damage dealt by the beam
+    #[serde(default = "default_beam_damage_type")]
+    pub damage_type: DamageType,
+    /// Time in seconds between damage ticks
+    pub tick_period: f32,
+    /// Position of the start of the beam, either relative to the source or global
+    pub position: SpawnPosition,
+    /// Angle in radians of the beam
+    pub direction: f32,
+    /// Maximum length of the beam
+    pub length: f32,
+    /// Width of the beam while it is sustained
+    pub width: f32,
+    /// Time in seconds before the beam starts dealing damage
+    pub warmup_time: f32,
+    /// Time in seconds that the beam deals damage
+    pub sustain_time: f32,
+    /// Time in seconds for the beam to fade after it stops dealing damage
+    pub cooldown_time: f32,
+    /// Sound that plays when the beam is fired
+    pub sound: SoundEffectType,
+}
+
+/// Stores data about a weapon that fires beams
+#[derive(Deserialize, Clone)]
+pub struct BeamWeaponData {
+    /// Time between firing beams, starting when the beam is fired
+    pub reload_time: f32,
+    /// Initial delay before the first beam can be fired
+    pub initial_time: f32,
+    /// Data about the beams fired from the weapon
+    pub beam_data: BeamData,
+}
+
+impl WeaponProjectileData {
+    /// Apply modulation from spread patterns that change from shot to shot
+    /// `shot` is the number of shots fired before this one
+    pub fn modulate_for_shot(&mut self, shot: usize) {
+        match &self.spread_pattern {
+            SpreadPattern::Spiral(spiral_pattern) => {
+                self.direction += spiral_pattern.angle_step * shot as f32;
+            }
+            SpreadPattern::Wave(wave_pattern) => {
+                let wave = (wave_pattern.phase_step * shot as f32).sin();
+                self.direction += wave_pattern.angle_amplitude * wave;
+                self.speed *= 1.0 + wave_pattern.speed_amplitude * wave;
+            }
+            _ => {}
+        }
+    }
 }
 
 /// Describes how projectiles are spawned
 #[derive(Component, Clone)]
 pub struct WeaponsComponent {
     pub weapons: Vec<Weapon>,
+    pub beam_weapons: Vec<BeamWeapon>,
 }
 
 impl From<Vec<WeaponData>> for WeaponsComponent {
@@ -90,21 +222,96 @@ impl From<Vec<WeaponData>> for WeaponsComponent {
             .map(|weapon_data| Weapon::from(weapon_data))
             .collect();
 
-        WeaponsComponent { weapons }
+        WeaponsComponent {
+            weapons,
+            beam_weapons: vec![],
+        }
     }
 }
 
 impl WeaponsComponent {
+    /// Create a component from optional projectile and beam weapons
+    /// Returns `None` if there are no weapons of either type
+    pub fn new(
+        weapons: Option<Vec<WeaponData>>,
+        beam_weapons: Option<Vec<BeamWeaponData>>,
+    ) -> Option<Self> {
+        if weapons.is_none() && beam_weapons.is_none() {
+            return None;
+        }
+
+        Some(WeaponsComponent {
+            beam_weapons: beam_weapons
+                .unwrap_or_default()
+                .iter()
+                .map(BeamWeapon::from)
+                .collect(),
+            ..WeaponsComponent::from(weapons.unwrap_or_default())
+        })
+    }
+
     pub fn enable_all(&mut self) {
         self.weapons
             .iter_mut()
             .for_each(|weapon| weapon.is_enabled = true);
+        self.beam_weapons
+            .iter_mut()
+            .for_each(|beam_weapon| beam_weapon.is_enabled = true);
     }
 
     pub fn disable_all(&mut self) {
         self.weapons
             .iter_mut()
             .for_each(|weapon| weapon.is_enabled = false);
+        self.beam_weapons
+            .iter_mut()
+            .for_each(|beam_weapon| beam_weapon.is_enabled = false);
+    }
+}
+
+/// A weapon that automatically fires beams
+#[derive(Clone)]
+pub struct BeamWeapon {
+    /// Tracks time until the next beam can be fired
+    pub reload_timer: Timer,
+    /// Initial delay before the first beam can be fired
+    pub initial_timer: Timer,
+    /// Whether weapon is enabled
+    pub is_enabled: bool,
+    /// Data about the beams fired from the weapon
+    pub beam_data: BeamData,
+}
+
+impl From<&BeamWeaponData> for BeamWeapon {
+    fn from(value: &BeamWeaponData) -> Self {
+        BeamWeapon {
+            reload_timer: Timer::from_seconds(value.reload_time, TimerMode::Repeating),
+            initial_timer: Timer::from_seconds(value.initial_time, TimerMode::Once),
+            is_enabled: true,
+            beam_data: value.beam_data.clone(),
+        }
+    }
+}
+
+impl BeamWeapon {
+    /// Tick the weapon's timers, and return the beam data if a beam should be fired
+    pub fn update(&mut self, delta_time: Duration) -> Option<BeamData> {
+        if !self.is_enabled {
+            return None;
+        }
+
+        if !self.initial_timer.finished() {
+            self.initial_timer.tick(delta_time);
+            return self
+                .initial_timer
+                .just_finished()
+                .then(|| self.beam_data.clone());
+        }
+
+        self.reload_timer
+            .tick(delta_time)
+            .just_finished()
+            .then(|| self.beam_data.clone())
     }
 }
 
@@ -124,6 +331,8 @@ pub struct Weapon {
     pub is_enabled: bool,
     /// Data about the projectiles fired from the weapon
     pub projectile_data: WeaponProjectileData,
+    /// Number of times the weapon has been fired, used by spread patterns that change every shot
+    pub shots_fired: usize,
 }
 
 impl From<&WeaponData> for Weapon {
@@ -136,6 +345,7 @@ impl From<&WeaponData> for Weapon {
             capacity: value.capacity,
             projectile_data: value.projectile_data.clone(),
             is_enabled: true,
+            shots_fired: 0,
         }
     }
 }
@@ -157,12 +367,20 @@ impl Weapon {
     pub fn fire_weapon(&mut self) -> Option<WeaponProjectileData> {
         if self.can_fire() && self.is_enabled {
             self.reload_timer.reset();
-            Some(self.projectile_data.clone())
+            Some(self.next_shot())
         } else {
             None
         }
     }
 
+    /// Returns the projectile data for the next shot, regardless of the reload timer
+    pub fn next_shot(&mut self) -> WeaponProjectileData {
+        let mut projectile_data = self.projectile_data.clone();
+        projectile_data.modulate_for_shot(self.shots_fired);
+        self.shots_fired += 1;
+        projectile_data
+    }
+
     /// Gain projectiles, but limit to the capacity of the weapon
     pub fn gain_projectiles(&mut self, projectiles: usize) {
         self.projectile_data.count = (self.projectile_data.count + projectiles).min(self.capacity);
@@ -174,3 +392,155 @@ impl Weapon {
             .set_duration(Duration::from_secs_f32(new_reload_time_seconds));
     }
 }
+
+#[cfg(test)]
+mod test {
+    use bevy_math::Vec2;
+    use std::time::Duration;
+
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::{
+        AimedBurstPatternData, BeamData, BeamWeapon, BeamWeaponData, SpiralPatternData,
+        SpreadPattern, WavePatternData, WeaponProjectileData,
+    };
+    use crate::{
+        audio::SoundEffectType,
+        health::DamageType,
+        spawnable::{Faction, ProjectileType, SpawnPosition},
+    };
+
+    fn beam_weapon(reload_time: f32, initial_time: f32) -> BeamWeapon {
+        BeamWeapon::from(&BeamWeaponData {
+            reload_time,
+            initial_time,
+            beam_data: BeamData {
+                faction: Faction::Enemy,
+                damage: 2,
+                damage_type: DamageType::Energy,
+                tick_period: 0.1,
+                position: SpawnPosition::Local(Vec2::ZERO),
+                direction: 0.0,
+                length: 800.0,
+                width: 6.0,
+                warmup_time: 1.0,
+                sustain_time: 2.0,
+                cooldown_time: 0.5,
+                sound: SoundEffectType::EnemyFireBlast,
+            },
+        })
+    }
+
+    fn secs(seconds: f32) -> Duration {
+        Duration::from_secs_f32(seconds)
+    }
+
+    #[test]
+    fn test_beam_weapon_fires_after_initial_time_then_every_reload() {
+        let mut weapon = beam_weapon(2.0, 1.0);
+
+        assert!(weapon.update(secs(0.5)).is_none());
+        assert!(weapon.update(secs(0.5)).is_some());
+
+        assert!(weapon.update(secs(1.5)).is_none());
+        assert!(weapon.update(secs(0.5)).is_some());
+        assert!(weapon.update(secs(1.0)).is_none());
+        assert!(weapon.update(secs(1.0)).is_some());
+    }
+
+    #[test]
+    fn test_disabled_beam_weapon_does_not_fire() {
+        let mut weapon = beam_weapon(2.0, 1.0);
+        weapon.is_enabled = false;
+
+        assert!(weapon.update(secs(3.0)).is_none());
+
+        // timers don't advance while the weapon is disabled
+        weapon.is_enabled = true;
+        assert!(weapon.update(secs(0.5)).is_none());
+        assert!(weapon.update(secs(0.5)).is_some());
+    }
+
+    fn projectile_data(spread_pattern: SpreadPattern) -> WeaponProjectileData {
+        WeaponProjectileData {
+            ammunition: ProjectileType::Blast(Faction::Enemy),
+            damage: 5,
+            position: SpawnPosition::Local(Vec2::ZERO),
+            speed: 100.0,
+            direction: 1.0,
+            despawn_time: 1.0,
+            count: 3,
+            spread_pattern,
+            size: 1.0,
+            sound: SoundEffectType::EnemyFireBlast,
+            homing: None,
+            extra_pierce: 0,
+            extra_ricochet: 0,
+            status_effects: vec![],
+        }
+    }
+
+    /// Direction and speed of a shot after modulation
+    fn modulated(spread_pattern: SpreadPattern, shot: usize) -> (f32, f32) {
+        let mut projectile_data = projectile_data(spread_pattern);
+        projectile_data.modulate_for_shot(shot);
+        (projectile_data.direction, projectile_data.speed)
+    }
+
+    fn assert_shot(actual: (f32, f32), expected: (f32, f32)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-4 && (actual.1 - expected.1).abs() < 1e-3,
+            "shot fired with {actual:?} instead of {expected:?}"
+        );
+    }
+
+    #[test]
+    fn test_spiral_rotates_every_shot() {
+        let spiral = || SpreadPattern::Spiral(SpiralPatternData { angle_step: 0.5 });
+
+        assert_shot(modulated(spiral(), 0), (1.0, 100.0));
+        assert_shot(modulated(spiral(), 1), (1.5, 100.0));
+        assert_shot(modulated(spiral(), 3), (2.5, 100.0));
+    }
+
+    #[test]
+    fn test_wave_modulates_direction_and_speed() {
+        let wave = || {
+            SpreadPattern::Wave(WavePatternData {
+                spread: 1.0,
+                angle_amplitude: 0.4,
+                speed_amplitude: 0.5,
+                phase_step: FRAC_PI_2,
+            })
+        };
+
+        assert_shot(modulated(wave(), 0), (1.0, 100.0));
+        assert_shot(modulated(wave(), 1), (1.4, 150.0));
+        assert_shot(modulated(wave(), 2), (1.0, 100.0));
+        assert_shot(modulated(wave(), 3), (0.6, 50.0));
+    }
+
+    #[test]
+    fn test_wave_without_amplitudes_is_not_modulated() {
+        let wave = SpreadPattern::Wave(WavePatternData {
+            spread: 1.0,
+            angle_amplitude: 0.0,
+            speed_amplitude: 0.0,
+            phase_step: FRAC_PI_2,
+        });
+
+        assert_shot(modulated(wave, 1), (1.0, 100.0));
+    }
+
+    #[test]
+    fn test_ring_and_aimed_burst_are_not_modulated() {
+        assert_shot(modulated(SpreadPattern::Ring, 5), (1.0, 100.0));
+        assert_shot(
+            modulated(
+                SpreadPattern::AimedBurst(AimedBurstPatternData { spread: 0.6 }),
+                5,
+            ),
+            (1.0, 100.0),
+        );
+    }
+}
@@ -45,7 +45,9 @@ pub(super) fn setup_db(conn: Connection) -> rusqlite::Result<()> {
         userId INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL ,
         totalShotsFired  INTEGER NOT NULL DEFAULT 0,
         totalShotsHit  INTEGER NOT NULL DEFAULT 0,
-        totalGamesLost INTEGER NOT NULL DEFAULT 0
+        totalGamesLost INTEGER NOT NULL DEFAULT 0,
+        totalDowns INTEGER NOT NULL DEFAULT 0,
+        totalRevives INTEGER NOT NULL DEFAULT 0
     )"
     );
 
@@ -68,6 +70,7 @@ pub(super) fn setup_db(conn: Connection) -> rusqlite::Result<()> {
     );
 
     conn.execute(&create_user_stats_sql, []).map(|_| ())?;
+    add_missing_user_stats_columns(&conn)?;
     conn.execute(&create_enemies_killed_table_sql, [])
         .map(|_| ())?;
     conn.execute(&create_options_table_sql, []).map(|_| ())?;
@@ -82,6 +85,28 @@ pub(super) fn setup_db(conn: Connection) -> rusqlite::Result<()> {
     Ok(())
 }
 
+/// Columns added to the user stats table after it was first created. Databases created by older releases are
+/// migrated by adding these columns.
+const ADDED_USER_STATS_COLUMNS: [&str; 2] = ["totalDowns", "totalRevives"];
+
+fn add_missing_user_stats_columns(conn: &Connection) -> rusqlite::Result<()> {
+    let existing_columns = conn
+        .prepare(&format!("PRAGMA table_info({USERSTAT})"))?
+        .query_map([], |r| r.get::<usize, String>(1))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+
+    for column in ADDED_USER_STATS_COLUMNS {
+        if !existing_columns.iter().any(|c| c == column) {
+            conn.execute(
+                &format!("ALTER TABLE {USERSTAT} ADD COLUMN {column} INTEGER NOT NULL DEFAULT 0"),
+                [],
+            )?;
+            info!("Added column {column} to {USERSTAT}");
+        }
+    }
+    Ok(())
+}
+
 pub(super) fn get_db() -> Result<Connection, OurDBError> {
     let db_path = match var_os(OsStr::new(THETAWAVE_DB_PATH_ENVVAR)) {
         Some(osstr) => Ok(PathBuf::from(osstr)),
@@ -89,3 +114,58 @@ pub(super) fn get_db() -> Result<Connection, OurDBError> {
     }?;
     Connection::open(db_path).map_err(OurDBError::from)
 }
+
+#[cfg(test)]
+mod test {
+    use super::{setup_db, USERSTAT};
+    use rusqlite::Connection;
+
+    #[test]
+    fn test_setup_db_adds_missing_user_stats_columns() {
+        let base_path = tempdir::TempDir::new("thetawave-tests").unwrap();
+        let db_path = base_path.path().join("thetawave_test.sqlite");
+
+        // user stats table from a release before downs and revives were tracked
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute(
+            &format!(
+                "CREATE TABLE {USERSTAT} (
+                userId INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL ,
+                totalShotsFired  INTEGER NOT NULL DEFAULT 0,
+                totalShotsHit  INTEGER NOT NULL DEFAULT 0,
+                totalGamesLost INTEGER NOT NULL DEFAULT 0
+            )"
+            ),
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            &format!("INSERT INTO {USERSTAT} (userId, totalShotsFired) VALUES (0, 7)"),
+            [],
+        )
+        .unwrap();
+        drop(conn);
+
+        setup_db(Connection::open(&db_path).unwrap()).unwrap();
+        // setting up an already migrated db doesn't add the columns again
+        setup_db(Connection::open(&db_path).unwrap()).unwrap();
+
+        let conn = Connection::open(&db_path).unwrap();
+        let (shots_fired, downs, revives) = conn
+            .query_row(
+                &format!(
+                    "SELECT totalShotsFired, totalDowns, totalRevives FROM {USERSTAT} WHERE userId=0"
+                ),
+                [],
+                |r| {
+                    Ok((
+                        r.get::<usize, usize>(0)?,
+                        r.get::<usize, usize>(1)?,
+                        r.get::<usize, usize>(2)?,
+                    ))
+                },
+            )
+            .unwrap();
+        assert_eq!((shots_fired, downs, revives), (7, 0, 0));
+    }
+}
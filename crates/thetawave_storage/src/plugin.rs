@@ -198,6 +198,8 @@ mod test {
         const N_GAMES_LOST: usize,
         const TOTAL_SHOTS_HIT: usize,
         const TOTAL_SHOTS_FIRED: usize,
+        const TOTAL_DOWNS: usize,
+        const TOTAL_REVIVES: usize,
     >(
         mut historical_user_stats: ResMut<UserStatsByPlayerForCompletedGamesCache>,
     ) {
@@ -207,6 +209,8 @@ mod test {
                 total_shots_fired: TOTAL_SHOTS_FIRED,
                 total_shots_hit: TOTAL_SHOTS_HIT,
                 total_games_lost: N_GAMES_LOST,
+                total_downs: TOTAL_DOWNS,
+                total_revives: TOTAL_REVIVES,
             },
         );
     }
@@ -230,6 +234,8 @@ mod test {
         const N_GAMES_PLAYED: usize = 2;
         const TOTAL_SHOTS_HIT: usize = 10;
         const TOTAL_SHOTS_FIRED: usize = 15;
+        const TOTAL_DOWNS: usize = 3;
+        const TOTAL_REVIVES: usize = 1;
 
         let mob_kills_after_1_game =
             MobKillsByPlayerForCompletedGames::from(MobsKilledByPlayerCacheT::from([(
@@ -246,6 +252,8 @@ mod test {
                         N_GAMES_PLAYED,
                         TOTAL_SHOTS_HIT,
                         TOTAL_SHOTS_FIRED,
+                        TOTAL_DOWNS,
+                        TOTAL_REVIVES,
                     >,
                 ),
             )
@@ -262,6 +270,8 @@ mod test {
                 total_shots_fired: TOTAL_SHOTS_FIRED,
                 total_shots_hit: TOTAL_SHOTS_HIT,
                 total_games_lost: N_GAMES_PLAYED,
+                total_downs: TOTAL_DOWNS,
+                total_revives: TOTAL_REVIVES,
             }
         );
         assert_eq!(
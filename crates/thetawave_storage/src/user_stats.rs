@@ -11,9 +11,9 @@ pub(super) fn set_user_stats_for_user_id(
 ) -> Result<(), OurDBError> {
     let stmt_raw = format!(
         "
-    INSERT OR REPLACE INTO {USERSTAT} (userId, totalShotsFired, totalGamesLost, totalShotsHit)
-    VALUES (?1,  ?2, ?3, ?4)
-    ON CONFLICT DO UPDATE SET totalShotsFired=?2, totalGamesLost=?3, totalShotsHit=?4"
+    INSERT OR REPLACE INTO {USERSTAT} (userId, totalShotsFired, totalGamesLost, totalShotsHit, totalDowns, totalRevives)
+    VALUES (?1,  ?2, ?3, ?4, ?5, ?6)
+    ON CONFLICT DO UPDATE SET totalShotsFired=?2, totalGamesLost=?3, totalShotsHit=?4, totalDowns=?5, totalRevives=?6"
     );
     let conn = get_db()?;
     info!(
@@ -25,6 +25,8 @@ pub(super) fn set_user_stats_for_user_id(
         user_stats.total_shots_fired,
         user_stats.total_games_lost,
         user_stats.total_shots_hit,
+        user_stats.total_downs,
+        user_stats.total_revives,
     ])?;
     Ok(())
 }
@@ -32,7 +34,7 @@ fn _get_user_stats(user_id: usize) -> Result<Option<UserStat>, OurDBError> {
     let conn = get_db()?;
     let stmt_raw = format!(
         "
-    SELECT totalGamesLost, totalShotsFired, totalShotsHit FROM  {USERSTAT}
+    SELECT totalGamesLost, totalShotsFired, totalShotsHit, totalDowns, totalRevives FROM  {USERSTAT}
     WHERE userId=?1"
     );
     let mut stmt = conn.prepare(&stmt_raw)?;
@@ -42,10 +44,14 @@ fn _get_user_stats(user_id: usize) -> Result<Option<UserStat>, OurDBError> {
             let total_games_lost = r.get(0)?;
             let total_shots_fired = r.get(1)?;
             let total_shots_hit = r.get(2)?;
+            let total_downs = r.get(3)?;
+            let total_revives = r.get(4)?;
             Ok(Some(UserStat {
                 total_games_lost,
                 total_shots_fired,
                 total_shots_hit,
+                total_downs,
+                total_revives,
             }))
         }
 
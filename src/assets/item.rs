@@ -22,30 +22,44 @@ impl ItemAssets {
         item_type: &ItemType,
     ) -> Handle<TextureAtlasLayout> {
         match item_type {
-            ItemType::EnhancedPlating => self.item_placeholder_layout.clone(),
-            /*
-            ItemType::SteelBarrel => self.item_placeholder.clone(),
-            ItemType::PlasmaBlasts => self.item_placeholder.clone(),
-            ItemType::HazardousReactor => self.item_placeholder.clone(),
-            ItemType::WarpThruster => self.item_placeholder.clone(),
-            ItemType::Tentaclover => self.item_placeholder.clone(),
-            ItemType::DefenseSatellite => self.item_placeholder.clone(),
-            ItemType::DoubleBarrel => self.item_placeholder.clone(),
-            ItemType::YithianPlague => self.item_placeholder.clone(),
-            ItemType::Spice => self.item_placeholder.clone(),
-            ItemType::StructureReinforcement => self.item_placeholder.clone(),
-            ItemType::BlasterSizeEnhancer => self.item_placeholder.clone(),
-            ItemType::FrequencyAugmentor => self.item_placeholder.clone(),
-            ItemType::TractorBeam => self.item_placeholder.clone(),
-            ItemType::BlastRepeller => self.item_placeholder.clone(),
-            */
+            ItemType::EnhancedPlating
+            | ItemType::SteelBarrel
+            | ItemType::PlasmaBlasts
+            | ItemType::HazardousReactor
+            | ItemType::WarpThruster
+            | ItemType::Tentaclover
+            | ItemType::DefenseSatellite
+            | ItemType::DoubleBarrel
+            | ItemType::YithianPlague
+            | ItemType::Spice
+            | ItemType::StructureReinforcement
+            | ItemType::BlasterSizeEnhancer
+            | ItemType::FrequencyAugmentor
+            | ItemType::SpareHull
+            | ItemType::WeaponCalibrator
+            | ItemType::AbilityCalibrator => self.item_placeholder_layout.clone(),
         }
     }
 
     /// Use a ItemType enum to access an item image handle
     pub(crate) fn get_image(&self, item_type: &ItemType) -> Handle<Image> {
         match item_type {
-            ItemType::EnhancedPlating => self.item_placeholder_image.clone(),
+            ItemType::EnhancedPlating
+            | ItemType::SteelBarrel
+            | ItemType::PlasmaBlasts
+            | ItemType::HazardousReactor
+            | ItemType::WarpThruster
+            | ItemType::Tentaclover
+            | ItemType::DefenseSatellite
+            | ItemType::DoubleBarrel
+            | ItemType::YithianPlague
+            | ItemType::Spice
+            | ItemType::StructureReinforcement
+            | ItemType::BlasterSizeEnhancer
+            | ItemType::FrequencyAugmentor
+            | ItemType::SpareHull
+            | ItemType::WeaponCalibrator
+            | ItemType::AbilityCalibrator => self.item_placeholder_image.clone(),
         }
     }
 }
@@ -1,34 +1,36 @@
-use bevy::prelude::{Handle, Image, Res, Resource};
+use bevy::log::error;
+use bevy::prelude::{Handle, Image, Resource};
+use bevy::utils::HashMap;
 use bevy_asset_loader::prelude::AssetCollection;
-use thetawave_interface::character::CharacterType;
+use thetawave_interface::character::Character;
 
 /// Collection of images for player characters
 #[derive(AssetCollection, Resource)]
 pub(crate) struct PlayerAssets {
-    #[asset(key = "captain")]
-    pub captain: Handle<Image>,
-    #[asset(key = "juggernaut")]
-    pub juggernaut: Handle<Image>,
-    #[asset(key = "captain_outline")]
-    pub captain_outline: Handle<Image>,
-    #[asset(key = "juggernaut_outline")]
-    pub juggernaut_outline: Handle<Image>,
+    /// Character sprites and outlines mapped to their paths
+    #[asset(key = "characters", collection(typed, mapped))]
+    pub characters: HashMap<String, Handle<Image>>,
 }
 
 impl PlayerAssets {
-    /// Use a CharacterType enum to access an image handle
-    pub(crate) fn get_asset(&self, character_type: &CharacterType) -> Handle<Image> {
-        match character_type {
-            CharacterType::Captain => self.captain.clone(),
-            CharacterType::Juggernaut => self.juggernaut.clone(),
-        }
+    /// Use a Character to access its image handle
+    pub(crate) fn get_asset(&self, character: &Character) -> Handle<Image> {
+        self.get_image(character, &character.sprite)
     }
 
-    /// Use a CharacterType enum to access a character's associated outline image handle
-    pub(crate) fn get_outline_asset(&self, character_type: &CharacterType) -> Handle<Image> {
-        match character_type {
-            CharacterType::Captain => self.captain_outline.clone(),
-            CharacterType::Juggernaut => self.juggernaut_outline.clone(),
-        }
+    /// Use a Character to access its associated outline image handle
+    pub(crate) fn get_outline_asset(&self, character: &Character) -> Handle<Image> {
+        self.get_image(character, &character.outline_sprite)
+    }
+
+    /// Image loaded from the path, logging an error and using an empty image if the path wasn't loaded
+    fn get_image(&self, character: &Character, path: &str) -> Handle<Image> {
+        self.characters.get(path).cloned().unwrap_or_else(|| {
+            error!(
+                "Image \"{}\" of character {} is not in the \"characters\" collection of player_assets.assets.ron",
+                path, character.name
+            );
+            Handle::default()
+        })
     }
 }
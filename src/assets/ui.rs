@@ -50,6 +50,20 @@ pub(crate) struct UiAssets {
     pub standard_blast_ability: Handle<Image>,
     #[asset(key = "ability_icon.standard_bullet")]
     pub standard_bullet_ability: Handle<Image>,
+    #[asset(key = "ability_icon.piercing_blast")]
+    pub piercing_blast_ability: Handle<Image>,
+    #[asset(key = "ability_icon.wave_bullet")]
+    pub wave_bullet_ability: Handle<Image>,
+    #[asset(key = "ability_icon.shield_bubble")]
+    pub shield_bubble_ability: Handle<Image>,
+    #[asset(key = "ability_icon.dash")]
+    pub dash_ability: Handle<Image>,
+    #[asset(key = "ability_icon.missile_salvo")]
+    pub missile_salvo_ability: Handle<Image>,
+    #[asset(key = "ability_icon.decoy")]
+    pub decoy_ability: Handle<Image>,
+    #[asset(key = "ability_icon.beam")]
+    pub beam_ability: Handle<Image>,
     #[asset(key = "ability_slot.left")]
     pub left_ability_slot: Handle<Image>,
     #[asset(key = "ability_slot.right")]
@@ -79,6 +93,8 @@ impl UiAssets {
         match ability_type {
             SlotOneAbilityType::StandardBlast => self.standard_blast_ability.clone(),
             SlotOneAbilityType::StandardBullet => self.standard_bullet_ability.clone(),
+            SlotOneAbilityType::PiercingBlast => self.piercing_blast_ability.clone(),
+            SlotOneAbilityType::WaveBullet => self.wave_bullet_ability.clone(),
         }
     }
 
@@ -88,6 +104,11 @@ impl UiAssets {
         match ability_type {
             SlotTwoAbilityType::MegaBlast => self.mega_blast_ability.clone(),
             SlotTwoAbilityType::Charge => self.charge_ability.clone(),
+            SlotTwoAbilityType::Beam => self.beam_ability.clone(),
+            SlotTwoAbilityType::ShieldBubble => self.shield_bubble_ability.clone(),
+            SlotTwoAbilityType::Dash => self.dash_ability.clone(),
+            SlotTwoAbilityType::HomingMissileSalvo => self.missile_salvo_ability.clone(),
+            SlotTwoAbilityType::Decoy => self.decoy_ability.clone(),
         }
     }
 
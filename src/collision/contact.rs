@@ -308,6 +308,14 @@ pub(super) fn contact_collision_system(
                         continue 'collision_events;
                     }
                 }
+                // check if the projectile collided with a barrier
+                else if barrier_query.get(colliding_entities.secondary).is_ok() {
+                    collision_event_writer.send(SortedCollisionEvent::ProjectileToBarrierContact {
+                        projectile_entity: projectile_entity_1,
+                        barrier_entity: colliding_entities.secondary,
+                    });
+                    continue 'collision_events;
+                }
             }
         }
     }
@@ -1,5 +1,6 @@
-use crate::spawnable::{
-    ConsumableComponent, MobComponent, MobSegmentComponent, ProjectileComponent,
+use crate::{
+    arena::ArenaBarrierComponent,
+    spawnable::{ConsumableComponent, MobComponent, MobSegmentComponent, ProjectileComponent},
 };
 use bevy::prelude::{debug, Entity, EventReader, EventWriter, Query, With};
 use bevy_rapier2d::{prelude::CollisionEvent, rapier::prelude::CollisionEventFlags};
@@ -11,6 +12,7 @@ use thetawave_interface::{
 use super::{CollidingEntityPair, SortedCollisionEvent};
 
 /// Creates events from intersection (sensor) collisions
+#[allow(clippy::too_many_arguments)]
 pub(super) fn intersection_collision_system(
     mut collision_event_writer: EventWriter<SortedCollisionEvent>,
     mut collision_events: EventReader<CollisionEvent>,
@@ -20,6 +22,7 @@ pub(super) fn intersection_collision_system(
     mob_query: Query<(Entity, &MobComponent)>,
     mob_segment_query: Query<(Entity, &MobSegmentComponent)>,
     projectile_query: Query<(Entity, &ProjectileComponent)>,
+    barrier_query: Query<Entity, With<ArenaBarrierComponent>>,
 ) {
     // loop through all collision events
     'collision_events: for collision_event in collision_events.read() {
@@ -141,6 +144,18 @@ pub(super) fn intersection_collision_system(
                     continue 'collision_events;
                 }
             }
+            // check if a projectile intersected with a barrier
+            else if projectile_query.get(colliding_entities.primary).is_ok()
+                && barrier_query.get(colliding_entities.secondary).is_ok()
+            {
+                collision_event_writer.send(
+                    SortedCollisionEvent::ProjectileToBarrierIntersection {
+                        projectile_entity: colliding_entities.primary,
+                        barrier_entity: colliding_entities.secondary,
+                    },
+                );
+                continue 'collision_events;
+            }
         }
     }
 }
@@ -138,6 +138,16 @@ pub enum SortedCollisionEvent {
     MobToBarrierContact {
         mob_entity: Entity,
     },
+
+    // Projectile to barrier
+    ProjectileToBarrierContact {
+        projectile_entity: Entity,
+        barrier_entity: Entity,
+    },
+    ProjectileToBarrierIntersection {
+        projectile_entity: Entity,
+        barrier_entity: Entity,
+    },
 }
 
 /// Stores two colliding entities
@@ -1,15 +1,18 @@
 /// Expose all of the mutations for the within-game metric counters via a bevy plugin.
-use crate::collision::SortedCollisionEvent;
 use crate::spawnable::FireWeaponEvent;
-use bevy::prelude::{debug, App, Entity, EventReader, OnEnter, Plugin, Query, ResMut, Update};
-use thetawave_interface::player::PlayerIDComponent;
+use bevy::prelude::{
+    debug, App, Entity, EventReader, OnEnter, Plugin, Query, ResMut, Update, Without,
+};
+use thetawave_interface::player::{
+    PlayerDownedEvent, PlayerIDComponent, PlayerRevivedEvent, WingmanComponent,
+};
 
 use std::collections::HashMap;
 use thetawave_interface::game::historical_metrics::{
     MobKillsByPlayerForCompletedGames, MobKillsByPlayerForCurrentGame, UserStat,
     UserStatsByPlayerForCompletedGamesCache, UserStatsByPlayerForCurrentGameCache, DEFAULT_USER_ID,
 };
-use thetawave_interface::spawnable::{MobDestroyedEvent, MobType};
+use thetawave_interface::spawnable::{MobDestroyedEvent, MobType, ProjectileHitEvent};
 use thetawave_interface::states::AppStates;
 
 /// Maintains/mutates singleton resources that keep track of metrics for the current game. Mostly
@@ -28,6 +31,7 @@ impl Plugin for CountingMetricsPlugin {
                 inc_in_memory_mob_destroyed_for_current_game_cache,
                 count_shots_fired_by_player_1_system,
                 inc_in_memory_projectile_hits_counter_system,
+                count_player_downs_and_revives_system,
             ),
         );
         app.add_systems(
@@ -73,40 +77,26 @@ fn inc_in_memory_mob_destroyed_for_current_game_cache(
         }
     }
 }
-fn find_player_1<'a, 'b: 'a>(
-    player_query: &'a Query<(Entity, &'b PlayerIDComponent)>,
-) -> Option<Entity> {
+/// Human players whose shots count towards the user's accuracy, wingmen are AI controlled
+type HumanPlayerQuery<'w, 's, 'a> =
+    Query<'w, 's, (Entity, &'a PlayerIDComponent), Without<WingmanComponent>>;
+
+fn find_player_1(player_query: &HumanPlayerQuery) -> Option<Entity> {
     player_query
         .iter()
         .find(|(_, id)| matches!(id, PlayerIDComponent::One))
         .map(|(entity, _)| entity)
 }
-fn mob_projectile_collision_originates_from_entity(
-    collision: &SortedCollisionEvent,
-    entity: &Entity,
-) -> bool {
-    if let Some(projectile_source_id) = match collision {
-        SortedCollisionEvent::MobToProjectileIntersection {
-            projectile_source, ..
-        } => Some(projectile_source),
-        SortedCollisionEvent::MobToProjectileContact {
-            projectile_source, ..
-        } => Some(projectile_source),
-        _ => None,
-    } {
-        return *projectile_source_id == *entity;
-    }
-    false
-}
 fn inc_in_memory_projectile_hits_counter_system(
     mut current_game_user_stats: ResMut<UserStatsByPlayerForCurrentGameCache>,
-    mut collision_event_reader: EventReader<SortedCollisionEvent>,
-    player_query: Query<(Entity, &PlayerIDComponent)>,
+    mut projectile_hit_event_reader: EventReader<ProjectileHitEvent>,
+    player_query: HumanPlayerQuery,
 ) {
     if let Some(player_1_entity_id) = find_player_1(&player_query) {
-        let n_player_1_hit_shots = collision_event_reader
+        // projectiles only send a hit event once, so piercing shots are counted once
+        let n_player_1_hit_shots = projectile_hit_event_reader
             .read()
-            .filter(|c| mob_projectile_collision_originates_from_entity(c, &player_1_entity_id))
+            .filter(|event| event.source == player_1_entity_id)
             .count();
         if let Some(ref mut player_1_user_stats) =
             (**current_game_user_stats).get_mut(&DEFAULT_USER_ID)
@@ -119,13 +109,9 @@ fn inc_in_memory_projectile_hits_counter_system(
 fn count_shots_fired_by_player_1_system(
     mut current_game_user_stats: ResMut<UserStatsByPlayerForCurrentGameCache>,
     mut fire_weapon_event_reader: EventReader<FireWeaponEvent>,
-    query: Query<(Entity, &PlayerIDComponent)>,
+    query: HumanPlayerQuery,
 ) {
-    let maybe_player_1_entity_id = query
-        .iter()
-        .find(|(_, id)| matches!(id, PlayerIDComponent::One))
-        .map(|(x, _)| x);
-    let n_p1_shots_fired = match maybe_player_1_entity_id {
+    let n_p1_shots_fired = match find_player_1(&query) {
         Some(player_1) => fire_weapon_event_reader
             .read()
             .filter(|x| x.source_entity == player_1)
@@ -148,6 +134,20 @@ fn count_shots_fired_by_player_1_system(
             });
     }
 }
+/// Counts co-op downs and revives for the whole team
+fn count_player_downs_and_revives_system(
+    mut current_game_user_stats: ResMut<UserStatsByPlayerForCurrentGameCache>,
+    mut player_downed_event_reader: EventReader<PlayerDownedEvent>,
+    mut player_revived_event_reader: EventReader<PlayerRevivedEvent>,
+) {
+    let n_downs = player_downed_event_reader.read().count();
+    let n_revives = player_revived_event_reader.read().count();
+    if n_downs > 0 || n_revives > 0 {
+        let user_stats = current_game_user_stats.entry(DEFAULT_USER_ID).or_default();
+        user_stats.total_downs += n_downs;
+        user_stats.total_revives += n_revives;
+    }
+}
 /// Analagous to "log rolling" except we merge counters and add integers.
 fn roll_current_game_counters_into_completed_game_metrics(
     mut current_game_user_stats: ResMut<UserStatsByPlayerForCurrentGameCache>,
@@ -199,11 +199,14 @@ mod test {
     use thetawave_interface::audio::SoundEffectType;
     use thetawave_interface::character::{Character, CharacterType};
     use thetawave_interface::game::historical_metrics::{
-        MobKillsByPlayerForCurrentGame, UserStatsByPlayerForCurrentGameCache, DEFAULT_USER_ID,
+        MobKillsByPlayerForCurrentGame, UserStat, UserStatsByPlayerForCurrentGameCache,
+        DEFAULT_USER_ID,
     };
+    use thetawave_interface::health::DamageDealtEvent;
     use thetawave_interface::player::PlayerBundle;
     use thetawave_interface::spawnable::{
-        EnemyMobType, Faction, MobDestroyedEvent, MobType, ProjectileType, SpawnPosition,
+        EnemyMobType, Faction, MobDestroyedEvent, MobType, ProjectileHitEvent, ProjectileType,
+        SpawnPosition,
     };
     use thetawave_interface::states::{AppStates, GameStates};
     use thetawave_interface::weapon::{ArcPatternData, SpreadPattern, WeaponProjectileData};
@@ -222,6 +225,8 @@ mod test {
             .add_event::<SortedCollisionEvent>()
             .add_event::<MobDestroyedEvent>()
             .add_event::<FireWeaponEvent>()
+            .add_event::<DamageDealtEvent>()
+            .add_event::<ProjectileHitEvent>()
             .insert_resource(UserStatsByPlayerForCurrentGameCache::default());
 
         app
@@ -260,7 +265,7 @@ mod test {
             .get_resource::<CharactersResource>()
             .unwrap()
             .characters
-            .get(&CharacterType::Captain)
+            .get(&CharacterType::from("captain"))
             .cloned()
             .unwrap();
         let player_1: PlayerBundle = PlayerBundle::from(&player_1_character);
@@ -282,6 +287,10 @@ mod test {
                 }),
                 size: 1.0,
                 sound: SoundEffectType::PlayerFireBlast,
+                homing: None,
+                extra_pierce: 0,
+                extra_ricochet: 0,
+                status_effects: vec![],
             },
             source_transform: Default::default(),
             source_entity: player_1_entity.id(),
@@ -313,4 +322,43 @@ mod test {
             .total_shots_fired;
         assert_eq!(n_p1_shots_fired_2, 2);
     }
+    #[test]
+    fn test_increment_player_1_shots_hit_counter() {
+        let mut app = base_app_required_for_counting_metrics();
+
+        let player_1_character: Character = app
+            .world()
+            .get_resource::<CharactersResource>()
+            .unwrap()
+            .characters
+            .get(&CharacterType::from("captain"))
+            .cloned()
+            .unwrap();
+        let player_1_entity = app
+            .world_mut()
+            .spawn(PlayerBundle::from(&player_1_character))
+            .id();
+        let other_entity = app.world_mut().spawn(NullComponent).id();
+        app.world_mut()
+            .resource_mut::<UserStatsByPlayerForCurrentGameCache>()
+            .insert(DEFAULT_USER_ID, UserStat::default());
+
+        // only hits from projectiles fired by player 1 are counted
+        for source in [player_1_entity, player_1_entity, other_entity] {
+            let projectile = app.world_mut().spawn(NullComponent).id();
+            app.world_mut()
+                .send_event(ProjectileHitEvent { projectile, source });
+        }
+        app.update();
+
+        let n_p1_shots_hit = app
+            .world()
+            .get_resource::<UserStatsByPlayerForCurrentGameCache>()
+            .unwrap()
+            .0
+            .get(&DEFAULT_USER_ID)
+            .unwrap()
+            .total_shots_hit;
+        assert_eq!(n_p1_shots_hit, 2);
+    }
 }
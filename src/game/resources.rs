@@ -2,6 +2,8 @@
 
 use bevy::prelude::Resource;
 use serde::Deserialize;
+use thetawave_interface::character::CharacterType;
+use thetawave_interface::player::{PlayerAiData, PlayerIDComponent};
 
 /// Values used globally throughout the game
 /// Put global values here that should not be able to be modified by the user
@@ -18,6 +20,30 @@ pub struct GameParametersResource {
     pub max_speed: f32,
     /// Distance between the center of the screen and the player spawn point
     pub player_spawn_distance: f32,
+    /// Time in seconds that a downed player lasts before being destroyed
+    pub bleed_out_time: f32,
+    /// Time in seconds that a teammate has to stay near a downed player to revive it
+    pub revive_time: f32,
+    /// Distance that a teammate has to be within to revive a downed player
+    pub revive_distance: f32,
+    /// Fraction of maximum health that revived players return with
+    pub revive_health: f32,
+    /// Time in seconds before a player that lost a life respawns
+    pub respawn_time: f32,
+    /// Time in seconds that respawned players ignore damage
+    pub respawn_invulnerability_time: f32,
+    /// Vertical position at the bottom of the arena where players respawn
+    pub respawn_y: f32,
+    /// Character of an AI wingman that fights next to solo players, `None` to play alone
+    pub wingman: Option<CharacterType>,
+    /// Steering policy of AI controlled players such as the wingman
+    pub player_ai: PlayerAiData,
+    /// Lets players aim slot one abilities with the mouse cursor or a gamepad stick
+    pub free_aim: bool,
+    /// Minimum tilt of the aim stick to aim with it, players fire straight ahead below it
+    pub aim_stick_deadzone: f32,
+    /// Distance from the player of the reticle of players aiming with a stick
+    pub aim_reticle_distance: f32,
     /// Sprite image size multiplier
     pub sprite_scale: f32,
     /// Threshold to set velocity to zero
@@ -29,7 +55,9 @@ pub struct GameParametersResource {
 }
 
 impl GameParametersResource {
+    /// Maximum amount of players, limited to the number of player ids
     pub fn get_max_players(&self) -> u8 {
         self.max_players
+            .clamp(1, PlayerIDComponent::MAX_PLAYERS as u8)
     }
 }
@@ -1,11 +1,13 @@
 //! Exposes a plugin that changes a player/mob's health and shields based on time and events
 use crate::spawnable::SpawnEffectEvent;
 use bevy::prelude::{
-    App, Entity, EventReader, EventWriter, Plugin, Query, Res, Time, Transform, Update,
+    App, Entity, EventReader, EventWriter, Has, Plugin, Query, Res, Time, Transform, Update,
 };
 use thetawave_interface::{
     health::{DamageDealtEvent, HealthComponent},
+    player::{PlayerIntangibleComponent, PlayerInvulnerableComponent},
     spawnable::{EffectType, TextEffectType},
+    status_effect::StatusEffectsComponent,
 };
 /// Includes systems to decrease a player's health and regenerate their shields over time.
 pub(super) struct HealthPlugin;
@@ -25,25 +27,55 @@ fn regenerate_shields_system(mut health_query: Query<&mut HealthComponent>, time
 }
 
 /// Receive damage dealt events, apply damage, and spawn effects
+/// Damage dealt to invulnerable or intangible players is ignored
 fn damage_system(
     mut damage_dealt_events: EventReader<DamageDealtEvent>,
-    mut health_query: Query<(Entity, &Transform, &mut HealthComponent)>,
+    mut health_query: Query<(
+        Entity,
+        &Transform,
+        &mut HealthComponent,
+        Option<&StatusEffectsComponent>,
+        Has<PlayerInvulnerableComponent>,
+        Has<PlayerIntangibleComponent>,
+    )>,
     mut spawn_effect_event_writer: EventWriter<SpawnEffectEvent>,
 ) {
     for event in damage_dealt_events.read() {
-        if let Ok((_entity, transform, mut health_component)) = health_query.get_mut(event.target) {
-            // take damage from health
-            health_component.take_damage(event.damage);
+        if let Ok((_entity, transform, mut health_component, status_effects, false, false)) =
+            health_query.get_mut(event.target)
+        {
+            // scale damage by status effects that increase damage taken
+            let damage = match status_effects {
+                Some(status_effects) => (event.damage as f32
+                    * status_effects.damage_taken_multiplier())
+                .round() as usize,
+                None => event.damage,
+            };
+
+            // take damage from health, after resistances
+            let damage = health_component.take_damage(damage, event.damage_type);
+
+            // spawn damage dealt text effect, critical hits are emphasized
+            let (text_effect_type, text) = if event.is_critical {
+                (
+                    TextEffectType::CriticalDamageDealt(event.damage_type),
+                    format!("{damage}!"),
+                )
+            } else {
+                (
+                    TextEffectType::DamageDealt(event.damage_type),
+                    damage.to_string(),
+                )
+            };
 
-            // spawn damage dealt text effect
             spawn_effect_event_writer.send(SpawnEffectEvent {
-                effect_type: EffectType::Text(TextEffectType::DamageDealt),
+                effect_type: EffectType::Text(text_effect_type),
                 transform: Transform {
                     translation: transform.translation,
                     scale: transform.scale,
                     ..Default::default()
                 },
-                text: Some(event.damage.to_string()),
+                text: Some(text),
                 ..Default::default()
             });
         }
@@ -1,6 +1,8 @@
 //! Exposes resources with methods to compute loot drops from killed mobs.
-use bevy::prelude::{App, EventWriter, Plugin, Resource, Vec2};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::{App, EventWriter, Plugin, Res, Resource, Vec2};
 use consumable::ConsumableLootDrop;
+use rand::seq::SliceRandom;
 use ron::de::from_bytes;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -8,7 +10,7 @@ use thetawave_interface::spawnable::{ItemType, SpawnItemEvent};
 
 mod consumable;
 
-use crate::spawnable::SpawnConsumableEvent;
+use crate::{run::CurrentRunProgressResource, spawnable::SpawnConsumableEvent};
 
 pub(crate) use self::consumable::DropListType;
 
@@ -34,17 +36,49 @@ pub struct LootDropsResource {
 pub enum LootDrop {
     Consumable(ConsumableLootDrop),
     Item(ItemType),
+    /// Drops one item chosen at random from the list
+    RandomItem(Vec<ItemType>),
+}
+
+/// Loot drop lists along with the current run, which decides what items can be dropped
+#[derive(SystemParam)]
+pub struct LootDrops<'w> {
+    loot_drops_resource: Res<'w, LootDropsResource>,
+    run_resource: Res<'w, CurrentRunProgressResource>,
+}
+
+impl LootDrops<'_> {
+    /// Roll for loot from the drop list, leaving out items that the current run has no use for
+    pub fn spawn_loot_drops(
+        &self,
+        drop_list_type: &DropListType,
+        consumable_event_writer: &mut EventWriter<SpawnConsumableEvent>,
+        item_event_writer: &mut EventWriter<SpawnItemEvent>,
+        position: Vec2,
+    ) {
+        self.loot_drops_resource.spawn_loot_drops(
+            drop_list_type,
+            consumable_event_writer,
+            item_event_writer,
+            position,
+            self.run_resource.lives.is_some(),
+        );
+    }
 }
 
 impl LootDropsResource {
     /// Roll for consumables from drop list and emit events when those rolls succeed.
+    /// Items that give lives are only dropped if the run uses lives.
     pub fn spawn_loot_drops(
         &self,
         drop_list_type: &DropListType,
         consumable_event_writer: &mut EventWriter<SpawnConsumableEvent>,
         item_event_writer: &mut EventWriter<SpawnItemEvent>,
         position: Vec2,
+        lives_enabled: bool,
     ) {
+        let can_drop = |item_type: &ItemType| lives_enabled || !item_type.requires_lives();
+
         // get drops list from resource
         let drop_list = &self.drops[drop_list_type];
 
@@ -54,12 +88,25 @@ impl LootDropsResource {
                 LootDrop::Consumable(consumable_loot_drop) => {
                     consumable_loot_drop.roll_and_spawn(consumable_event_writer, position);
                 }
-                LootDrop::Item(item_type) => {
+                LootDrop::Item(item_type) if can_drop(item_type) => {
                     item_event_writer.send(SpawnItemEvent {
                         item_type: item_type.clone(),
                         position,
                     });
                 }
+                LootDrop::Item(_) => {}
+                LootDrop::RandomItem(item_types) => {
+                    let item_types: Vec<&ItemType> = item_types
+                        .iter()
+                        .filter(|item_type| can_drop(item_type))
+                        .collect();
+                    if let Some(item_type) = item_types.choose(&mut rand::thread_rng()) {
+                        item_event_writer.send(SpawnItemEvent {
+                            item_type: (*item_type).clone(),
+                            position,
+                        });
+                    }
+                }
             }
         }
     }
@@ -32,6 +32,7 @@ mod run;
 mod scanner;
 mod spawnable;
 mod states;
+mod status_effect;
 mod tools;
 mod ui;
 mod weapon;
@@ -194,6 +195,7 @@ impl PluginGroup for ThetawaveGamePlugins {
             .add(states::StatesPlugin)
             .add(game::counters::plugin::CountingMetricsPlugin)
             .add(health::HealthPlugin)
+            .add(status_effect::StatusEffectPlugin)
             .add(weapon::WeaponPlugin)
             .add(
                 RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(PHYSICS_PIXELS_PER_METER)
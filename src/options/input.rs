@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use leafwing_input_manager::prelude::InputMap;
+use leafwing_input_manager::prelude::{GamepadStick, InputMap};
 use leafwing_input_manager::{prelude::ActionState, InputManagerBundle};
 use serde::Deserialize;
 use thetawave_interface::input::{InputsResource, MainMenuExplorer, MenuAction, PlayerAction};
@@ -14,6 +14,22 @@ pub fn spawn_menu_explorer_system(mut commands: Commands, inputs_res: Res<Inputs
         .insert(MainMenuExplorer);
 }
 
+/// Gamepad sticks that can be bound to dual axis actions
+#[derive(Deserialize, Clone, Copy)]
+pub enum GamepadStickBinding {
+    LeftStick,
+    RightStick,
+}
+
+impl From<GamepadStickBinding> for GamepadStick {
+    fn from(binding: GamepadStickBinding) -> Self {
+        match binding {
+            GamepadStickBinding::LeftStick => GamepadStick::LEFT,
+            GamepadStickBinding::RightStick => GamepadStick::RIGHT,
+        }
+    }
+}
+
 #[derive(Deserialize)]
 pub struct InputBindings {
     pub menu_keyboard: Vec<(MenuAction, KeyCode)>,
@@ -21,18 +37,34 @@ pub struct InputBindings {
     pub player_keyboard: Vec<(PlayerAction, KeyCode)>,
     pub player_gamepad: Vec<(PlayerAction, GamepadButtonType)>,
     pub player_mouse: Vec<(PlayerAction, MouseButton)>,
+    /// Sticks bound to dual axis actions, such as aiming
+    #[serde(default)]
+    pub player_gamepad_sticks: Vec<(PlayerAction, GamepadStickBinding)>,
 }
 
 impl From<InputBindings> for InputsResource {
     fn from(bindings: InputBindings) -> Self {
+        let mut player_gamepad = InputMap::new(bindings.player_gamepad);
+        for (action, stick) in bindings.player_gamepad_sticks {
+            player_gamepad.insert_dual_axis(action, GamepadStick::from(stick));
+        }
+
+        let join_gamepad_buttons = bindings
+            .menu_gamepad
+            .iter()
+            .filter(|(action, _)| matches!(action, MenuAction::JoinGamepad))
+            .map(|(_, button)| *button)
+            .collect();
+
         InputsResource {
+            join_gamepad_buttons,
             menu: InputMap::new(bindings.menu_keyboard)
                 .insert_multiple(bindings.menu_gamepad)
                 .to_owned(),
             player_keyboard: InputMap::new(bindings.player_keyboard)
                 .insert_multiple(bindings.player_mouse)
                 .to_owned(),
-            player_gamepad: InputMap::new(bindings.player_gamepad),
+            player_gamepad,
         }
     }
 }
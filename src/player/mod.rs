@@ -1,7 +1,7 @@
 //! Exposes a plugin and resources to deal with player behavior such as spawning, moving, firing,
 //! and dying.
 use bevy::{
-    app::{App, Plugin, Update},
+    app::{App, Plugin, Startup, Update},
     ecs::schedule::IntoSystemConfigs,
     state::{
         condition::in_state,
@@ -12,9 +12,11 @@ use leafwing_input_manager::prelude::InputManagerPlugin;
 use ron::de::from_bytes;
 
 use thetawave_interface::{
-    abilities::{AbilitiesResource, AbilityDescriptionsResource, ActivateAbilityEvent},
+    abilities::{
+        AbilitiesResource, AbilityDescriptionsResource, ActivateAbilityEvent, UpgradeAbilityEvent,
+    },
     input::PlayerAction,
-    player::{InputRestrictionsAtSpawn, PlayersResource},
+    player::{InputRestrictionsAtSpawn, PlayerDownedEvent, PlayerRevivedEvent, PlayersResource},
     states::{AppStates, GameStates},
 };
 
@@ -25,13 +27,19 @@ use self::{
     spawn::spawn_players_system,
     systems::{
         abilities::{
+            beam_ability_system, decoy_ability_system, decoy_hit_system,
             player_ability_cooldown_system, player_ability_input_system,
-            standard_weapon_ability_system, start_charge_ability_system,
-            update_charge_ability_system,
+            standard_weapon_ability_system, start_charge_ability_system, start_dash_ability_system,
+            start_shield_bubble_ability_system, update_charge_ability_system,
+            update_dash_ability_system, update_decoy_system, update_shield_bubble_ability_system,
         },
+        ai::player_ai_input_system,
+        aim::{aim_reticle_system, player_aim_system},
         movement::{player_movement_system, player_tilt_system},
-        player_death_system, players_reset_system,
-        upgrades::scale_ability_cooldowns_system,
+        player_death_system, player_intangibility_system, player_invulnerability_system,
+        player_respawn_system, player_revive_system, players_reset_system,
+        upgrades::{scale_ability_cooldowns_system, upgrade_ability_system},
+        validate_characters_system,
     },
 };
 
@@ -46,6 +54,9 @@ impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(InputManagerPlugin::<PlayerAction>::default());
         app.add_event::<ActivateAbilityEvent>();
+        app.add_event::<UpgradeAbilityEvent>();
+        app.add_event::<PlayerDownedEvent>();
+        app.add_event::<PlayerRevivedEvent>();
 
         app.insert_resource(
             from_bytes::<CharactersResource>(include_bytes!("../../assets/data/characters.ron"))
@@ -64,6 +75,8 @@ impl Plugin for PlayerPlugin {
             .unwrap(),
         );
 
+        app.add_systems(Startup, validate_characters_system);
+
         app.insert_resource(PlayersResource::default())
             .insert_resource(InputRestrictionsAtSpawn::default());
 
@@ -76,14 +89,40 @@ impl Plugin for PlayerPlugin {
             Update,
             (
                 player_death_system,
+                player_revive_system.after(player_death_system),
+                player_respawn_system.after(player_death_system),
+                player_invulnerability_system.after(player_respawn_system),
+                player_intangibility_system,
+                player_ai_input_system
+                    .before(player_movement_system)
+                    .before(player_ability_input_system),
                 player_movement_system.in_set(GameUpdateSet::Movement),
                 player_tilt_system.in_set(GameUpdateSet::Movement),
                 player_ability_cooldown_system,
                 player_ability_input_system,
+                scale_ability_cooldowns_system,
+                upgrade_ability_system,
+                player_aim_system.before(standard_weapon_ability_system),
+                aim_reticle_system.after(player_aim_system),
+            )
+                .run_if(in_state(AppStates::Game))
+                .run_if(in_state(GameStates::Playing)),
+        );
+
+        app.add_systems(
+            Update,
+            (
                 standard_weapon_ability_system,
+                beam_ability_system,
                 start_charge_ability_system,
                 update_charge_ability_system,
-                scale_ability_cooldowns_system,
+                start_shield_bubble_ability_system,
+                update_shield_bubble_ability_system,
+                start_dash_ability_system,
+                update_dash_ability_system,
+                decoy_ability_system,
+                decoy_hit_system,
+                update_decoy_system.after(decoy_hit_system),
             )
                 .run_if(in_state(AppStates::Game))
                 .run_if(in_state(GameStates::Playing)),
@@ -1,5 +1,6 @@
 use bevy::{ecs::system::Resource, utils::HashMap};
 use serde::Deserialize;
+use thiserror::Error;
 
 use thetawave_interface::character::{Character, CharacterType};
 
@@ -8,4 +9,101 @@ use thetawave_interface::character::{Character, CharacterType};
 pub struct CharactersResource {
     /// Names mapped to characters for all characters
     pub characters: HashMap<CharacterType, Character>,
+    /// Order of characters in the character selection carousel
+    pub selection_order: Vec<CharacterType>,
+}
+
+/// Problems with character data that would otherwise only show up once the character is used
+#[derive(Error, Debug, PartialEq)]
+pub enum CharacterDataError {
+    #[error("No characters are in the selection order.")]
+    EmptySelectionOrder,
+    #[error("Character \"{0}\" is in the selection order but has no entry in characters.ron.")]
+    UnknownSelectableCharacter(String),
+    #[error("Wingman character \"{0}\" has no entry in characters.ron.")]
+    UnknownWingmanCharacter(String),
+}
+
+impl CharactersResource {
+    /// Character that players start with when joining, the first in the selection order
+    pub fn get_default_character(&self) -> CharacterType {
+        self.selection_order
+            .first()
+            .cloned()
+            .expect("at least one character should be in the selection order")
+    }
+
+    /// Check that the selection order and the wingman only refer to characters that exist
+    pub fn validate(&self, wingman: Option<&CharacterType>) -> Result<(), CharacterDataError> {
+        if self.selection_order.is_empty() {
+            return Err(CharacterDataError::EmptySelectionOrder);
+        }
+
+        if let Some(character_type) = self
+            .selection_order
+            .iter()
+            .find(|character_type| !self.characters.contains_key(*character_type))
+        {
+            return Err(CharacterDataError::UnknownSelectableCharacter(
+                character_type.0.clone(),
+            ));
+        }
+
+        match wingman {
+            Some(character_type) if !self.characters.contains_key(character_type) => Err(
+                CharacterDataError::UnknownWingmanCharacter(character_type.0.clone()),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ron::de::from_bytes;
+    use thetawave_interface::character::CharacterType;
+
+    use super::{CharacterDataError, CharactersResource};
+
+    fn characters_res() -> CharactersResource {
+        from_bytes::<CharactersResource>(include_bytes!("../../../assets/data/characters.ron"))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_character_data_is_valid() {
+        assert_eq!(characters_res().validate(None), Ok(()));
+        assert_eq!(
+            characters_res().validate(Some(&CharacterType::from("captain"))),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_unknown_characters_are_rejected() {
+        let mut characters_res = characters_res();
+
+        assert_eq!(
+            characters_res.validate(Some(&CharacterType::from("pilot"))),
+            Err(CharacterDataError::UnknownWingmanCharacter(
+                "pilot".to_string()
+            ))
+        );
+
+        characters_res
+            .selection_order
+            .push(CharacterType::from("pilot"));
+        assert_eq!(
+            characters_res.validate(None),
+            Err(CharacterDataError::UnknownSelectableCharacter(
+                "pilot".to_string()
+            ))
+        );
+
+        characters_res.selection_order.clear();
+        assert_eq!(
+            characters_res.validate(None),
+            Err(CharacterDataError::EmptySelectionOrder)
+        );
+    }
 }
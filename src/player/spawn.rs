@@ -1,26 +1,35 @@
-use bevy::color::Color;
+use std::f32::consts::FRAC_PI_4;
+
+use bevy::color::{Color, Srgba};
 use bevy::core::Name;
 use bevy::ecs::system::{Commands, Res};
 use bevy::hierarchy::{BuildChildren, ChildBuilder};
 use bevy::input::gamepad::Gamepad;
-use bevy::math::Vec3;
+use bevy::math::{Quat, Vec2, Vec3};
+use bevy::render::view::Visibility;
 use bevy::sprite::{Sprite, SpriteBundle};
 use bevy::transform::components::Transform;
 use bevy_rapier2d::dynamics::{ExternalImpulse, LockedAxes, RigidBody, Velocity};
 use bevy_rapier2d::geometry::{ActiveEvents, Collider, ColliderMassProperties, Restitution};
 use leafwing_input_manager::{prelude::ActionState, InputManagerBundle};
 use thetawave_interface::abilities::{
-    AbilitiesResource, ChargeAbilityBundle, SlotOneAbilityType, SlotTwoAbilityType,
+    AbilitiesResource, BeamAbilityBundle, ChargeAbilityBundle, DashAbilityBundle,
+    DecoyAbilityBundle, ShieldBubbleAbilityBundle, SlotOneAbilityType, SlotTwoAbilityType,
     StandardWeaponAbilityBundle,
 };
+use thetawave_interface::character::CharacterType;
 use thetawave_interface::input::{InputsResource, PlayerAction};
-use thetawave_interface::player::{PlayerBundle, PlayerIDComponent};
+use thetawave_interface::player::{
+    AimReticleComponent, AimSource, PlayerAiComponent, PlayerAimComponent, PlayerBundle,
+    PlayerIDComponent, WingmanComponent,
+};
 use thetawave_interface::{health::HealthComponent, player::PlayerInput, states::GameCleanup};
 
 use crate::{
     assets,
     game::GameParametersResource,
     player::{CharactersResource, PlayersResource},
+    run::CurrentRunProgressResource,
 };
 
 trait PlayerAbilityChildBuilderExt {
@@ -51,6 +60,12 @@ impl PlayerAbilityChildBuilderExt for ChildBuilder<'_> {
                 SlotOneAbilityType::StandardBullet => self.spawn(
                     StandardWeaponAbilityBundle::from(&abilities_res.standard_bullet_ability),
                 ),
+                SlotOneAbilityType::PiercingBlast => self.spawn(StandardWeaponAbilityBundle::from(
+                    &abilities_res.piercing_blast_ability,
+                )),
+                SlotOneAbilityType::WaveBullet => self.spawn(StandardWeaponAbilityBundle::from(
+                    &abilities_res.wave_bullet_ability,
+                )),
             };
         }
     }
@@ -68,12 +83,56 @@ impl PlayerAbilityChildBuilderExt for ChildBuilder<'_> {
                 SlotTwoAbilityType::MegaBlast => self.spawn(StandardWeaponAbilityBundle::from(
                     &abilities_res.mega_blast_ability,
                 )),
+                SlotTwoAbilityType::Beam => {
+                    self.spawn(BeamAbilityBundle::from(&abilities_res.beam_ability))
+                }
+                SlotTwoAbilityType::ShieldBubble => self.spawn(ShieldBubbleAbilityBundle::from(
+                    &abilities_res.shield_bubble_ability,
+                )),
+                SlotTwoAbilityType::Dash => {
+                    self.spawn(DashAbilityBundle::from(&abilities_res.dash_ability))
+                }
+                SlotTwoAbilityType::HomingMissileSalvo => self.spawn(
+                    StandardWeaponAbilityBundle::from(&abilities_res.homing_missile_salvo_ability),
+                ),
+                SlotTwoAbilityType::Decoy => {
+                    self.spawn(DecoyAbilityBundle::from(&abilities_res.decoy_ability))
+                }
             };
         }
     }
 }
 
-/// Spawns player into the game
+/// Color of the reticles of players in the free aim mode
+const AIM_RETICLE_COLOR: Srgba = Srgba::new(1.0, 1.0, 1.0, 0.6);
+
+/// Width and height of aim reticles
+const AIM_RETICLE_SIZE: f32 = 12.0;
+
+/// Depth of aim reticles, above players and projectiles
+const AIM_RETICLE_Z: f32 = 20.0;
+
+/// Color of the outline drawn around each player in multiplayer
+fn get_outline_color(player_id: PlayerIDComponent) -> Color {
+    match player_id {
+        PlayerIDComponent::One => Color::srgb(0.7, 0.0, 0.0),
+        PlayerIDComponent::Two => Color::srgb(0.0, 0.0, 1.0),
+        PlayerIDComponent::Three => Color::srgb(0.0, 0.7, 0.0),
+        PlayerIDComponent::Four => Color::srgb(0.8, 0.7, 0.0),
+    }
+}
+
+/// Horizontal spawn position of a player, with players spread evenly between the spawn distance on either side
+fn get_spawn_x(spawn_idx: usize, player_count: usize, spawn_distance: f32) -> f32 {
+    if player_count > 1 {
+        -spawn_distance + 2.0 * spawn_distance * spawn_idx as f32 / (player_count - 1) as f32
+    } else {
+        0.0
+    }
+}
+
+/// Spawns player into the game, along with an AI wingman for solo players if enabled
+#[allow(clippy::too_many_arguments)]
 pub(super) fn spawn_players_system(
     mut commands: Commands,
     characters: Res<CharactersResource>,
@@ -82,66 +141,93 @@ pub(super) fn spawn_players_system(
     players_resource: Res<PlayersResource>,
     inputs_res: Res<InputsResource>,
     abilities_res: Res<AbilitiesResource>,
+    run_resource: Res<CurrentRunProgressResource>,
 ) {
+    // joined players with their characters, and inputs for players that aren't AI controlled
+    let mut players: Vec<(PlayerIDComponent, CharacterType, Option<PlayerInput>)> =
+        players_resource
+            .player_data
+            .iter()
+            .enumerate()
+            .filter_map(|(id, pd)| {
+                let pd = pd.as_ref()?;
+                Some((
+                    PlayerIDComponent::try_from(id).ok()?,
+                    pd.character.clone(),
+                    Some(pd.input),
+                ))
+            })
+            .collect();
+
+    // an AI wingman takes the next player id when playing solo
+    let wingman = match (&game_parameters.wingman, players.as_slice()) {
+        (Some(wingman_character), [(player_id, _, _)]) => {
+            PlayerIDComponent::try_from(usize::from(*player_id) + 1)
+                .ok()
+                .map(|wingman_id| (wingman_id, wingman_character.clone(), None))
+        }
+        _ => None,
+    };
+    players.extend(wingman);
+
     // check if more than one player is playing
-    let is_multiplayer = players_resource.player_data.get(1).is_some();
-
-    for (player_id, maybe_player_data) in players_resource
-        .player_data
-        .iter()
-        .enumerate()
-        .map(|(id, pd)| (PlayerIDComponent::from(id), pd))
-    {
-        if let Some(player_data) = maybe_player_data {
-            // choose a character
-            let character = &characters.characters[&player_data.character];
-
-            // scale collider to align with the sprite
-            let collider_size_hx =
-                character.collider_dimensions.x * game_parameters.sprite_scale / 2.0;
-            let collider_size_hy =
-                character.collider_dimensions.y * game_parameters.sprite_scale / 2.0;
-
-            // create player component from character
-            let player_bundle = PlayerBundle::from(character).with_id(player_id);
-
-            // spawn the player
-            let mut player_entity = commands.spawn_empty();
-            player_entity
-                .insert(SpriteBundle {
-                    texture: player_assets.get_asset(&character.character_type),
-                    ..Default::default()
-                })
-                .insert(RigidBody::Dynamic)
-                .insert(LockedAxes::ROTATION_LOCKED)
-                .insert(Transform {
-                    translation: if is_multiplayer {
-                        Vec3::new(
-                            if matches!(player_id, PlayerIDComponent::One) {
-                                -game_parameters.player_spawn_distance
-                            } else {
-                                game_parameters.player_spawn_distance
-                            },
-                            0.0,
-                            if matches!(player_id, PlayerIDComponent::One) {
-                                0.0
-                            } else {
-                                0.2
-                            },
-                        )
-                    } else {
-                        Vec3::ZERO
-                    },
-                    scale: Vec3::new(
-                        game_parameters.sprite_scale,
-                        game_parameters.sprite_scale,
-                        1.0,
+    let player_count = players.len();
+    let is_multiplayer = player_count > 1;
+
+    for (spawn_idx, (player_id, character_type, input)) in players.into_iter().enumerate() {
+        // choose a character
+        let character = &characters.characters[&character_type];
+
+        // scale collider to align with the sprite
+        let collider_size_hx = character.collider_dimensions.x * game_parameters.sprite_scale / 2.0;
+        let collider_size_hy = character.collider_dimensions.y * game_parameters.sprite_scale / 2.0;
+
+        // create player component from character
+        let player_bundle = PlayerBundle::from(character)
+            .with_id(player_id)
+            .with_lives(run_resource.lives);
+
+        // spawn the player
+        let mut player_entity = commands.spawn_empty();
+        player_entity
+            .insert(SpriteBundle {
+                texture: player_assets.get_asset(character),
+                ..Default::default()
+            })
+            .insert(RigidBody::Dynamic)
+            .insert(LockedAxes::ROTATION_LOCKED)
+            .insert(Transform {
+                translation: Vec3::new(
+                    get_spawn_x(
+                        spawn_idx,
+                        player_count,
+                        game_parameters.player_spawn_distance,
                     ),
-                    ..Default::default()
-                })
-                .insert(InputManagerBundle::<PlayerAction> {
+                    0.0,
+                    0.2 * spawn_idx as f32,
+                ),
+                scale: Vec3::new(
+                    game_parameters.sprite_scale,
+                    game_parameters.sprite_scale,
+                    1.0,
+                ),
+                ..Default::default()
+            });
+
+        // read actions from the player's input device, or write them with the AI
+        match input {
+            Some(input) => {
+                // keyboard players aim with the mouse, gamepad players with a stick
+                if game_parameters.free_aim {
+                    player_entity.insert(PlayerAimComponent::new(match input {
+                        PlayerInput::Keyboard => AimSource::Cursor,
+                        PlayerInput::Gamepad(_) => AimSource::Stick,
+                    }));
+                }
+
+                player_entity.insert(InputManagerBundle::<PlayerAction> {
                     action_state: ActionState::default(),
-                    input_map: match player_data.input {
+                    input_map: match input {
                         PlayerInput::Keyboard => inputs_res.player_keyboard.clone(),
                         PlayerInput::Gamepad(id) => inputs_res
                             .player_gamepad
@@ -149,41 +235,70 @@ pub(super) fn spawn_players_system(
                             .set_gamepad(Gamepad { id })
                             .to_owned(),
                     },
-                })
-                .insert(Collider::cuboid(collider_size_hx, collider_size_hy))
-                .insert(Velocity::default())
-                .insert(Restitution::new(1.0))
-                .insert(ColliderMassProperties::Density(character.collider_density))
-                .insert(player_bundle)
-                .insert(HealthComponent::from(character))
-                .insert(GameCleanup)
-                .insert(ActiveEvents::COLLISION_EVENTS)
-                .insert(ExternalImpulse::default())
-                .insert(Name::new("Player"))
-                .with_children(|parent| {
-                    parent.spawn_slot_1_ability(&abilities_res, &character.slot_1_ability);
-                    parent.spawn_slot_2_ability(&abilities_res, &character.slot_2_ability);
                 });
+            }
+            None => {
+                player_entity.insert((
+                    ActionState::<PlayerAction>::default(),
+                    PlayerAiComponent::from(&game_parameters.player_ai),
+                    WingmanComponent,
+                ));
+            }
+        }
+
+        player_entity
+            .insert(Collider::cuboid(collider_size_hx, collider_size_hy))
+            .insert(Velocity::default())
+            .insert(Restitution::new(1.0))
+            .insert(ColliderMassProperties::Density(character.collider_density))
+            .insert(player_bundle)
+            .insert(HealthComponent::from(character))
+            .insert(GameCleanup)
+            .insert(ActiveEvents::COLLISION_EVENTS)
+            .insert(ExternalImpulse::default())
+            .insert(Name::new("Player"))
+            .with_children(|parent| {
+                parent.spawn_slot_1_ability(&abilities_res, &character.slot_1_ability);
+                parent.spawn_slot_2_ability(&abilities_res, &character.slot_2_ability);
+            });
 
-            // add colored outline to player if multiplayer
-            if is_multiplayer {
-                player_entity.with_children(|parent| {
-                    parent
-                        .spawn(SpriteBundle {
-                            texture: player_assets.get_outline_asset(&character.character_type),
-                            sprite: Sprite {
-                                color: if matches!(player_id, PlayerIDComponent::One) {
-                                    Color::srgb(0.7, 0.0, 0.0)
-                                } else {
-                                    Color::srgb(0.0, 0.0, 1.0)
-                                },
-                                ..Default::default()
-                            },
+        // add colored outline to player if multiplayer
+        if is_multiplayer {
+            player_entity.with_children(|parent| {
+                parent
+                    .spawn(SpriteBundle {
+                        texture: player_assets.get_outline_asset(character),
+                        sprite: Sprite {
+                            color: get_outline_color(player_id),
                             ..Default::default()
-                        })
-                        .insert(Transform::from_translation(Vec3::new(0.0, 0.0, 0.1)));
-                });
-            }
+                        },
+                        ..Default::default()
+                    })
+                    .insert(Transform::from_translation(Vec3::new(0.0, 0.0, 0.1)));
+            });
+        }
+
+        // spawn a reticle for players that aim, hidden until the player aims
+        if game_parameters.free_aim && input.is_some() {
+            let player = player_entity.id();
+            commands
+                .spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: AIM_RETICLE_COLOR.into(),
+                        custom_size: Some(Vec2::splat(AIM_RETICLE_SIZE)),
+                        ..Default::default()
+                    },
+                    transform: Transform {
+                        translation: Vec3::new(0.0, 0.0, AIM_RETICLE_Z),
+                        rotation: Quat::from_rotation_z(FRAC_PI_4),
+                        ..Default::default()
+                    },
+                    visibility: Visibility::Hidden,
+                    ..Default::default()
+                })
+                .insert(AimReticleComponent { player })
+                .insert(GameCleanup)
+                .insert(Name::new("Aim Reticle"));
         }
     }
 }
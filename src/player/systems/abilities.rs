@@ -1,25 +1,62 @@
+use bevy::asset::Handle;
+use bevy::color::Srgba;
+use bevy::core::Name;
 use bevy::ecs::entity::Entity;
 use bevy::ecs::event::{EventReader, EventWriter};
-use bevy::ecs::system::{Query, Res};
-use bevy::hierarchy::Children;
-use bevy::math::Vec2;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::hierarchy::{Children, DespawnRecursiveExt};
+use bevy::math::{Vec2, Vec3Swizzles};
 use bevy::prelude::default;
+use bevy::render::texture::Image;
+use bevy::sprite::{Sprite, SpriteBundle};
 use bevy::time::{Time, Timer, TimerMode};
 use bevy::transform::components::Transform;
 use bevy_rapier2d::dynamics::{ExternalImpulse, Velocity};
+use bevy_rapier2d::geometry::{ActiveEvents, Collider, CollisionGroups, Group, Sensor};
+use bevy_rapier2d::prelude::CollisionEvent;
 use leafwing_input_manager::action_state::ActionState;
 use thetawave_interface::abilities::{
-    AbilityCooldownComponent, AbilitySlotIDComponent, ActivateAbilityEvent, ChargeAbilityComponent,
+    AbilityCooldownComponent, AbilityInputComponent, AbilityInputMode, AbilitySlotIDComponent,
+    ActivateAbilityEvent, BeamAbilityComponent, ChargeAbilityComponent, DashAbilityComponent,
+    DecoyAbilityComponent, DecoyComponent, ShieldBubbleAbilityComponent,
     StandardWeaponAbilityComponent,
 };
+use thetawave_interface::health::{DamageDealtEvent, HealthComponent};
 use thetawave_interface::input::PlayerAction;
 use thetawave_interface::player::{
-    PlayerIDComponent, PlayerIncomingDamageComponent, PlayerMovementComponent,
-    PlayerOutgoingDamageComponent,
+    InputRestrictionsAtSpawn, PlayerAimComponent, PlayerComponent, PlayerDownedComponent,
+    PlayerIDComponent, PlayerIncomingDamageComponent, PlayerIntangibleComponent,
+    PlayerMovementComponent, PlayerOutgoingDamageComponent, PlayerRespawnComponent,
 };
+use thetawave_interface::spawnable::{EffectType, Faction, ProjectileType};
+use thetawave_interface::states::GameCleanup;
+use thetawave_interface::status_effect::StatusEffectsComponent;
 use thetawave_interface::weapon::WeaponProjectileData;
 
-use crate::spawnable::{FireWeaponEvent, InitialMotion};
+use crate::collision::{
+    ENEMY_PROJECTILE_COLLIDER_GROUP, MOB_COLLIDER_GROUP, NEUTRAL_PROJECTILE_COLLIDER_GROUP,
+    SPAWNABLE_COLLIDER_GROUP,
+};
+use crate::spawnable::{FireWeaponEvent, InitialMotion, ProjectileComponent, SpawnEffectEvent};
+use crate::weapon::FireBeamEvent;
+
+/// Color of decoy drones, a translucent copy of the player that spawned them
+const DECOY_COLOR: Srgba = Srgba::new(0.5, 0.8, 1.0, 0.5);
+
+/// Normalized direction of the player's movement inputs, `None` if no direction is pressed
+fn get_input_direction(action_state: &ActionState<PlayerAction>) -> Option<Vec2> {
+    let up = action_state.pressed(&PlayerAction::MoveUp);
+    let down = action_state.pressed(&PlayerAction::MoveDown);
+    let left = action_state.pressed(&PlayerAction::MoveLeft);
+    let right = action_state.pressed(&PlayerAction::MoveRight);
+
+    Vec2::new(
+        (-(left as i8) + right as i8) as f32,
+        (-(down as i8) + up as i8) as f32,
+    )
+    .try_normalize()
+}
 
 /// Tick ability cooldown timers for each player
 pub(in crate::player) fn player_ability_cooldown_system(
@@ -32,48 +69,97 @@ pub(in crate::player) fn player_ability_cooldown_system(
 }
 
 /// Checks all abilities for if their cooldown timers (in `AbilityCooldownComponent`) are finished, if they are,
-/// and the player has the ability's respective input pressed, sends an ActivateAbilityEvent
-/// and resets the ability's cooldown timer
+/// and the player uses the ability's respective input, sends an ActivateAbilityEvent and resets the ability's
+/// cooldown timer. Abilities that are pressed activate while their input is pressed, and hold-to-charge abilities
+/// charge while their input is held and activate when it is released. Stunned, downed, and respawning players
+/// can't activate abilities, and abilities in slots forbidden by the `InputRestrictionsAtSpawn` can't be used.
+/// Abilities that can't be used lose any charge they had.
+#[allow(clippy::type_complexity)]
 pub(in crate::player) fn player_ability_input_system(
     player_input_query: Query<(
         &ActionState<PlayerAction>,
         &PlayerOutgoingDamageComponent,
         &PlayerIDComponent,
         &Children,
+        Option<&StatusEffectsComponent>,
+        Has<PlayerDownedComponent>,
+        Has<PlayerRespawnComponent>,
+    )>,
+    mut ability_query: Query<(
+        &mut AbilityCooldownComponent,
+        &mut AbilityInputComponent,
+        &AbilitySlotIDComponent,
     )>,
-    mut ability_query: Query<(&mut AbilityCooldownComponent, &AbilitySlotIDComponent)>,
     mut ability_event_writer: EventWriter<ActivateAbilityEvent>,
+    input_restrictions: Res<InputRestrictionsAtSpawn>,
+    time: Res<Time>,
 ) {
-    for (action_state, player_damage, player_id, children) in player_input_query.iter() {
+    for (
+        action_state,
+        player_damage,
+        player_id,
+        children,
+        status_effects,
+        is_downed,
+        is_respawning,
+    ) in player_input_query.iter()
+    {
+        let is_disabled = is_downed
+            || is_respawning
+            || status_effects.is_some_and(|status_effects| status_effects.is_stunned());
+
         for child in children {
-            if let Ok((mut ability_cooldown, ability_id)) = ability_query.get_mut(*child) {
-                match ability_id {
-                    AbilitySlotIDComponent::One => {
-                        if action_state.pressed(&PlayerAction::SlotOneAbility)
-                            && ability_cooldown.cooldown_timer.finished()
-                        {
-                            ability_cooldown.cooldown_timer = Timer::from_seconds(
-                                ability_cooldown.base_cooldown_time
-                                    * player_damage.cooldown_multiplier,
-                                TimerMode::Once,
-                            );
-                            ability_event_writer
-                                .send(ActivateAbilityEvent::new(*player_id, *ability_id));
-                        }
-                    }
-                    AbilitySlotIDComponent::Two => {
-                        if action_state.pressed(&PlayerAction::SlotTwoAbility)
-                            && ability_cooldown.cooldown_timer.finished()
-                        {
-                            ability_cooldown.cooldown_timer = Timer::from_seconds(
-                                ability_cooldown.base_cooldown_time
-                                    * player_damage.cooldown_multiplier,
-                                TimerMode::Once,
+            if let Ok((mut ability_cooldown, mut ability_input, ability_id)) =
+                ability_query.get_mut(*child)
+            {
+                let (action, forbid_reason) = match ability_id {
+                    AbilitySlotIDComponent::One => (
+                        PlayerAction::SlotOneAbility,
+                        &input_restrictions.forbid_main_attack_reason,
+                    ),
+                    AbilitySlotIDComponent::Two => (
+                        PlayerAction::SlotTwoAbility,
+                        &input_restrictions.forbid_special_attack_reason,
+                    ),
+                };
+
+                if is_disabled || forbid_reason.is_some() {
+                    ability_input.charge_time = None;
+                    continue;
+                }
+
+                let is_ready = ability_cooldown.cooldown_timer.finished();
+                let is_pressed = action_state.pressed(&action);
+
+                let input_mode = ability_input.mode;
+                let power = match input_mode {
+                    AbilityInputMode::Press => (is_pressed && is_ready).then_some(1.0),
+                    AbilityInputMode::HoldToCharge(charge_data) => {
+                        if is_pressed && is_ready {
+                            ability_input.charge_time = Some(
+                                (ability_input.charge_time.unwrap_or(0.0) + time.delta_seconds())
+                                    .min(charge_data.max_charge_time),
                             );
-                            ability_event_writer
-                                .send(ActivateAbilityEvent::new(*player_id, *ability_id));
+                            None
+                        } else {
+                            ability_input
+                                .charge_time
+                                .take()
+                                .map(|charge_time| charge_data.get_power(charge_time))
                         }
                     }
+                };
+
+                if let Some(power) = power {
+                    ability_cooldown.cooldown_timer = Timer::from_seconds(
+                        ability_cooldown.base_cooldown_time * player_damage.cooldown_multiplier,
+                        TimerMode::Once,
+                    );
+                    ability_event_writer.send(ActivateAbilityEvent::new(
+                        *player_id,
+                        *ability_id,
+                        power,
+                    ));
                 }
             }
         }
@@ -84,6 +170,9 @@ pub(in crate::player) fn player_ability_input_system(
 /// for a player for corresponding ActivateAbilityEvents.
 /// Combines the stats in the player's `PlayerOutgoingDamageComponent` of the player with
 /// the stats in `StandardWeaponAbilityComponent`.
+/// The power of the activation scales the damage and size of the fired projectiles.
+/// Slot one abilities fire in the direction that the player aims in the free aim mode.
+#[allow(clippy::type_complexity)]
 pub(in crate::player) fn standard_weapon_ability_system(
     player_query: Query<(
         Entity,
@@ -92,8 +181,9 @@ pub(in crate::player) fn standard_weapon_ability_system(
         &PlayerOutgoingDamageComponent,
         &PlayerIDComponent,
         &Children,
+        Option<&PlayerAimComponent>,
     )>,
-    ability_query: Query<(&AbilitySlotIDComponent, &StandardWeaponAbilityComponent)>,
+    mut ability_query: Query<(&AbilitySlotIDComponent, &mut StandardWeaponAbilityComponent)>,
     mut ability_event_reader: EventReader<ActivateAbilityEvent>,
     mut fire_weapon_event_writer: EventWriter<FireWeaponEvent>,
 ) {
@@ -105,30 +195,51 @@ pub(in crate::player) fn standard_weapon_ability_system(
             player_damage,
             player_id,
             children,
+            player_aim,
         ) in player_query.iter()
         {
             for child in children.iter() {
-                if let Ok((ability_id, weapon)) = ability_query.get(*child) {
+                if let Ok((ability_id, mut weapon)) = ability_query.get_mut(*child) {
                     if event.player_id == *player_id && event.ability_slot_id == *ability_id {
+                        // fire slot one abilities where the player is aiming, if it is aiming
+                        let direction = match ability_id {
+                            AbilitySlotIDComponent::One => player_aim
+                                .and_then(|aim| aim.direction)
+                                .unwrap_or(weapon.direction),
+                            AbilitySlotIDComponent::Two => weapon.direction,
+                        };
+
+                        let mut weapon_projectile_data = WeaponProjectileData {
+                            ammunition: weapon.ammunition,
+                            damage: (weapon.damage_multiplier
+                                * player_damage.weapon_damage as f32
+                                * event.power)
+                                .round() as usize,
+                            position: player_damage.projectile_spawn_position.clone(),
+                            speed: weapon.speed_multiplier * player_damage.projectile_speed,
+                            direction,
+                            despawn_time: weapon.despawn_time_multiplier
+                                * player_damage.projectile_despawn_time,
+                            count: ((weapon.count_multiplier
+                                * player_damage.projectile_count as f32)
+                                .round() as usize)
+                                .max(1)
+                                + weapon.extra_count,
+                            spread_pattern: weapon.spread_pattern.clone(),
+                            size: weapon.size_multiplier
+                                * player_damage.projectile_size
+                                * event.power,
+                            sound: weapon.sound,
+                            homing: weapon.homing.clone(),
+                            extra_pierce: weapon.extra_pierce,
+                            extra_ricochet: weapon.extra_ricochet,
+                            status_effects: vec![],
+                        };
+                        weapon_projectile_data.modulate_for_shot(weapon.shots_fired);
+                        weapon.shots_fired += 1;
+
                         fire_weapon_event_writer.send(FireWeaponEvent {
-                            weapon_projectile_data: WeaponProjectileData {
-                                ammunition: weapon.ammunition,
-                                damage: (weapon.damage_multiplier
-                                    * player_damage.weapon_damage as f32)
-                                    .round() as usize,
-                                position: player_damage.projectile_spawn_position.clone(),
-                                speed: weapon.speed_multiplier * player_damage.projectile_speed,
-                                direction: weapon.direction,
-                                despawn_time: weapon.despawn_time_multiplier
-                                    * player_damage.projectile_despawn_time,
-                                count: ((weapon.count_multiplier
-                                    * player_damage.projectile_count as f32)
-                                    .round() as usize)
-                                    .max(1),
-                                spread_pattern: weapon.spread_pattern.clone(),
-                                size: weapon.size_multiplier * player_damage.projectile_size,
-                                sound: weapon.sound,
-                            },
+                            weapon_projectile_data,
                             source_transform: *player_transform,
                             source_entity: player_entity,
                             initial_motion: InitialMotion {
@@ -143,15 +254,53 @@ pub(in crate::player) fn standard_weapon_ability_system(
     }
 }
 
+/// Activates a beam ability (abilities with `BeamAbilityComponent`)
+/// for a player for corresponding ActivateAbilityEvents.
+/// The damage of the beam is the player's weapon damage multiplied by the ability's damage multiplier
+/// and the power of the activation.
+pub(in crate::player) fn beam_ability_system(
+    player_query: Query<(
+        Entity,
+        &PlayerOutgoingDamageComponent,
+        &PlayerIDComponent,
+        &Children,
+    )>,
+    ability_query: Query<(&AbilitySlotIDComponent, &BeamAbilityComponent)>,
+    mut ability_event_reader: EventReader<ActivateAbilityEvent>,
+    mut fire_beam_event_writer: EventWriter<FireBeamEvent>,
+) {
+    for event in ability_event_reader.read() {
+        for (player_entity, player_damage, player_id, children) in player_query.iter() {
+            for child in children.iter() {
+                if let Ok((ability_id, beam_ability)) = ability_query.get(*child) {
+                    if event.player_id == *player_id && event.ability_slot_id == *ability_id {
+                        let mut beam_data = beam_ability.beam.clone();
+                        beam_data.damage = (beam_ability.damage_multiplier
+                            * player_damage.weapon_damage as f32
+                            * event.power)
+                            .round() as usize;
+
+                        fire_beam_event_writer.send(FireBeamEvent {
+                            beam_data,
+                            source_entity: player_entity,
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Activates a charge ability (abilities with `ChargeAbilityComponent`)
 /// for a player for corresponding ActivateAbilityEvents.
-/// Applies damage reduction and an external impulse to the player
+/// Applies damage reduction, bonus collision damage, and an external impulse to the player
 pub(in crate::player) fn start_charge_ability_system(
     mut player_query: Query<(
         &ActionState<PlayerAction>,
         &mut ExternalImpulse,
         &mut PlayerMovementComponent,
         &mut PlayerIncomingDamageComponent,
+        &mut PlayerOutgoingDamageComponent,
         &PlayerIDComponent,
         &Children,
     )>,
@@ -164,6 +313,7 @@ pub(in crate::player) fn start_charge_ability_system(
             mut player_ext_impulse,
             mut player_movement,
             mut player_incoming_damage,
+            mut player_outgoing_damage,
             player_id,
             children,
         ) in player_query.iter_mut()
@@ -171,30 +321,24 @@ pub(in crate::player) fn start_charge_ability_system(
             for child in children.iter() {
                 if let Ok((ability_id, mut charge_ability)) = ability_query.get_mut(*child) {
                     if event.player_id == *player_id && event.ability_slot_id == *ability_id {
+                        // charged activations charge further
+                        let impulse = charge_ability.impulse * event.power;
+
                         // check all movement inputs to see if the player wants to charge in a specific direction
-                        let up = action_state.pressed(&PlayerAction::MoveUp);
-                        let down = action_state.pressed(&PlayerAction::MoveDown);
-                        let left = action_state.pressed(&PlayerAction::MoveLeft);
-                        let right = action_state.pressed(&PlayerAction::MoveRight);
-
-                        // try to create a normalized Vec2 using the inputs
-                        if let Some(vec2_normal) = Vec2::new(
-                            (-(left as i8) + right as i8) as f32,
-                            (-(down as i8) + up as i8) as f32,
-                        )
-                        .try_normalize()
-                        {
+                        if let Some(vec2_normal) = get_input_direction(action_state) {
                             // multiply the normalized vector by the charge ability's impulse
-                            player_ext_impulse.impulse = charge_ability.impulse * vec2_normal;
+                            player_ext_impulse.impulse = impulse * vec2_normal;
                         } else {
                             // if a normalized vector could not be created apply the impulse in the +y direction
-                            player_ext_impulse.impulse = Vec2::new(0.0, charge_ability.impulse);
+                            player_ext_impulse.impulse = Vec2::new(0.0, impulse);
                         }
 
-                        // disable movement and apply damage reduction
+                        // disable movement, apply damage reduction and bonus collision damage
                         player_movement.movement_enabled = false;
                         player_incoming_damage.multiplier -=
                             charge_ability.incoming_damage_multiplier;
+                        player_outgoing_damage.collision_damage +=
+                            charge_ability.collision_damage_bonus;
 
                         // begin the action timer for the ability
                         charge_ability.action_timer.reset();
@@ -207,18 +351,25 @@ pub(in crate::player) fn start_charge_ability_system(
 
 /// Updates the charge ability (`ChargeAbilityComponent`)
 /// Ticks the action timer, when completed enables movment and resets the incoming damage multiplier
+/// and collision damage
 pub(in crate::player) fn update_charge_ability_system(
     mut player_query: Query<(
         &mut Velocity,
         &mut PlayerMovementComponent,
         &mut PlayerIncomingDamageComponent,
+        &mut PlayerOutgoingDamageComponent,
         &Children,
     )>,
     mut ability_query: Query<&mut ChargeAbilityComponent>,
     time: Res<Time>,
 ) {
-    for (mut player_velocity, mut player_movement, mut player_incoming_damage, children) in
-        player_query.iter_mut()
+    for (
+        mut player_velocity,
+        mut player_movement,
+        mut player_incoming_damage,
+        mut player_outgoing_damage,
+        children,
+    ) in player_query.iter_mut()
     {
         for child in children.iter() {
             if let Ok(mut charge_ability) = ability_query.get_mut(*child) {
@@ -230,8 +381,324 @@ pub(in crate::player) fn update_charge_ability_system(
                     player_velocity.linvel = Vec2::splat(0.0);
                     player_movement.movement_enabled = true;
                     player_incoming_damage.multiplier += charge_ability.incoming_damage_multiplier;
+                    player_outgoing_damage.collision_damage = player_outgoing_damage
+                        .collision_damage
+                        .saturating_sub(charge_ability.collision_damage_bonus);
+                }
+            }
+        }
+    }
+}
+
+/// Despawn a hostile projectile that was blocked by an ability, with a despawn effect
+fn absorb_projectile(
+    commands: &mut Commands,
+    spawn_effect_event_writer: &mut EventWriter<SpawnEffectEvent>,
+    projectile_entity: Entity,
+    projectile_transform: &Transform,
+    projectile: &ProjectileComponent,
+) {
+    spawn_effect_event_writer.send(SpawnEffectEvent {
+        effect_type: match projectile.projectile_type {
+            ProjectileType::Blast(_) => EffectType::EnemyBlastDespawn,
+            ProjectileType::Bullet(_) => EffectType::EnemyBulletDespawn,
+        },
+        transform: Transform {
+            translation: projectile_transform.translation,
+            scale: projectile_transform.scale,
+            ..Default::default()
+        },
+        ..default()
+    });
+
+    commands.entity(projectile_entity).despawn_recursive();
+}
+
+/// Activates a shield bubble ability (abilities with `ShieldBubbleAbilityComponent`)
+/// for a player for corresponding ActivateAbilityEvents.
+pub(in crate::player) fn start_shield_bubble_ability_system(
+    player_query: Query<(&PlayerIDComponent, &Children)>,
+    mut ability_query: Query<(&AbilitySlotIDComponent, &mut ShieldBubbleAbilityComponent)>,
+    mut ability_event_reader: EventReader<ActivateAbilityEvent>,
+) {
+    for event in ability_event_reader.read() {
+        for (player_id, children) in player_query.iter() {
+            for child in children.iter() {
+                if let Ok((ability_id, mut shield_bubble)) = ability_query.get_mut(*child) {
+                    if event.player_id == *player_id && event.ability_slot_id == *ability_id {
+                        // begin the action timer for the ability
+                        shield_bubble.action_timer.reset();
+                    }
                 }
             }
         }
     }
 }
+
+/// Updates the shield bubble ability (`ShieldBubbleAbilityComponent`)
+/// Ticks the action timer, until completed absorbs hostile projectiles within the bubble's radius
+pub(in crate::player) fn update_shield_bubble_ability_system(
+    mut commands: Commands,
+    player_query: Query<(&Transform, &Children), With<PlayerComponent>>,
+    mut ability_query: Query<&mut ShieldBubbleAbilityComponent>,
+    projectile_query: Query<(Entity, &Transform, &ProjectileComponent)>,
+    mut spawn_effect_event_writer: EventWriter<SpawnEffectEvent>,
+    time: Res<Time>,
+) {
+    // projectiles absorbed this frame, so overlapping bubbles don't despawn a projectile twice
+    let mut absorbed_projectiles: Vec<Entity> = vec![];
+
+    for (player_transform, children) in player_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut shield_bubble) = ability_query.get_mut(*child) {
+                shield_bubble.action_timer.tick(time.delta());
+
+                if shield_bubble.action_timer.finished() {
+                    continue;
+                }
+
+                for (projectile_entity, projectile_transform, projectile) in projectile_query.iter()
+                {
+                    if absorbed_projectiles.contains(&projectile_entity)
+                        || !projectile
+                            .projectile_type
+                            .get_faction()
+                            .is_hostile_to(&Faction::Ally)
+                        || projectile_transform
+                            .translation
+                            .xy()
+                            .distance(player_transform.translation.xy())
+                            > shield_bubble.radius
+                    {
+                        continue;
+                    }
+
+                    absorb_projectile(
+                        &mut commands,
+                        &mut spawn_effect_event_writer,
+                        projectile_entity,
+                        projectile_transform,
+                        projectile,
+                    );
+                    absorbed_projectiles.push(projectile_entity);
+                }
+            }
+        }
+    }
+}
+
+/// Collision groups of intangible players, which pass through mobs and hostile projectiles
+/// but still collide with the arena, allied projectiles, consumables, and items
+fn intangible_collision_groups() -> CollisionGroups {
+    CollisionGroups {
+        memberships: Group::ALL,
+        filters: Group::ALL
+            ^ (SPAWNABLE_COLLIDER_GROUP
+                | MOB_COLLIDER_GROUP
+                | ENEMY_PROJECTILE_COLLIDER_GROUP
+                | NEUTRAL_PROJECTILE_COLLIDER_GROUP),
+    }
+}
+
+/// Activates a dash ability (abilities with `DashAbilityComponent`)
+/// for a player for corresponding ActivateAbilityEvents.
+/// Sets the player's velocity in the input direction, and makes the player intangible while dashing
+#[allow(clippy::type_complexity)]
+pub(in crate::player) fn start_dash_ability_system(
+    mut commands: Commands,
+    mut player_query: Query<(
+        Entity,
+        &ActionState<PlayerAction>,
+        &mut Velocity,
+        &mut PlayerMovementComponent,
+        &PlayerIDComponent,
+        &Children,
+        Option<&PlayerIntangibleComponent>,
+    )>,
+    mut ability_query: Query<(&AbilitySlotIDComponent, &mut DashAbilityComponent)>,
+    mut ability_event_reader: EventReader<ActivateAbilityEvent>,
+) {
+    for event in ability_event_reader.read() {
+        for (
+            player_entity,
+            action_state,
+            mut player_velocity,
+            mut player_movement,
+            player_id,
+            children,
+            intangible,
+        ) in player_query.iter_mut()
+        {
+            for child in children.iter() {
+                if let Ok((ability_id, mut dash_ability)) = ability_query.get_mut(*child) {
+                    if event.player_id == *player_id && event.ability_slot_id == *ability_id {
+                        // dash in the input direction, or in the +y direction if no direction is pressed
+                        player_velocity.linvel = dash_ability.speed
+                            * event.power
+                            * get_input_direction(action_state).unwrap_or(Vec2::Y);
+
+                        // disable movement, and become intangible unless already intangible for longer
+                        player_movement.movement_enabled = false;
+                        let dash_time = dash_ability.action_timer.duration().as_secs_f32();
+                        if intangible.map_or(true, |intangible| {
+                            intangible.intangible_timer.remaining_secs() < dash_time
+                        }) {
+                            commands.entity(player_entity).insert((
+                                PlayerIntangibleComponent::new(dash_time),
+                                intangible_collision_groups(),
+                            ));
+                        }
+
+                        // begin the action timer for the ability
+                        dash_ability.action_timer.reset();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Updates the dash ability (`DashAbilityComponent`)
+/// Ticks the action timer, when completed enables movement and slows the player to its maximum speed
+pub(in crate::player) fn update_dash_ability_system(
+    mut player_query: Query<(&mut Velocity, &mut PlayerMovementComponent, &Children)>,
+    mut ability_query: Query<&mut DashAbilityComponent>,
+    time: Res<Time>,
+) {
+    for (mut player_velocity, mut player_movement, children) in player_query.iter_mut() {
+        for child in children.iter() {
+            if let Ok(mut dash_ability) = ability_query.get_mut(*child) {
+                dash_ability.action_timer.tick(time.delta());
+
+                if dash_ability.action_timer.just_finished() {
+                    player_velocity.linvel = player_velocity
+                        .linvel
+                        .clamp(-player_movement.speed, player_movement.speed);
+                    player_movement.movement_enabled = true;
+                }
+            }
+        }
+    }
+}
+
+/// Activates a decoy ability (abilities with `DecoyAbilityComponent`)
+/// for a player for corresponding ActivateAbilityEvents.
+/// Spawns a translucent copy of the player that mobs target and shoot at until it is destroyed or despawns
+#[allow(clippy::type_complexity)]
+pub(in crate::player) fn decoy_ability_system(
+    mut commands: Commands,
+    player_query: Query<(
+        &Transform,
+        &Handle<Image>,
+        &Collider,
+        &PlayerIDComponent,
+        &Children,
+    )>,
+    ability_query: Query<(&AbilitySlotIDComponent, &DecoyAbilityComponent)>,
+    mut ability_event_reader: EventReader<ActivateAbilityEvent>,
+) {
+    for event in ability_event_reader.read() {
+        for (player_transform, player_image, player_collider, player_id, children) in
+            player_query.iter()
+        {
+            for child in children.iter() {
+                if let Ok((ability_id, decoy_ability)) = ability_query.get(*child) {
+                    if event.player_id == *player_id && event.ability_slot_id == *ability_id {
+                        commands
+                            .spawn(SpriteBundle {
+                                texture: player_image.clone(),
+                                sprite: Sprite {
+                                    color: DECOY_COLOR.into(),
+                                    ..default()
+                                },
+                                transform: Transform {
+                                    translation: player_transform.translation
+                                        + decoy_ability.offset.extend(0.0),
+                                    scale: player_transform.scale,
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .insert(DecoyComponent::new(decoy_ability.lifetime * event.power))
+                            .insert(HealthComponent::new(decoy_ability.health, 0, 0.0))
+                            .insert(player_collider.clone())
+                            .insert(Sensor)
+                            .insert(ActiveEvents::COLLISION_EVENTS)
+                            .insert(GameCleanup)
+                            .insert(Name::new("Decoy"));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Decoys take damage from the hostile projectiles that hit them, absorbing the projectiles
+pub(in crate::player) fn decoy_hit_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    decoy_query: Query<(), With<DecoyComponent>>,
+    projectile_query: Query<(&Transform, &ProjectileComponent)>,
+    mut damage_dealt_event_writer: EventWriter<DamageDealtEvent>,
+    mut spawn_effect_event_writer: EventWriter<SpawnEffectEvent>,
+) {
+    // projectiles absorbed this frame, so a projectile hitting two decoys only damages one
+    let mut absorbed_projectiles: Vec<Entity> = vec![];
+
+    for collision_event in collision_events.read() {
+        let CollisionEvent::Started(collider1_entity, collider2_entity, _) = collision_event else {
+            continue;
+        };
+
+        let (decoy_entity, projectile_entity) = if decoy_query.contains(*collider1_entity) {
+            (*collider1_entity, *collider2_entity)
+        } else if decoy_query.contains(*collider2_entity) {
+            (*collider2_entity, *collider1_entity)
+        } else {
+            continue;
+        };
+
+        let Ok((projectile_transform, projectile)) = projectile_query.get(projectile_entity) else {
+            continue;
+        };
+
+        if absorbed_projectiles.contains(&projectile_entity)
+            || !projectile
+                .projectile_type
+                .get_faction()
+                .is_hostile_to(&Faction::Ally)
+        {
+            continue;
+        }
+
+        damage_dealt_event_writer.send(DamageDealtEvent {
+            damage: projectile.damage,
+            target: decoy_entity,
+            source: Some(projectile.source),
+            damage_type: projectile.damage_type,
+            is_critical: false,
+        });
+
+        absorb_projectile(
+            &mut commands,
+            &mut spawn_effect_event_writer,
+            projectile_entity,
+            projectile_transform,
+            projectile,
+        );
+        absorbed_projectiles.push(projectile_entity);
+    }
+}
+
+/// Despawn decoys once their lifetimes are over or they are destroyed
+pub(in crate::player) fn update_decoy_system(
+    mut commands: Commands,
+    mut decoy_query: Query<(Entity, &mut DecoyComponent, &HealthComponent)>,
+    time: Res<Time>,
+) {
+    for (entity, mut decoy, health) in decoy_query.iter_mut() {
+        if decoy.lifetime_timer.tick(time.delta()).just_finished() || health.is_dead() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,339 @@
+//! Systems for AI controlled players, such as the wingman
+
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::system::Query;
+use bevy::math::{Vec2, Vec3Swizzles};
+use bevy::transform::components::Transform;
+use leafwing_input_manager::prelude::ActionState;
+use thetawave_interface::input::PlayerAction;
+use thetawave_interface::player::{
+    PlayerAiComponent, PlayerAiData, PlayerDownedComponent, PlayerRespawnComponent,
+};
+use thetawave_interface::spawnable::Faction;
+use thetawave_interface::targeting::TargetingPolicy;
+
+use crate::spawnable::{
+    hostile_targets, select_target, ConsumableComponent, ProjectileComponent, TargetsQuery,
+};
+
+/// Press or release an action
+fn set_action(action_state: &mut ActionState<PlayerAction>, action: PlayerAction, pressed: bool) {
+    if pressed {
+        action_state.press(&action);
+    } else {
+        action_state.release(&action);
+    }
+}
+
+/// Position closest to the origin
+fn nearest(origin: Vec2, positions: impl Iterator<Item = Vec2>) -> Option<Vec2> {
+    positions.min_by(|a, b| a.distance(origin).total_cmp(&b.distance(origin)))
+}
+
+/// Position that an AI controlled player steers towards
+/// Players dodge the nearest hostile projectile first, then revive the nearest downed teammate,
+/// collect the nearest consumable, and line up under their target
+fn ai_destination(
+    data: &PlayerAiData,
+    position: Vec2,
+    threat: Option<Vec2>,
+    downed_teammate: Option<Vec2>,
+    consumable: Option<Vec2>,
+    target: Option<Vec2>,
+) -> Vec2 {
+    if let Some(threat) = threat.filter(|threat| threat.distance(position) <= data.dodge_distance) {
+        // move directly away from the projectile
+        position + (position - threat).normalize_or_zero() * data.dodge_distance
+    } else if let Some(downed_teammate) = downed_teammate {
+        downed_teammate
+    } else if let Some(consumable) =
+        consumable.filter(|consumable| consumable.distance(position) <= data.collect_distance)
+    {
+        consumable
+    } else if let Some(target) = target {
+        Vec2::new(target.x, data.preferred_y)
+    } else {
+        Vec2::new(position.x, data.preferred_y)
+    }
+}
+
+/// Direction to press on each axis to move by the offset, axes within the tolerance aren't pressed
+fn ai_move_input(offset: Vec2, tolerance: f32) -> Vec2 {
+    let axis = |offset: f32| {
+        if offset > tolerance {
+            1.0
+        } else if offset < -tolerance {
+            -1.0
+        } else {
+            0.0
+        }
+    };
+    Vec2::new(axis(offset.x), axis(offset.y))
+}
+
+/// Whether any of the targets is above the player and lined up horizontally
+fn is_lined_up(
+    data: &PlayerAiData,
+    position: Vec2,
+    mut targets: impl Iterator<Item = Vec2>,
+) -> bool {
+    targets
+        .any(|target| target.y > position.y && (target.x - position.x).abs() <= data.fire_alignment)
+}
+
+/// Steer AI controlled players by writing to their action states
+/// Players dodge hostile projectiles first, then revive downed teammates, collect consumables,
+/// and line up under targets, firing their slot one ability when lined up
+#[allow(clippy::type_complexity)]
+pub(in crate::player) fn player_ai_input_system(
+    mut ai_query: Query<
+        (
+            &PlayerAiComponent,
+            &Transform,
+            &mut ActionState<PlayerAction>,
+        ),
+        (
+            Without<PlayerDownedComponent>,
+            Without<PlayerRespawnComponent>,
+        ),
+    >,
+    downed_query: Query<&Transform, With<PlayerDownedComponent>>,
+    projectile_query: Query<(&Transform, &ProjectileComponent)>,
+    consumable_query: Query<&Transform, With<ConsumableComponent>>,
+    targets_query: TargetsQuery,
+) {
+    let targets = targets_query.targets();
+
+    for (ai, transform, mut action_state) in ai_query.iter_mut() {
+        let position = transform.translation.xy();
+
+        let threat = nearest(
+            position,
+            projectile_query
+                .iter()
+                .filter(|(_, projectile)| {
+                    projectile
+                        .projectile_type
+                        .get_faction()
+                        .is_hostile_to(&Faction::Ally)
+                })
+                .map(|(projectile_transform, _)| projectile_transform.translation.xy()),
+        );
+
+        let downed_teammate = nearest(
+            position,
+            downed_query.iter().map(|downed| downed.translation.xy()),
+        );
+
+        let consumable = nearest(
+            position,
+            consumable_query
+                .iter()
+                .map(|consumable| consumable.translation.xy()),
+        );
+
+        let target = select_target(
+            TargetingPolicy::Nearest,
+            position,
+            hostile_targets(&targets, Faction::Ally),
+        )
+        .map(|target| target.position);
+
+        let destination = ai_destination(
+            &ai.data,
+            position,
+            threat,
+            downed_teammate,
+            consumable,
+            target,
+        );
+
+        let move_input = ai_move_input(destination - position, ai.data.movement_tolerance);
+        set_action(
+            &mut action_state,
+            PlayerAction::MoveRight,
+            move_input.x > 0.0,
+        );
+        set_action(
+            &mut action_state,
+            PlayerAction::MoveLeft,
+            move_input.x < 0.0,
+        );
+        set_action(&mut action_state, PlayerAction::MoveUp, move_input.y > 0.0);
+        set_action(
+            &mut action_state,
+            PlayerAction::MoveDown,
+            move_input.y < 0.0,
+        );
+
+        // fire at targets above the player that are lined up horizontally
+        set_action(
+            &mut action_state,
+            PlayerAction::SlotOneAbility,
+            is_lined_up(
+                &ai.data,
+                position,
+                hostile_targets(&targets, Faction::Ally).map(|target| target.position),
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::app::{App, Update};
+    use bevy::math::{Vec2, Vec3};
+    use bevy::transform::components::Transform;
+    use bevy::MinimalPlugins;
+    use leafwing_input_manager::prelude::ActionState;
+    use thetawave_interface::input::PlayerAction;
+    use thetawave_interface::player::{PlayerAiComponent, PlayerAiData, PlayerDownedComponent};
+
+    use super::{ai_destination, ai_move_input, is_lined_up, player_ai_input_system};
+
+    fn ai_data() -> PlayerAiData {
+        PlayerAiData {
+            dodge_distance: 120.0,
+            collect_distance: 250.0,
+            preferred_y: 0.0,
+            movement_tolerance: 10.0,
+            fire_alignment: 30.0,
+        }
+    }
+
+    #[test]
+    fn test_ai_dodges_nearby_projectiles_first() {
+        let destination = ai_destination(
+            &ai_data(),
+            Vec2::ZERO,
+            Some(Vec2::new(0.0, 100.0)),
+            Some(Vec2::new(200.0, 0.0)),
+            Some(Vec2::new(-100.0, 0.0)),
+            Some(Vec2::new(50.0, 300.0)),
+        );
+
+        assert_eq!(destination, Vec2::new(0.0, -120.0));
+    }
+
+    #[test]
+    fn test_ai_ignores_distant_projectiles_and_consumables() {
+        let destination = ai_destination(
+            &ai_data(),
+            Vec2::ZERO,
+            Some(Vec2::new(0.0, 200.0)),
+            None,
+            Some(Vec2::new(-300.0, 0.0)),
+            Some(Vec2::new(50.0, 300.0)),
+        );
+
+        assert_eq!(destination, Vec2::new(50.0, 0.0));
+    }
+
+    #[test]
+    fn test_ai_revives_downed_teammates_before_collecting() {
+        let destination = ai_destination(
+            &ai_data(),
+            Vec2::ZERO,
+            None,
+            Some(Vec2::new(200.0, -50.0)),
+            Some(Vec2::new(-100.0, 0.0)),
+            None,
+        );
+
+        assert_eq!(destination, Vec2::new(200.0, -50.0));
+    }
+
+    #[test]
+    fn test_ai_move_input_ignores_offsets_within_tolerance() {
+        assert_eq!(
+            ai_move_input(Vec2::new(50.0, -5.0), 10.0),
+            Vec2::new(1.0, 0.0)
+        );
+        assert_eq!(
+            ai_move_input(Vec2::new(-50.0, 20.0), 10.0),
+            Vec2::new(-1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_ai_fires_at_lined_up_targets_above() {
+        let data = ai_data();
+
+        assert!(is_lined_up(
+            &data,
+            Vec2::ZERO,
+            [Vec2::new(20.0, 200.0)].into_iter()
+        ));
+        assert!(!is_lined_up(
+            &data,
+            Vec2::ZERO,
+            [Vec2::new(100.0, 200.0), Vec2::new(0.0, -200.0)].into_iter()
+        ));
+    }
+
+    /// Headless balance check: a solo AI player survives a projectile aimed next to it that would
+    /// hit a player standing still
+    #[test]
+    fn test_ai_player_avoids_falling_projectile() {
+        const PLAYER_SPEED: f32 = 400.0;
+        const PROJECTILE_SPEED: f32 = 300.0;
+        const HIT_DISTANCE: f32 = 60.0;
+        const DELTA_SECONDS: f32 = 1.0 / 60.0;
+
+        let data = ai_data();
+        let mut position = Vec2::ZERO;
+        let mut projectile = Vec2::new(20.0, 300.0);
+        let mut closest_distance = f32::MAX;
+
+        for _ in 0..180 {
+            let destination = ai_destination(&data, position, Some(projectile), None, None, None);
+            position += ai_move_input(destination - position, data.movement_tolerance)
+                * PLAYER_SPEED
+                * DELTA_SECONDS;
+            projectile.y -= PROJECTILE_SPEED * DELTA_SECONDS;
+            closest_distance = closest_distance.min(position.distance(projectile));
+        }
+
+        assert!(
+            closest_distance > HIT_DISTANCE,
+            "projectile came within {} of the AI player",
+            closest_distance
+        );
+        assert!(
+            projectile.y < position.y,
+            "projectile never passed the player"
+        );
+    }
+
+    #[test]
+    fn test_ai_input_system_writes_action_state() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_systems(Update, player_ai_input_system);
+
+        let ai_player = app
+            .world_mut()
+            .spawn((
+                PlayerAiComponent::from(&ai_data()),
+                Transform::from_translation(Vec3::ZERO),
+                ActionState::<PlayerAction>::default(),
+            ))
+            .id();
+        app.world_mut().spawn((
+            PlayerDownedComponent::new(10.0, 3.0),
+            Transform::from_translation(Vec3::new(200.0, 0.0, 0.0)),
+        ));
+
+        app.update();
+
+        let action_state = app
+            .world()
+            .get::<ActionState<PlayerAction>>(ai_player)
+            .unwrap();
+        assert!(action_state.pressed(&PlayerAction::MoveRight));
+        assert!(!action_state.pressed(&PlayerAction::MoveLeft));
+        assert!(!action_state.pressed(&PlayerAction::MoveUp));
+        assert!(!action_state.pressed(&PlayerAction::MoveDown));
+        assert!(!action_state.pressed(&PlayerAction::SlotOneAbility));
+    }
+}
@@ -0,0 +1,186 @@
+//! Systems for aiming slot one abilities in the free aim mode
+use bevy::core_pipeline::core_2d::Camera2d;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::hierarchy::DespawnRecursiveExt;
+use bevy::math::{Vec2, Vec3Swizzles};
+use bevy::render::{camera::Camera, view::Visibility};
+use bevy::transform::components::{GlobalTransform, Transform};
+use bevy::window::{PrimaryWindow, Window};
+use leafwing_input_manager::action_state::ActionState;
+use thetawave_interface::input::PlayerAction;
+use thetawave_interface::player::{AimReticleComponent, AimSource, PlayerAimComponent};
+
+use crate::game::GameParametersResource;
+
+/// Update the aim of players in the free aim mode from the mouse cursor or their aim stick
+/// Players aiming with a stick that is within the deadzone aren't aiming
+pub(in crate::player) fn player_aim_system(
+    mut player_query: Query<(
+        &ActionState<PlayerAction>,
+        &Transform,
+        &mut PlayerAimComponent,
+    )>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    game_parameters: Res<GameParametersResource>,
+) {
+    // position of the cursor in the arena, accounting for the camera's zoom and translation
+    let cursor_position = window_query
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get_single().ok())
+        .and_then(|(mouse_pos, (camera, camera_transform))| {
+            camera.viewport_to_world_2d(camera_transform, mouse_pos)
+        });
+
+    for (action_state, transform, mut aim) in player_query.iter_mut() {
+        let player_position = transform.translation.xy();
+
+        let (aim_vector, reticle_position) = match aim.source {
+            AimSource::Cursor => (
+                cursor_position.map(|cursor_position| cursor_position - player_position),
+                cursor_position,
+            ),
+            AimSource::Stick => stick_aim(
+                action_state.axis_pair(&PlayerAction::Aim),
+                player_position,
+                &game_parameters,
+            )
+            .unzip(),
+        };
+
+        aim.direction = aim_vector.and_then(aim_direction);
+        aim.reticle_position = reticle_position.filter(|_| aim.direction.is_some());
+    }
+}
+
+/// Aim vector and reticle position of an aim stick, or `None` if the stick is within the deadzone
+fn stick_aim(
+    stick: Vec2,
+    player_position: Vec2,
+    game_parameters: &GameParametersResource,
+) -> Option<(Vec2, Vec2)> {
+    (stick.length() >= game_parameters.aim_stick_deadzone).then(|| {
+        (
+            stick,
+            player_position + stick.normalize_or_zero() * game_parameters.aim_reticle_distance,
+        )
+    })
+}
+
+/// Angle in radians of an aim vector, or `None` if the vector doesn't point anywhere
+fn aim_direction(aim_vector: Vec2) -> Option<f32> {
+    (aim_vector != Vec2::ZERO).then(|| aim_vector.y.atan2(aim_vector.x))
+}
+
+/// Move aim reticles to where their players are aiming, hiding them while their players aren't aiming
+/// Reticles of players that no longer exist are despawned
+pub(in crate::player) fn aim_reticle_system(
+    mut commands: Commands,
+    mut reticle_query: Query<
+        (
+            Entity,
+            &AimReticleComponent,
+            &mut Transform,
+            &mut Visibility,
+        ),
+        Without<PlayerAimComponent>,
+    >,
+    player_query: Query<&PlayerAimComponent>,
+) {
+    for (entity, reticle, mut transform, mut visibility) in reticle_query.iter_mut() {
+        let Ok(aim) = player_query.get(reticle.player) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        if let Some(reticle_position) = aim.reticle_position {
+            transform.translation = reticle_position.extend(transform.translation.z);
+            *visibility = Visibility::Inherited;
+        } else {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::math::Vec2;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    use super::{aim_direction, stick_aim};
+    use crate::game::GameParametersResource;
+
+    fn game_parameters() -> GameParametersResource {
+        ron::de::from_bytes::<GameParametersResource>(include_bytes!(
+            "../../../assets/data/game_parameters.ron"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_stick_within_deadzone_does_not_aim() {
+        let game_parameters = game_parameters();
+        let stick = Vec2::new(0.6, 0.8) * (game_parameters.aim_stick_deadzone * 0.9);
+
+        assert_eq!(stick_aim(stick, Vec2::ZERO, &game_parameters), None);
+        assert_eq!(stick_aim(Vec2::ZERO, Vec2::ZERO, &game_parameters), None);
+    }
+
+    #[test]
+    fn test_stick_outside_deadzone_places_reticle_at_fixed_distance() {
+        let game_parameters = game_parameters();
+        let player_position = Vec2::new(100.0, -50.0);
+
+        for stick_length in [game_parameters.aim_stick_deadzone, 1.0] {
+            let stick = Vec2::new(0.6, 0.8) * stick_length;
+            let (aim_vector, reticle_position) =
+                stick_aim(stick, player_position, &game_parameters).unwrap();
+
+            assert_eq!(aim_vector, stick);
+            assert!(
+                (reticle_position
+                    - (player_position
+                        + Vec2::new(0.6, 0.8) * game_parameters.aim_reticle_distance))
+                    .length()
+                    < 1e-3
+            );
+        }
+    }
+
+    #[test]
+    fn test_aim_direction_is_angle_from_positive_x_axis() {
+        for (aim_vector, expected_angle) in [
+            (Vec2::new(1.0, 0.0), 0.0),
+            (Vec2::new(0.0, 3.0), FRAC_PI_2),
+            (Vec2::new(2.0, 2.0), FRAC_PI_4),
+            (Vec2::new(-5.0, 0.0), PI),
+            (Vec2::new(0.0, -1.0), -FRAC_PI_2),
+        ] {
+            let angle = aim_direction(aim_vector).unwrap();
+            assert!(
+                (angle - expected_angle).abs() < 1e-5,
+                "{aim_vector:?} aimed at {angle} instead of {expected_angle}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cursor_on_player_does_not_aim() {
+        let player_position = Vec2::new(20.0, 30.0);
+        assert_eq!(aim_direction(player_position - player_position), None);
+    }
+
+    #[test]
+    fn test_cursor_aims_relative_to_player() {
+        // a cursor above and to the left of a player that is off center in the arena
+        let player_position = Vec2::new(100.0, -100.0);
+        let cursor_position = Vec2::new(0.0, 0.0);
+
+        let angle = aim_direction(cursor_position - player_position).unwrap();
+        assert!((angle - 3.0 * FRAC_PI_4).abs() < 1e-5);
+    }
+}
@@ -1,72 +1,684 @@
 //! Systems for managing players
 
 pub mod abilities;
+pub mod ai;
+pub mod aim;
 pub mod movement;
 pub mod upgrades;
 
 use crate::{game::GameParametersResource, spawnable::SpawnEffectEvent};
 
+use bevy::color::{Color, Mix, Srgba};
 use bevy::ecs::entity::Entity;
 use bevy::ecs::event::EventWriter;
-use bevy::ecs::query::With;
+use bevy::ecs::query::{Has, With, Without};
 use bevy::ecs::system::{Commands, Query, Res, ResMut};
 use bevy::hierarchy::DespawnRecursiveExt;
-use bevy::math::Vec3;
+use bevy::math::{Vec2, Vec3, Vec3Swizzles};
+use bevy::render::view::Visibility;
+use bevy::sprite::Sprite;
+use bevy::time::Time;
 use bevy::transform::components::Transform;
 use bevy::utils::default;
+use bevy_rapier2d::dynamics::Velocity;
+use bevy_rapier2d::geometry::{ColliderDisabled, CollisionGroups};
 use thetawave_interface::audio::{PlaySoundEffectEvent, SoundEffectType};
 use thetawave_interface::health::HealthComponent;
-use thetawave_interface::player::PlayerComponent;
+use thetawave_interface::player::{
+    PlayerComponent, PlayerDownedComponent, PlayerDownedEvent, PlayerIDComponent,
+    PlayerIntangibleComponent, PlayerInventoryComponent, PlayerInvulnerableComponent,
+    PlayerRespawnComponent, PlayerRevivedEvent, WingmanComponent,
+};
 use thetawave_interface::run::{RunDefeatType, RunEndEvent, RunOutcomeType};
 use thetawave_interface::spawnable::EffectType;
+use thetawave_interface::status_effect::StatusEffectsComponent;
 
-use super::PlayersResource;
+use super::{CharactersResource, PlayersResource};
+
+/// Color of downed players before a teammate starts reviving them
+const DOWNED_COLOR: Srgba = Srgba::new(0.3, 0.3, 0.3, 1.0);
+
+/// Time in seconds that invulnerable players spend visible or hidden on each blink
+const INVULNERABLE_BLINK_PERIOD: f32 = 0.1;
 
 /// Handle player reaching zero health
+/// Players with lives left lose a life and respawn, otherwise players are downed instead of
+/// destroyed while a teammate is still playing
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub(super) fn player_death_system(
     mut commands: Commands,
     mut effect_event_writer: EventWriter<SpawnEffectEvent>,
-    player_query: Query<(Entity, &Transform, &HealthComponent), With<PlayerComponent>>,
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &HealthComponent,
+            &PlayerIDComponent,
+            &mut PlayerInventoryComponent,
+            &mut Sprite,
+            &mut Visibility,
+            &mut Velocity,
+            Has<PlayerDownedComponent>,
+            Has<PlayerRespawnComponent>,
+        ),
+        With<PlayerComponent>,
+    >,
+    wingman_query: Query<(), With<WingmanComponent>>,
     mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
     game_parameters: Res<GameParametersResource>,
     mut run_end_event_writer: EventWriter<RunEndEvent>,
+    mut player_downed_event_writer: EventWriter<PlayerDownedEvent>,
 ) {
-    // end the game if no players are alive
-    if player_query.iter().count() == 0 {
+    // end the game if no players are alive, downed players can't be revived without a teammate
+    // an alive wingman can revive downed players, but doesn't keep the game going on its own
+    let all_players_downed = player_query
+        .iter()
+        .all(|(_, _, _, _, _, _, _, _, is_downed, _)| is_downed);
+    let only_wingmen_left = player_query
+        .iter()
+        .all(|(entity, ..)| wingman_query.contains(entity));
+    if all_players_downed || only_wingmen_left {
         run_end_event_writer.send(RunEndEvent {
             outcome: RunOutcomeType::Defeat(RunDefeatType::PlayersDestroyed),
         });
     }
 
+    // respawning players count as active since they will return to revive their teammates
+    let active_player_count = player_query
+        .iter()
+        .filter(|(_, _, health, _, _, _, _, _, is_downed, is_respawning)| {
+            !is_downed && (*is_respawning || !health.is_dead())
+        })
+        .count();
+
     // handle death of player entities
-    for (entity, transform, health) in player_query.iter() {
-        if health.is_dead() {
-            // despawn the player
-            commands.entity(entity).despawn_recursive();
-
-            // spawn explosion effect
-            effect_event_writer.send(SpawnEffectEvent {
-                effect_type: EffectType::MobExplosion,
-                transform: Transform {
-                    translation: transform.translation,
-                    scale: Vec3::new(
-                        game_parameters.sprite_scale,
-                        game_parameters.sprite_scale,
-                        1.0,
-                    ),
-                    ..Default::default()
-                },
-                ..default()
+    for (
+        entity,
+        transform,
+        health,
+        player_id,
+        mut inventory,
+        mut sprite,
+        mut visibility,
+        mut velocity,
+        is_downed,
+        is_respawning,
+    ) in player_query.iter_mut()
+    {
+        if !health.is_dead() || is_downed || is_respawning {
+            continue;
+        }
+
+        if let Some(lives) = inventory.lives.as_mut().filter(|lives| **lives > 0) {
+            // lose a life, and hide the player until it respawns
+            *lives -= 1;
+            commands.entity(entity).insert((
+                PlayerRespawnComponent::new(game_parameters.respawn_time),
+                ColliderDisabled,
+            ));
+            *visibility = Visibility::Hidden;
+            velocity.linvel = Vec2::ZERO;
+            spawn_player_explosion(
+                transform,
+                &mut effect_event_writer,
+                &mut sound_effect_event_writer,
+                &game_parameters,
+            );
+        } else if active_player_count > 0 {
+            // down the player so that a teammate can revive it
+            commands.entity(entity).insert(PlayerDownedComponent::new(
+                game_parameters.bleed_out_time,
+                game_parameters.revive_time,
+            ));
+            sprite.color = DOWNED_COLOR.into();
+            player_downed_event_writer.send(PlayerDownedEvent {
+                player_id: *player_id,
             });
+        } else {
+            destroy_player(
+                &mut commands,
+                entity,
+                transform,
+                &mut effect_event_writer,
+                &mut sound_effect_event_writer,
+                &game_parameters,
+            );
+        }
+    }
+}
+
+/// Revive downed players that have a teammate nearby, and destroy downed players that bleed out
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(super) fn player_revive_system(
+    mut commands: Commands,
+    mut downed_query: Query<(
+        Entity,
+        &Transform,
+        &mut HealthComponent,
+        &mut PlayerDownedComponent,
+        &PlayerIDComponent,
+        &mut Sprite,
+    )>,
+    active_player_query: Query<
+        (&Transform, &HealthComponent),
+        (
+            With<PlayerComponent>,
+            Without<PlayerDownedComponent>,
+            Without<PlayerRespawnComponent>,
+        ),
+    >,
+    time: Res<Time>,
+    mut effect_event_writer: EventWriter<SpawnEffectEvent>,
+    mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+    game_parameters: Res<GameParametersResource>,
+    mut player_revived_event_writer: EventWriter<PlayerRevivedEvent>,
+) {
+    for (entity, transform, mut health, mut downed, player_id, mut sprite) in
+        downed_query.iter_mut()
+    {
+        let is_teammate_nearby =
+            active_player_query
+                .iter()
+                .any(|(teammate_transform, teammate_health)| {
+                    !teammate_health.is_dead()
+                        && teammate_transform
+                            .translation
+                            .xy()
+                            .distance(transform.translation.xy())
+                            <= game_parameters.revive_distance
+                });
 
-            // play explosion sound effect
-            sound_effect_event_writer.send(PlaySoundEffectEvent {
-                sound_effect_type: SoundEffectType::PlayerExplosion,
+        if is_teammate_nearby {
+            downed.revive_timer.tick(time.delta());
+        } else {
+            downed.revive_timer.reset();
+            downed.bleed_out_timer.tick(time.delta());
+        }
+
+        if downed.revive_timer.finished() {
+            let revive_health =
+                (health.get_max_health() as f32 * game_parameters.revive_health).ceil() as usize;
+            health.heal(revive_health);
+            sprite.color = Color::WHITE;
+            commands.entity(entity).remove::<PlayerDownedComponent>();
+            player_revived_event_writer.send(PlayerRevivedEvent {
+                player_id: *player_id,
             });
+        } else if downed.bleed_out_timer.finished() {
+            destroy_player(
+                &mut commands,
+                entity,
+                transform,
+                &mut effect_event_writer,
+                &mut sound_effect_event_writer,
+                &game_parameters,
+            );
+        } else {
+            // brighten the wreck as it is revived
+            sprite.color = DOWNED_COLOR
+                .mix(&Srgba::WHITE, downed.revive_timer.fraction())
+                .into();
+        }
+    }
+}
+
+/// Respawn players at the bottom of the arena with full health once their respawn timers finish
+#[allow(clippy::type_complexity)]
+pub(super) fn player_respawn_system(
+    mut commands: Commands,
+    mut player_query: Query<(
+        Entity,
+        &mut Transform,
+        &mut HealthComponent,
+        &mut PlayerRespawnComponent,
+        &mut Visibility,
+        &mut Velocity,
+        Option<&mut StatusEffectsComponent>,
+    )>,
+    time: Res<Time>,
+    game_parameters: Res<GameParametersResource>,
+) {
+    for (
+        entity,
+        mut transform,
+        mut health,
+        mut respawn,
+        mut visibility,
+        mut velocity,
+        status_effects,
+    ) in player_query.iter_mut()
+    {
+        if !respawn.respawn_timer.tick(time.delta()).finished() {
+            continue;
+        }
+
+        transform.translation.y = game_parameters.respawn_y;
+        velocity.linvel = Vec2::ZERO;
+        health.full_heal();
+        if let Some(mut status_effects) = status_effects {
+            status_effects.clear();
+        }
+        *visibility = Visibility::Inherited;
+
+        commands
+            .entity(entity)
+            .remove::<(PlayerRespawnComponent, ColliderDisabled)>()
+            .insert(PlayerInvulnerableComponent::new(
+                game_parameters.respawn_invulnerability_time,
+            ));
+    }
+}
+
+/// Blink invulnerable players, and make them vulnerable again once their timers finish
+pub(super) fn player_invulnerability_system(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut PlayerInvulnerableComponent, &mut Visibility)>,
+    time: Res<Time>,
+) {
+    for (entity, mut invulnerable, mut visibility) in player_query.iter_mut() {
+        invulnerable.invulnerable_timer.tick(time.delta());
+
+        if invulnerable.invulnerable_timer.finished() {
+            *visibility = Visibility::Inherited;
+            commands
+                .entity(entity)
+                .remove::<PlayerInvulnerableComponent>();
+            continue;
         }
+
+        // alternate between hidden and visible every blink period
+        let blink =
+            (invulnerable.invulnerable_timer.elapsed_secs() / INVULNERABLE_BLINK_PERIOD) as usize;
+        *visibility = if blink % 2 == 1 {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+}
+
+/// Make intangible players collide with mobs and hostile projectiles again once their timers finish
+pub(super) fn player_intangibility_system(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut PlayerIntangibleComponent)>,
+    time: Res<Time>,
+) {
+    for (entity, mut intangible) in player_query.iter_mut() {
+        if intangible.intangible_timer.tick(time.delta()).finished() {
+            commands
+                .entity(entity)
+                .remove::<(PlayerIntangibleComponent, CollisionGroups)>();
+        }
+    }
+}
+
+/// Despawn a player with an explosion
+fn destroy_player(
+    commands: &mut Commands,
+    entity: Entity,
+    transform: &Transform,
+    effect_event_writer: &mut EventWriter<SpawnEffectEvent>,
+    sound_effect_event_writer: &mut EventWriter<PlaySoundEffectEvent>,
+    game_parameters: &GameParametersResource,
+) {
+    // despawn the player
+    commands.entity(entity).despawn_recursive();
+
+    spawn_player_explosion(
+        transform,
+        effect_event_writer,
+        sound_effect_event_writer,
+        game_parameters,
+    );
+}
+
+/// Spawn an explosion effect and sound where a player was destroyed or lost a life
+fn spawn_player_explosion(
+    transform: &Transform,
+    effect_event_writer: &mut EventWriter<SpawnEffectEvent>,
+    sound_effect_event_writer: &mut EventWriter<PlaySoundEffectEvent>,
+    game_parameters: &GameParametersResource,
+) {
+    // spawn explosion effect
+    effect_event_writer.send(SpawnEffectEvent {
+        effect_type: EffectType::MobExplosion,
+        transform: Transform {
+            translation: transform.translation,
+            scale: Vec3::new(
+                game_parameters.sprite_scale,
+                game_parameters.sprite_scale,
+                1.0,
+            ),
+            ..Default::default()
+        },
+        ..default()
+    });
+
+    // play explosion sound effect
+    sound_effect_event_writer.send(PlaySoundEffectEvent {
+        sound_effect_type: SoundEffectType::PlayerExplosion,
+    });
+}
+
+/// Check at startup that the character selection and the wingman only refer to characters that exist
+/// The wingman is only checked if the game parameters are loaded
+pub(super) fn validate_characters_system(
+    characters_res: Res<CharactersResource>,
+    game_parameters: Option<Res<GameParametersResource>>,
+) {
+    let wingman = game_parameters
+        .as_ref()
+        .and_then(|game_parameters| game_parameters.wingman.as_ref());
+    if let Err(e) = characters_res.validate(wingman) {
+        panic!("Invalid character data. {e}");
     }
 }
 
 pub(super) fn players_reset_system(mut players_resource: ResMut<PlayersResource>) {
     *players_resource = PlayersResource::default();
 }
+
+#[cfg(test)]
+mod test {
+    use bevy::app::{App, Update};
+    use bevy::ecs::entity::Entity;
+    use bevy::ecs::event::Events;
+    use bevy::render::view::Visibility;
+    use bevy::sprite::Sprite;
+    use bevy::time::TimeUpdateStrategy;
+    use bevy::transform::components::Transform;
+    use bevy::MinimalPlugins;
+    use bevy_rapier2d::dynamics::Velocity;
+    use bevy_rapier2d::geometry::CollisionGroups;
+    use std::time::Duration;
+    use thetawave_interface::audio::PlaySoundEffectEvent;
+    use thetawave_interface::health::{DamageType, HealthComponent};
+    use thetawave_interface::player::{
+        PlayerComponent, PlayerDownedComponent, PlayerDownedEvent, PlayerIDComponent,
+        PlayerIntangibleComponent, PlayerInventoryComponent, PlayerRevivedEvent, WingmanComponent,
+    };
+    use thetawave_interface::run::RunEndEvent;
+
+    use super::{player_death_system, player_intangibility_system, player_revive_system};
+    use crate::{game::GameParametersResource, spawnable::SpawnEffectEvent};
+
+    fn death_test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_event::<SpawnEffectEvent>()
+            .add_event::<PlaySoundEffectEvent>()
+            .add_event::<RunEndEvent>()
+            .add_event::<PlayerDownedEvent>()
+            .insert_resource(
+                ron::de::from_bytes::<GameParametersResource>(include_bytes!(
+                    "../../../assets/data/game_parameters.ron"
+                ))
+                .unwrap(),
+            )
+            .add_systems(Update, player_death_system);
+        app
+    }
+
+    fn spawn_player(app: &mut App, player_id: PlayerIDComponent, is_dead: bool) -> Entity {
+        let mut health = HealthComponent::new(10, 0, 1.0);
+        if is_dead {
+            health.take_damage(10, DamageType::Collision);
+        }
+
+        app.world_mut()
+            .spawn((
+                PlayerComponent,
+                player_id,
+                health,
+                PlayerInventoryComponent {
+                    money: 0,
+                    lives: None,
+                },
+                Transform::default(),
+                Sprite::default(),
+                Visibility::default(),
+                Velocity::default(),
+            ))
+            .id()
+    }
+
+    fn run_ended(app: &App) -> bool {
+        !app.world().resource::<Events<RunEndEvent>>().is_empty()
+    }
+
+    #[test]
+    fn test_alive_wingman_keeps_run_going() {
+        let mut app = death_test_app();
+        let human = spawn_player(&mut app, PlayerIDComponent::One, true);
+        let wingman = spawn_player(&mut app, PlayerIDComponent::Two, false);
+        app.world_mut().entity_mut(wingman).insert(WingmanComponent);
+
+        // the human is downed on the first frame, and the run continues on the next
+        app.update();
+        app.update();
+
+        assert!(app.world().get::<PlayerDownedComponent>(human).is_some());
+        assert!(!run_ended(&app));
+    }
+
+    #[test]
+    fn test_run_ends_when_wingman_is_also_downed() {
+        let mut app = death_test_app();
+        let human = spawn_player(&mut app, PlayerIDComponent::One, false);
+        let wingman = spawn_player(&mut app, PlayerIDComponent::Two, false);
+        app.world_mut()
+            .entity_mut(wingman)
+            .insert((WingmanComponent, PlayerDownedComponent::new(10.0, 3.0)));
+        app.world_mut()
+            .entity_mut(human)
+            .insert(PlayerDownedComponent::new(10.0, 3.0));
+
+        app.update();
+
+        assert!(run_ended(&app));
+    }
+
+    #[test]
+    fn test_wingman_alone_ends_run() {
+        let mut app = death_test_app();
+        let wingman = spawn_player(&mut app, PlayerIDComponent::Two, false);
+        app.world_mut().entity_mut(wingman).insert(WingmanComponent);
+
+        app.update();
+
+        assert!(run_ended(&app));
+    }
+
+    #[test]
+    fn test_intangible_player_collides_again_after_timer() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+                0.1,
+            )))
+            .add_systems(Update, player_intangibility_system);
+
+        let player = app
+            .world_mut()
+            .spawn((
+                PlayerIntangibleComponent::new(0.25),
+                CollisionGroups::default(),
+            ))
+            .id();
+
+        // the first update has no elapsed time
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(app
+            .world()
+            .get::<PlayerIntangibleComponent>(player)
+            .is_some());
+
+        app.update();
+        assert!(app
+            .world()
+            .get::<PlayerIntangibleComponent>(player)
+            .is_none());
+        assert!(app.world().get::<CollisionGroups>(player).is_none());
+    }
+
+    fn revive_test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+                0.1,
+            )))
+            .add_event::<SpawnEffectEvent>()
+            .add_event::<PlaySoundEffectEvent>()
+            .add_event::<PlayerRevivedEvent>()
+            .insert_resource(
+                ron::de::from_bytes::<GameParametersResource>(include_bytes!(
+                    "../../../assets/data/game_parameters.ron"
+                ))
+                .unwrap(),
+            )
+            .add_systems(Update, player_revive_system);
+        app
+    }
+
+    fn spawn_downed_player(app: &mut App, bleed_out_time: f32, revive_time: f32) -> Entity {
+        let player = spawn_player(app, PlayerIDComponent::One, true);
+        app.world_mut()
+            .entity_mut(player)
+            .insert(PlayerDownedComponent::new(bleed_out_time, revive_time));
+        player
+    }
+
+    fn spawn_teammate(app: &mut App, x: f32, is_dead: bool) -> Entity {
+        let teammate = spawn_player(app, PlayerIDComponent::Two, is_dead);
+        app.world_mut()
+            .get_mut::<Transform>(teammate)
+            .unwrap()
+            .translation
+            .x = x;
+        teammate
+    }
+
+    fn revive_distance(app: &App) -> f32 {
+        app.world()
+            .resource::<GameParametersResource>()
+            .revive_distance
+    }
+
+    fn is_downed(app: &App, player: Entity) -> bool {
+        app.world().get::<PlayerDownedComponent>(player).is_some()
+    }
+
+    #[test]
+    fn test_nearby_teammate_revives_after_revive_time() {
+        let mut app = revive_test_app();
+        let downed = spawn_downed_player(&mut app, 10.0, 0.25);
+        let distance = revive_distance(&app);
+        spawn_teammate(&mut app, distance, false);
+
+        // the first update has no elapsed time
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(is_downed(&app, downed));
+        assert!(app
+            .world()
+            .resource::<Events<PlayerRevivedEvent>>()
+            .is_empty());
+
+        app.update();
+        assert!(!is_downed(&app, downed));
+        assert!(!app
+            .world()
+            .resource::<Events<PlayerRevivedEvent>>()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_revive_heals_by_revive_health() {
+        let mut app = revive_test_app();
+        let downed = spawn_downed_player(&mut app, 10.0, 0.05);
+        spawn_teammate(&mut app, 0.0, false);
+
+        app.update();
+        app.update();
+
+        let revive_health = app
+            .world()
+            .resource::<GameParametersResource>()
+            .revive_health;
+        let health = app.world().get::<HealthComponent>(downed).unwrap();
+        assert!(!health.is_dead());
+        assert_eq!(
+            health.get_health(),
+            (health.get_max_health() as f32 * revive_health).ceil() as usize
+        );
+    }
+
+    #[test]
+    fn test_revive_timer_resets_without_nearby_teammate() {
+        let mut app = revive_test_app();
+        let downed = spawn_downed_player(&mut app, 10.0, 0.25);
+        let distance = revive_distance(&app);
+        let teammate = spawn_teammate(&mut app, 0.0, false);
+
+        for _ in 0..3 {
+            app.update();
+        }
+
+        // moving away from the downed player loses the revive progress
+        app.world_mut()
+            .get_mut::<Transform>(teammate)
+            .unwrap()
+            .translation
+            .x = distance + 1.0;
+        app.update();
+        let downed_component = app.world().get::<PlayerDownedComponent>(downed).unwrap();
+        assert_eq!(downed_component.revive_timer.elapsed_secs(), 0.0);
+        assert!(downed_component.bleed_out_timer.elapsed_secs() > 0.0);
+
+        // the full revive time is needed after coming back
+        app.world_mut()
+            .get_mut::<Transform>(teammate)
+            .unwrap()
+            .translation
+            .x = 0.0;
+        for _ in 0..2 {
+            app.update();
+        }
+        assert!(is_downed(&app, downed));
+
+        app.update();
+        assert!(!is_downed(&app, downed));
+    }
+
+    #[test]
+    fn test_downed_player_bleeds_out_without_living_teammates() {
+        let mut app = revive_test_app();
+        let downed = spawn_downed_player(&mut app, 0.25, 0.05);
+
+        // dead and downed teammates can't revive
+        spawn_teammate(&mut app, 0.0, true);
+        let downed_teammate = spawn_teammate(&mut app, 0.0, true);
+        app.world_mut()
+            .entity_mut(downed_teammate)
+            .insert(PlayerDownedComponent::new(10.0, 10.0));
+
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(app.world().get_entity(downed).is_some());
+        assert!(app
+            .world()
+            .resource::<Events<SpawnEffectEvent>>()
+            .is_empty());
+
+        app.update();
+        assert!(app.world().get_entity(downed).is_none());
+        assert!(!app
+            .world()
+            .resource::<Events<SpawnEffectEvent>>()
+            .is_empty());
+    }
+}
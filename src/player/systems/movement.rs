@@ -1,24 +1,32 @@
 use std::f32::consts::PI;
 
-use bevy::ecs::query::With;
+use bevy::ecs::query::{With, Without};
 use bevy::ecs::system::{Query, Res};
 use bevy::transform::components::Transform;
 use bevy_rapier2d::dynamics::Velocity;
 use leafwing_input_manager::prelude::ActionState;
 
 use thetawave_interface::input::PlayerAction;
-use thetawave_interface::player::{PlayerComponent, PlayerMovementComponent};
+use thetawave_interface::player::{
+    PlayerComponent, PlayerDownedComponent, PlayerMovementComponent, PlayerRespawnComponent,
+};
 
 use crate::game::GameParametersResource;
 
-/// Move player by modifying velocity with input
+/// Move player by modifying velocity with input, downed players drift
 pub(in crate::player) fn player_movement_system(
     game_parameters: Res<GameParametersResource>,
-    mut player_info: Query<(
-        &PlayerMovementComponent,
-        &mut Velocity,
-        &ActionState<PlayerAction>,
-    )>,
+    mut player_info: Query<
+        (
+            &PlayerMovementComponent,
+            &mut Velocity,
+            &ActionState<PlayerAction>,
+        ),
+        (
+            Without<PlayerDownedComponent>,
+            Without<PlayerRespawnComponent>,
+        ),
+    >,
 ) {
     for (player_movement, mut vel, action_state) in player_info.iter_mut() {
         let up = action_state.pressed(&PlayerAction::MoveUp);
@@ -1,5 +1,19 @@
-use bevy::ecs::{query::Changed, system::Query};
-use thetawave_interface::player::{PlayerInventoryComponent, PlayerOutgoingDamageComponent};
+use bevy::ecs::{
+    event::EventReader,
+    query::{Changed, QueryData},
+    system::Query,
+};
+use bevy::hierarchy::Children;
+use bevy::log::info;
+use bevy::time::Timer;
+use thetawave_interface::abilities::{
+    AbilityCooldownComponent, AbilityLevelComponent, AbilitySlotIDComponent, AbilityUpgrade,
+    BeamAbilityComponent, ChargeAbilityComponent, DashAbilityComponent, DecoyAbilityComponent,
+    ShieldBubbleAbilityComponent, StandardWeaponAbilityComponent, UpgradeAbilityEvent,
+};
+use thetawave_interface::player::{
+    PlayerIDComponent, PlayerInventoryComponent, PlayerOutgoingDamageComponent,
+};
 
 trait PlayerOutgoingDamageComponentExt {
     fn update_cooldown_multiplier_from_collected_money(&mut self, money: usize);
@@ -28,3 +42,317 @@ pub(in crate::player) fn scale_ability_cooldowns_system(
         player_damage.update_cooldown_multiplier_from_collected_money(player_inventory.money);
     }
 }
+
+/// Ability components of an ability entity that can be changed by upgrades
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub(in crate::player) struct AbilityUpgradeQuery {
+    slot: &'static AbilitySlotIDComponent,
+    level: &'static mut AbilityLevelComponent,
+    cooldown: &'static mut AbilityCooldownComponent,
+    standard_weapon: Option<&'static mut StandardWeaponAbilityComponent>,
+    beam: Option<&'static mut BeamAbilityComponent>,
+    charge: Option<&'static mut ChargeAbilityComponent>,
+    shield_bubble: Option<&'static mut ShieldBubbleAbilityComponent>,
+    dash: Option<&'static mut DashAbilityComponent>,
+    decoy: Option<&'static mut DecoyAbilityComponent>,
+}
+
+impl AbilityUpgradeQueryItem<'_> {
+    /// Apply an upgrade to the ability, upgrades that don't affect the ability's type are ignored
+    fn apply_upgrade(
+        &mut self,
+        upgrade: &AbilityUpgrade,
+        player_damage: &mut PlayerOutgoingDamageComponent,
+    ) {
+        match upgrade {
+            AbilityUpgrade::CooldownMultiplier(multiplier) => {
+                self.cooldown.base_cooldown_time *= multiplier;
+            }
+            AbilityUpgrade::DamageMultiplier(multiplier) => {
+                if let Some(standard_weapon) = self.standard_weapon.as_mut() {
+                    standard_weapon.damage_multiplier *= multiplier;
+                }
+                if let Some(beam) = self.beam.as_mut() {
+                    beam.damage_multiplier *= multiplier;
+                }
+            }
+            AbilityUpgrade::AddProjectileCount(count) => {
+                if let Some(standard_weapon) = self.standard_weapon.as_mut() {
+                    standard_weapon.extra_count += count;
+                }
+            }
+            AbilityUpgrade::AddPierce(count) => {
+                if let Some(standard_weapon) = self.standard_weapon.as_mut() {
+                    standard_weapon.extra_pierce += count;
+                }
+            }
+            AbilityUpgrade::SizeMultiplier(multiplier) => {
+                if let Some(standard_weapon) = self.standard_weapon.as_mut() {
+                    standard_weapon.size_multiplier *= multiplier;
+                }
+            }
+            AbilityUpgrade::DurationMultiplier(multiplier) => {
+                if let Some(charge) = self.charge.as_mut() {
+                    scale_timer_duration(&mut charge.action_timer, *multiplier);
+                }
+                if let Some(shield_bubble) = self.shield_bubble.as_mut() {
+                    scale_timer_duration(&mut shield_bubble.action_timer, *multiplier);
+                }
+                if let Some(dash) = self.dash.as_mut() {
+                    scale_timer_duration(&mut dash.action_timer, *multiplier);
+                }
+                if let Some(decoy) = self.decoy.as_mut() {
+                    decoy.lifetime *= multiplier;
+                }
+            }
+            AbilityUpgrade::AddChargeDamage(damage) => {
+                if let Some(charge) = self.charge.as_mut() {
+                    charge.collision_damage_bonus += damage;
+
+                    // the bonus is removed when the charge ends, so apply it to a charge in progress
+                    if !charge.action_timer.finished() {
+                        player_damage.collision_damage += damage;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Multiply the duration of a timer, keeping its elapsed time
+fn scale_timer_duration(timer: &mut Timer, multiplier: f32) {
+    let duration = timer.duration().mul_f32(multiplier);
+    timer.set_duration(duration);
+}
+
+/// Levels up abilities for corresponding `UpgradeAbilityEvent`s by applying the upgrades of the next
+/// level in their upgrade trees. Abilities that are already at their max level are unchanged.
+pub(in crate::player) fn upgrade_ability_system(
+    mut upgrade_ability_event_reader: EventReader<UpgradeAbilityEvent>,
+    mut player_query: Query<(
+        &PlayerIDComponent,
+        &mut PlayerOutgoingDamageComponent,
+        &Children,
+    )>,
+    mut ability_query: Query<AbilityUpgradeQuery>,
+) {
+    for event in upgrade_ability_event_reader.read() {
+        for (player_id, mut player_damage, children) in player_query.iter_mut() {
+            if event.player_id != *player_id {
+                continue;
+            }
+
+            for child in children.iter() {
+                if let Ok(mut ability) = ability_query.get_mut(*child) {
+                    if *ability.slot != event.ability_slot_id {
+                        continue;
+                    }
+
+                    let Some(next_level) = ability.level.next_level().cloned() else {
+                        info!("Ability is already at its max level");
+                        continue;
+                    };
+
+                    for upgrade in next_level.upgrades.iter() {
+                        ability.apply_upgrade(upgrade, &mut player_damage);
+                    }
+                    ability.level.level += 1;
+
+                    info!(
+                        "Upgraded ability to level {}: {}",
+                        ability.level.level, next_level.description
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::app::{App, Update};
+    use bevy::ecs::bundle::Bundle;
+    use bevy::ecs::entity::Entity;
+    use bevy::ecs::event::Events;
+    use bevy::hierarchy::BuildWorldChildren;
+    use bevy::MinimalPlugins;
+    use ron::de::from_bytes;
+    use thetawave_interface::abilities::{
+        AbilitiesResource, AbilityLevelComponent, AbilitySlotIDComponent, AbilityUpgrade,
+        AbilityUpgradeLevel, ChargeAbilityBundle, ChargeAbilityComponent,
+        StandardWeaponAbilityBundle, StandardWeaponAbilityComponent, UpgradeAbilityEvent,
+    };
+    use thetawave_interface::character::CharacterType;
+    use thetawave_interface::player::{PlayerIDComponent, PlayerOutgoingDamageComponent};
+
+    use super::upgrade_ability_system;
+    use crate::player::CharactersResource;
+
+    fn abilities_res() -> AbilitiesResource {
+        from_bytes::<AbilitiesResource>(include_bytes!("../../../assets/data/abilities.ron"))
+            .unwrap()
+    }
+
+    /// App with a captain that has the ability in slot two, along with the player and ability entities
+    fn upgrade_test_app(ability: impl Bundle) -> (App, Entity, Entity) {
+        let characters_res =
+            from_bytes::<CharactersResource>(include_bytes!("../../../assets/data/characters.ron"))
+                .unwrap();
+        let captain = &characters_res.characters[&CharacterType::from("captain")];
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_event::<UpgradeAbilityEvent>()
+            .add_systems(Update, upgrade_ability_system);
+
+        let mut ability_entity = Entity::PLACEHOLDER;
+        let player_entity = app
+            .world_mut()
+            .spawn((
+                PlayerIDComponent::One,
+                PlayerOutgoingDamageComponent::from(captain),
+            ))
+            .with_children(|parent| {
+                ability_entity = parent.spawn(ability).id();
+            })
+            .id();
+
+        (app, player_entity, ability_entity)
+    }
+
+    fn upgrade_slot_two(app: &mut App) {
+        app.world_mut()
+            .resource_mut::<Events<UpgradeAbilityEvent>>()
+            .send(UpgradeAbilityEvent {
+                player_id: PlayerIDComponent::One,
+                ability_slot_id: AbilitySlotIDComponent::Two,
+            });
+        app.update();
+    }
+
+    #[test]
+    fn test_ability_levels_follow_upgrade_tree() {
+        let upgrade_tree = vec![
+            AbilityUpgradeLevel {
+                description: "Shorter cooldown".to_string(),
+                upgrades: vec![AbilityUpgrade::CooldownMultiplier(0.5)],
+            },
+            AbilityUpgradeLevel {
+                description: "More damage".to_string(),
+                upgrades: vec![AbilityUpgrade::DamageMultiplier(2.0)],
+            },
+        ];
+        let mut level = AbilityLevelComponent::new(&upgrade_tree);
+
+        assert_eq!(level.max_level(), 3);
+        assert_eq!(level.next_level().unwrap().description, "Shorter cooldown");
+
+        level.level = 2;
+        assert_eq!(level.next_level().unwrap().description, "More damage");
+
+        level.level = 3;
+        assert!(level.next_level().is_none());
+        assert_eq!(AbilityLevelComponent::new(&[]).max_level(), 1);
+    }
+
+    #[test]
+    fn test_extra_projectiles_are_added_after_count_multiplier() {
+        let abilities_res = abilities_res();
+        let (mut app, _, ability_entity) = upgrade_test_app(StandardWeaponAbilityBundle::from(
+            &abilities_res.homing_missile_salvo_ability,
+        ));
+        let count_multiplier = app
+            .world()
+            .get::<StandardWeaponAbilityComponent>(ability_entity)
+            .unwrap()
+            .count_multiplier;
+
+        // the first level of the missile salvo fires 2 extra missiles
+        upgrade_slot_two(&mut app);
+
+        let weapon = app
+            .world()
+            .get::<StandardWeaponAbilityComponent>(ability_entity)
+            .unwrap();
+        assert_eq!(weapon.extra_count, 2);
+        assert_eq!(weapon.count_multiplier, count_multiplier);
+        assert_eq!(
+            app.world()
+                .get::<AbilityLevelComponent>(ability_entity)
+                .unwrap()
+                .level,
+            2
+        );
+    }
+
+    #[test]
+    fn test_upgrades_stop_at_max_level() {
+        let abilities_res = abilities_res();
+        let (mut app, _, ability_entity) = upgrade_test_app(StandardWeaponAbilityBundle::from(
+            &abilities_res.homing_missile_salvo_ability,
+        ));
+        let max_level = app
+            .world()
+            .get::<AbilityLevelComponent>(ability_entity)
+            .unwrap()
+            .max_level();
+
+        for _ in 0..max_level + 2 {
+            upgrade_slot_two(&mut app);
+        }
+
+        assert_eq!(
+            app.world()
+                .get::<AbilityLevelComponent>(ability_entity)
+                .unwrap()
+                .level,
+            max_level
+        );
+    }
+
+    #[test]
+    fn test_charge_damage_applies_to_charge_in_progress() {
+        let abilities_res = abilities_res();
+        let (mut app, player_entity, ability_entity) =
+            upgrade_test_app(ChargeAbilityBundle::from(&abilities_res.charge_ability));
+        let collision_damage = app
+            .world()
+            .get::<PlayerOutgoingDamageComponent>(player_entity)
+            .unwrap()
+            .collision_damage;
+
+        // the first level of the charge adds 20 collision damage, not applied while not charging
+        upgrade_slot_two(&mut app);
+        assert_eq!(
+            app.world()
+                .get::<ChargeAbilityComponent>(ability_entity)
+                .unwrap()
+                .collision_damage_bonus,
+            20
+        );
+        assert_eq!(
+            app.world()
+                .get::<PlayerOutgoingDamageComponent>(player_entity)
+                .unwrap()
+                .collision_damage,
+            collision_damage
+        );
+
+        // the third level adds 20 more, which also applies to the charge in progress
+        upgrade_slot_two(&mut app);
+        app.world_mut()
+            .get_mut::<ChargeAbilityComponent>(ability_entity)
+            .unwrap()
+            .action_timer
+            .reset();
+        upgrade_slot_two(&mut app);
+        assert_eq!(
+            app.world()
+                .get::<PlayerOutgoingDamageComponent>(player_entity)
+                .unwrap()
+                .collision_damage,
+            collision_damage + 20
+        );
+    }
+}
@@ -5,7 +5,10 @@ use bevy::{
 };
 use serde::Deserialize;
 use std::collections::HashMap;
-use thetawave_interface::spawnable::{SpawnMobEvent, SpawnableType};
+use thetawave_interface::{
+    path::PathData,
+    spawnable::{SpawnMobEvent, SpawnableType},
+};
 
 use crate::{spawnable::SpawnConsumableEvent, tools::weighted_rng};
 
@@ -54,6 +57,28 @@ pub struct Formation {
     pub weight: f32,
     /// Time until next spawn
     pub period: f32,
+    /// Path shared by the mobs in the formation
+    #[serde(default)]
+    pub path: Option<FormationPath>,
+}
+
+/// A path that all mobs in a formation follow, offset by their positions in the formation
+#[derive(Deserialize, Clone)]
+pub struct FormationPath {
+    pub path: PathData,
+    /// Distance along the path between each mob, in the order they are listed in the formation
+    #[serde(default)]
+    pub stagger: f32,
+}
+
+impl FormationPath {
+    /// Path for the mob at the given index and position in the formation
+    fn get_mob_path(&self, index: usize, position: Vec2) -> PathData {
+        let mut path = self.path.clone();
+        path.offset += position;
+        path.distance -= self.stagger * index as f32;
+        path
+    }
 }
 
 impl Formation {
@@ -64,7 +89,7 @@ impl Formation {
         spawn_mob: &mut EventWriter<SpawnMobEvent>,
     ) {
         // iterate through all spawnables in the formation and spawn at given position
-        for formation_spawnable in self.formation_spawnables.iter() {
+        for (i, formation_spawnable) in self.formation_spawnables.iter().enumerate() {
             // TODO: add cases for items, consumables, etc, as they are added
             // call the appropriate spawn function for the spawnable
             match &formation_spawnable.spawnable_type {
@@ -74,6 +99,10 @@ impl Formation {
                         position: formation_spawnable.position,
                         rotation: Quat::default(),
                         boss: false,
+                        path: self.path.as_ref().map(|formation_path| {
+                            formation_path.get_mob_path(i, formation_spawnable.position)
+                        }),
+                        parent: None,
                     });
                 }
 
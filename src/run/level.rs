@@ -21,11 +21,9 @@ use thetawave_interface::{
     objective::{MobReachedBottomGateEvent, Objective},
     player::PlayerComponent,
     run::CyclePhaseEvent,
-    spawnable::{MobDestroyedEvent, MobSegmentDestroyedEvent, SpawnMobEvent},
+    spawnable::{BossesDestroyedEvent, MobDestroyedEvent, MobSegmentDestroyedEvent, SpawnMobEvent},
 };
 
-use crate::spawnable::BossesDestroyedEvent;
-
 use super::{FormationPoolsResource, SpawnFormationEvent};
 
 #[derive(Resource, Deserialize)]
@@ -214,6 +212,8 @@ impl Level {
                                 position: *position,
                                 rotation: Quat::default(),
                                 boss: true,
+                                path: None,
+                                parent: None,
                             });
                         }
                         false
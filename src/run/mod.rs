@@ -19,17 +19,18 @@ use thetawave_interface::{
     objective::{DefenseInteraction, MobReachedBottomGateEvent, Objective},
     player::PlayerComponent,
     run::{CyclePhaseEvent, RunDefeatType, RunEndEvent, RunOutcomeType},
-    spawnable::{MobDestroyedEvent, MobSegmentDestroyedEvent, SpawnMobEvent},
+    spawnable::{BossesDestroyedEvent, MobDestroyedEvent, MobSegmentDestroyedEvent, SpawnMobEvent},
     states::{AppStates, GameStates},
 };
 
-use crate::{spawnable::BossesDestroyedEvent, GameUpdateSet};
+use crate::GameUpdateSet;
 
 mod formation;
 mod level;
 pub(crate) mod level_phase;
 pub(crate) mod tutorial;
 
+pub(crate) use self::level::BGMusicTransition;
 use self::{
     formation::{spawn_formation_system, FormationPoolsResource, SpawnFormationEvent},
     level::{Level, PremadeLevelsResource},
@@ -90,7 +91,17 @@ impl Plugin for RunPlugin {
 
 #[derive(Resource, Deserialize)]
 pub(super) struct PremadeRunsResource {
-    pub runs: HashMap<String, Vec<String>>,
+    pub runs: HashMap<String, PremadeRunData>,
+}
+
+/// Levels and settings of a premade run
+#[derive(Deserialize)]
+pub(super) struct PremadeRunData {
+    /// Keys of the levels in the run, in the order they are played
+    pub levels: Vec<String>,
+    /// Lives each player starts the run with, players are not respawned if `None`
+    #[serde(default)]
+    pub lives: Option<usize>,
 }
 
 /// The most up to date information on how close the player is to winning. This also keeps the
@@ -102,6 +113,8 @@ pub struct CurrentRunProgressResource {
     pub completed_levels: VecDeque<Level>,
     /// Tracks the level currently being played
     pub current_level: Option<Level>,
+    /// Lives each player starts the run with, `None` if the run doesn't use lives
+    pub lives: Option<usize>,
 }
 
 impl Default for CurrentRunProgressResource {
@@ -110,6 +123,7 @@ impl Default for CurrentRunProgressResource {
             queued_levels: VecDeque::new(),
             completed_levels: VecDeque::new(),
             current_level: None,
+            lives: None,
         }
     }
 }
@@ -122,17 +136,19 @@ impl CurrentRunProgressResource {
         premade_runs_res: &PremadeRunsResource,
         premade_levels_res: &PremadeLevelsResource,
     ) {
-        // get the level keys from the premade runs resource
-        let level_keys = premade_runs_res.runs.get(&run_key).unwrap();
+        // get the level keys and settings from the premade runs resource
+        let run_data = premade_runs_res.runs.get(&run_key).unwrap();
 
         // get levels from the levels resource
-        let levels: VecDeque<Level> = level_keys
+        let levels: VecDeque<Level> = run_data
+            .levels
             .iter()
             .map(|key| Level::from(premade_levels_res.levels_data.get(key).unwrap()))
             .collect();
 
         // set levels in the run resource
         self.queued_levels = levels;
+        self.lives = run_data.lives;
 
         info!("Generated premade level");
     }
@@ -354,7 +370,7 @@ fn run_reset_system(
 #[cfg(test)]
 mod test {
     use crate::run::{RunPlugin, SpawnFormationEvent};
-    use crate::spawnable::{BossesDestroyedEvent, SpawnConsumableEvent};
+    use crate::spawnable::SpawnConsumableEvent;
     use bevy::app::App;
     use bevy::log::{Level, LogPlugin};
     use bevy::prelude::{default, NextState, State};
@@ -365,7 +381,7 @@ mod test {
     use thetawave_interface::objective::{DefenseInteraction, MobReachedBottomGateEvent};
     use thetawave_interface::player::{InputRestrictionsAtSpawn, PlayersResource};
     use thetawave_interface::spawnable::{
-        MobDestroyedEvent, MobSegmentDestroyedEvent, SpawnMobEvent,
+        BossesDestroyedEvent, MobDestroyedEvent, MobSegmentDestroyedEvent, SpawnMobEvent,
     };
     use thetawave_interface::states::{AppStates, GameStates};
 
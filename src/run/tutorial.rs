@@ -335,6 +335,8 @@ impl TutorialLesson {
                     position: (thread_rng().gen_range(spawn_range_x.clone()), *spawn_y).into(),
                     rotation: Quat::default(),
                     boss: false,
+                    path: None,
+                    parent: None,
                 });
             }
 
@@ -355,6 +357,8 @@ impl TutorialLesson {
                                 .into(),
                             rotation: Quat::default(),
                             boss: false,
+                            path: None,
+                            parent: None,
                         });
                     } else if *mobs_to_protect > 0 {
                         play_sound_effect_event_writer.send(PlaySoundEffectEvent {
@@ -367,6 +371,8 @@ impl TutorialLesson {
                                 .into(),
                             rotation: Quat::default(),
                             boss: false,
+                            path: None,
+                            parent: None,
                         });
                     }
                 }
@@ -383,6 +389,8 @@ impl TutorialLesson {
                         position: (thread_rng().gen_range(spawn_range_x.clone()), *spawn_y).into(),
                         rotation: Quat::default(),
                         boss: false,
+                        path: None,
+                        parent: None,
                     });
                 }
             }
@@ -404,6 +412,8 @@ impl TutorialLesson {
                                     .into(),
                                 rotation: Quat::default(),
                                 boss: false,
+                                path: None,
+                                parent: None,
                             });
                         } else {
                             play_sound_effect_event_writer.send(PlaySoundEffectEvent {
@@ -419,6 +429,8 @@ impl TutorialLesson {
                                 .into(),
                             rotation: Quat::default(),
                             boss: false,
+                            path: None,
+                            parent: None,
                         });
                     }
                 }
@@ -454,6 +466,8 @@ impl TutorialLesson {
                     position: (thread_rng().gen_range(spawn_range_x.clone()), *spawn_y).into(),
                     rotation: Quat::default(),
                     boss: false,
+                    path: None,
+                    parent: None,
                 });
             }
 
@@ -474,6 +488,8 @@ impl TutorialLesson {
                                 .into(),
                             rotation: Quat::default(),
                             boss: false,
+                            path: None,
+                            parent: None,
                         });
                     } else {
                         play_sound_effect_event_writer.send(PlaySoundEffectEvent {
@@ -493,6 +509,8 @@ impl TutorialLesson {
                                 .into(),
                             rotation: Quat::default(),
                             boss: false,
+                            path: None,
+                            parent: None,
                         });
                     }
                 }
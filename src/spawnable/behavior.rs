@@ -1,16 +1,22 @@
 use crate::{
-    collision::SortedCollisionEvent, game::GameParametersResource, spawnable::SpawnableComponent,
+    collision::SortedCollisionEvent,
+    game::GameParametersResource,
+    spawnable::{
+        targeting::{hostile_targets, select_target, TargetingComponent, TargetsQuery},
+        MobParentComponent, SpawnableComponent,
+    },
     tools::signed_modulo,
 };
-use bevy::prelude::{Entity, EventReader, Query, Res, Transform, Vec2, Vec3Swizzles, With};
+use bevy::prelude::{
+    Entity, EventReader, Quat, Query, Res, Time, Transform, Vec2, Vec3Swizzles, With,
+};
 use bevy_rapier2d::prelude::Velocity;
 use serde::Deserialize;
+use thetawave_interface::path::FollowedPath;
 use thetawave_interface::player::PlayerAttractionComponent;
 use thetawave_interface::spawnable::AttractToClosestPlayerComponent;
-use thetawave_interface::{
-    player::PlayerComponent,
-    spawnable::{EnemyMobType, MobType, SpawnableType},
-};
+use thetawave_interface::spawnable::Faction;
+use thetawave_interface::targeting::TargetingPolicy;
 
 /// Types of behaviors that can be performed by spawnables
 #[derive(Deserialize, Clone, PartialEq)]
@@ -24,13 +30,118 @@ pub enum SpawnableBehavior {
     ChangeHorizontalDirectionOnImpact,
     MoveToPosition(Vec2),
     AttractToPlayer,
+    /// Follow a path, replacing other movement behaviors
+    /// Added to mobs that are spawned on a path, such as mobs in formations
+    #[serde(skip)]
+    FollowPath(FollowedPath),
+    /// Move down while moving side to side in a sine wave
+    SineWave(SineWaveData),
+    /// Circle around a point or the parent of the spawnable
+    OrbitAround(OrbitData),
+    /// Move down while switching horizontal direction on a timer
+    ZigZag(ZigZagData),
+    /// Hold a vertical position while strafing side to side
+    HoverAt(HoverData),
 }
 
+/// Describes a sine wave motion
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct SineWaveData {
+    /// Maximum horizontal distance from the center of the wave
+    pub amplitude: f32,
+    /// Number of full waves per second
+    pub frequency: f32,
+    /// Current phase of the wave in radians
+    #[serde(default)]
+    pub phase: f32,
+}
+
+/// Point that an orbiting spawnable circles around
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub enum OrbitCenter {
+    Point(Vec2),
+    /// The mob or mob segment that spawned the spawnable, the spawnable moves down if the parent is gone
+    Parent,
+}
+
+/// Describes an orbit around a point
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct OrbitData {
+    pub center: OrbitCenter,
+    pub radius: f32,
+    /// Speed of the orbit in radians per second, negative values orbit clockwise
+    pub angular_speed: f32,
+    /// Current angle around the center, starts at the angle of the spawnable's position
+    #[serde(skip)]
+    pub angle: Option<f32>,
+}
+
+/// Describes a zig-zag motion
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct ZigZagData {
+    /// Time in seconds between switching directions
+    pub period: f32,
+    /// Whether the spawnable is moving right, spawnables start by moving left by default
+    #[serde(default)]
+    pub moving_right: bool,
+    /// Time in seconds since the last switch
+    #[serde(default)]
+    pub timer: f32,
+}
+
+/// Describes hovering at a vertical position
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct HoverData {
+    /// Vertical position to hold
+    pub y: f32,
+    /// Horizontal distance from the center of the arena to strafe to before turning around
+    pub strafe_range: f32,
+    /// Whether the spawnable is strafing to the right
+    #[serde(default)]
+    pub strafe_right: bool,
+}
+
+impl SpawnableBehavior {
+    /// Whether the behavior sets the velocity of the spawnable
+    pub fn is_movement(&self) -> bool {
+        matches!(
+            self,
+            SpawnableBehavior::MoveForward
+                | SpawnableBehavior::MoveDown
+                | SpawnableBehavior::MoveRight
+                | SpawnableBehavior::MoveLeft
+                | SpawnableBehavior::BrakeHorizontal
+                | SpawnableBehavior::MoveToPosition(_)
+                | SpawnableBehavior::FollowPath(_)
+                | SpawnableBehavior::SineWave(_)
+                | SpawnableBehavior::OrbitAround(_)
+                | SpawnableBehavior::ZigZag(_)
+                | SpawnableBehavior::HoverAt(_)
+        )
+    }
+}
+
+/// Multiple of the distance from an orbit's radius added to the orbiting velocity to correct it, per second
+const ORBIT_CORRECTION: f32 = 2.0;
+/// Multiple of the distance from a hover position used as vertical velocity, per second
+const HOVER_CORRECTION: f32 = 2.0;
+
+/// Multiple of a path's speed that spawnables can move at to catch up after being knocked off of it
+const PATH_CATCH_UP_MULTIPLIER: f32 = 2.0;
+
 /// Manages excuting behaviors of spawnables
 pub fn spawnable_execute_behavior_system(
     game_parameters: Res<GameParametersResource>,
-    mut spawnable_query: Query<(Entity, &mut SpawnableComponent, &mut Velocity, &Transform)>,
+    mut spawnable_query: Query<(
+        Entity,
+        &mut SpawnableComponent,
+        &mut Velocity,
+        &Transform,
+        Option<&MobParentComponent>,
+    )>,
+    parent_query: Query<&Transform>,
     mut collision_events: EventReader<SortedCollisionEvent>,
+    time: Res<Time>,
 ) {
     let mut collision_events_vec = vec![];
     for collision_event in collision_events.read() {
@@ -38,11 +149,11 @@ pub fn spawnable_execute_behavior_system(
     }
 
     // Iterate through all spawnable entities and execute their behavior
-    for (spawnable_entity, mut spawnable_component, mut rb_vel, spawnable_transform) in
+    for (spawnable_entity, mut spawnable_component, mut rb_vel, spawnable_transform, parent) in
         spawnable_query.iter_mut()
     {
         let behaviors = spawnable_component.behaviors.clone();
-        for behavior in behaviors {
+        for (i, behavior) in behaviors.into_iter().enumerate() {
             match behavior {
                 SpawnableBehavior::MoveDown => {
                     move_down(&spawnable_component, &mut rb_vel);
@@ -77,62 +188,138 @@ pub fn spawnable_execute_behavior_system(
                         &mut spawnable_component,
                     );
                 }
+                SpawnableBehavior::SineWave(mut sine_wave) => {
+                    sine_wave_move(
+                        &mut sine_wave,
+                        &game_parameters,
+                        &spawnable_component,
+                        &mut rb_vel,
+                        time.delta_seconds(),
+                    );
+                    spawnable_component.behaviors[i] = SpawnableBehavior::SineWave(sine_wave);
+                }
+                SpawnableBehavior::OrbitAround(mut orbit) => {
+                    let center = match orbit.center {
+                        OrbitCenter::Point(point) => Some(point),
+                        OrbitCenter::Parent => parent
+                            .and_then(|parent| parent_query.get(parent.0).ok())
+                            .map(|parent_transform| parent_transform.translation.xy()),
+                    };
+
+                    if let Some(center) = center {
+                        orbit_around(
+                            &mut orbit,
+                            center,
+                            spawnable_transform,
+                            &game_parameters,
+                            &spawnable_component,
+                            &mut rb_vel,
+                            time.delta_seconds(),
+                        );
+                        spawnable_component.behaviors[i] = SpawnableBehavior::OrbitAround(orbit);
+                    } else {
+                        move_down(&spawnable_component, &mut rb_vel);
+                    }
+                }
+                SpawnableBehavior::ZigZag(mut zig_zag) => {
+                    zig_zag_move(
+                        &mut zig_zag,
+                        &game_parameters,
+                        &spawnable_component,
+                        &mut rb_vel,
+                        time.delta_seconds(),
+                    );
+                    spawnable_component.behaviors[i] = SpawnableBehavior::ZigZag(zig_zag);
+                }
+                SpawnableBehavior::HoverAt(mut hover) => {
+                    hover_at(
+                        &mut hover,
+                        spawnable_transform,
+                        &game_parameters,
+                        &spawnable_component,
+                        &mut rb_vel,
+                    );
+                    spawnable_component.behaviors[i] = SpawnableBehavior::HoverAt(hover);
+                }
                 _ => {}
             }
         }
     }
 }
 
-/// Manages setting targeting of spawnables
-pub fn spawnable_set_target_behavior_system(
-    player_query: Query<&Transform, With<PlayerComponent>>,
-    mut spawnable_query: Query<(&mut SpawnableComponent, &Transform)>,
+/// Moves spawnables along their paths, and rotates them to face the direction of the path
+pub(super) fn spawnable_follow_path_system(
+    mut spawnable_query: Query<(&mut SpawnableComponent, &mut Velocity, &mut Transform)>,
+    time: Res<Time>,
 ) {
-    // Sets targetting to None
-    for (mut spawnable_component, _) in spawnable_query.iter_mut() {
+    let delta_seconds = time.delta_seconds();
+    if delta_seconds <= 0.0 {
+        return;
+    }
+
+    for (mut spawnable_component, mut rb_vel, mut transform) in spawnable_query.iter_mut() {
         for behavior in spawnable_component.behaviors.iter_mut() {
-            if let SpawnableBehavior::RotateToTarget(_) = behavior {
-                *behavior = SpawnableBehavior::RotateToTarget(None);
+            let SpawnableBehavior::FollowPath(path) = behavior else {
+                continue;
+            };
+
+            path.advance(path.data.speed * delta_seconds);
+            let (target, direction) = path.sample();
+
+            // set the velocity needed to reach the next point on the path
+            rb_vel.linvel = ((target - transform.translation.xy()) / delta_seconds)
+                .clamp_length_max(path.data.speed * PATH_CATCH_UP_MULTIPLIER);
+
+            // sprites face down, so a direction of -y is no rotation
+            if path.data.face_direction && direction != Vec2::ZERO {
+                transform.rotation = Quat::from_rotation_z(
+                    direction.y.atan2(direction.x) + std::f32::consts::FRAC_PI_2,
+                );
+                rb_vel.angvel = 0.0;
             }
         }
     }
+}
 
-    // Recalculates what the target should be
-    for player_transform in player_query.iter() {
-        for (mut spawnable_component, spawnable_transform) in spawnable_query.iter_mut() {
-            match &spawnable_component.spawnable_type {
-                SpawnableType::Mob(mob_type) => match mob_type {
-                    MobType::Enemy(enemy_type) => match enemy_type {
-                        EnemyMobType::Missile => {
-                            // set target to closest player
-                            for behavior in spawnable_component.behaviors.iter_mut() {
-                                *behavior = match behavior {
-                                    SpawnableBehavior::RotateToTarget(target) => {
-                                        let spawnable_position_vec2: Vec2 =
-                                            spawnable_transform.translation.xy();
-                                        let player_position_vec2: Vec2 =
-                                            player_transform.translation.xy();
-                                        if target.is_none()
-                                            || spawnable_position_vec2
-                                                .distance(player_position_vec2)
-                                                < spawnable_position_vec2.distance(target.unwrap())
-                                        {
-                                            SpawnableBehavior::RotateToTarget(Some(
-                                                player_position_vec2,
-                                            ))
-                                        } else {
-                                            behavior.clone()
-                                        }
-                                    }
-                                    _ => behavior.clone(),
-                                }
-                            }
-                        }
-                        _ => {}
-                    },
-                    _ => {}
-                },
-                _ => {}
+/// Manages setting targeting of spawnables
+/// Spawnables with a `TargetingComponent` rotate to its target, others rotate to the nearest player
+pub fn spawnable_set_target_behavior_system(
+    targets_query: TargetsQuery,
+    mut spawnable_query: Query<(
+        &mut SpawnableComponent,
+        &Transform,
+        Option<&TargetingComponent>,
+    )>,
+) {
+    let targets = targets_query.targets();
+
+    for (mut spawnable_component, spawnable_transform, targeting) in spawnable_query.iter_mut() {
+        if !spawnable_component
+            .behaviors
+            .iter()
+            .any(|behavior| matches!(behavior, SpawnableBehavior::RotateToTarget(_)))
+        {
+            continue;
+        }
+
+        let target_position = match targeting {
+            Some(targeting) => targeting.target.and_then(|entity| {
+                targets
+                    .iter()
+                    .find(|target| target.entity == entity)
+                    .map(|target| target.position)
+            }),
+            None => select_target(
+                TargetingPolicy::Nearest,
+                spawnable_transform.translation.xy(),
+                hostile_targets(&targets, Faction::Enemy),
+            )
+            .map(|target| target.position),
+        };
+
+        for behavior in spawnable_component.behaviors.iter_mut() {
+            if let SpawnableBehavior::RotateToTarget(target) = behavior {
+                *target = target_position;
             }
         }
     }
@@ -289,6 +476,151 @@ fn move_left(spawnable_component: &SpawnableComponent, rb_vel: &mut Velocity) {
     }
 }
 
+/// Changes a velocity toward a target velocity, using acceleration to speed up and deceleration to slow down
+fn accelerate_toward(velocity: &mut f32, target: f32, acceleration: f32, deceleration: f32) {
+    let step = if target.abs() > velocity.abs() && target * *velocity >= 0.0 {
+        acceleration
+    } else {
+        deceleration
+    };
+
+    if *velocity < target {
+        *velocity = (*velocity + step).min(target);
+    } else {
+        *velocity = (*velocity - step).max(target);
+    }
+}
+
+/// Accelerates toward a target velocity, limited by the speed of the spawnable and the maximum speed of the game
+fn accelerate_toward_velocity(
+    target: Vec2,
+    game_parameters: &GameParametersResource,
+    spawnable_component: &SpawnableComponent,
+    rb_vel: &mut Velocity,
+) {
+    let max_speed = spawnable_component
+        .speed
+        .min(Vec2::splat(game_parameters.max_speed));
+    let target = target.clamp(-max_speed, max_speed);
+
+    accelerate_toward(
+        &mut rb_vel.linvel.x,
+        target.x,
+        spawnable_component.acceleration.x,
+        spawnable_component.deceleration.x,
+    );
+    accelerate_toward(
+        &mut rb_vel.linvel.y,
+        target.y,
+        spawnable_component.acceleration.y,
+        spawnable_component.deceleration.y,
+    );
+}
+
+/// Moves entity down while moving side to side in a sine wave
+fn sine_wave_move(
+    sine_wave: &mut SineWaveData,
+    game_parameters: &GameParametersResource,
+    spawnable_component: &SpawnableComponent,
+    rb_vel: &mut Velocity,
+    delta_seconds: f32,
+) {
+    let angular_frequency = std::f32::consts::TAU * sine_wave.frequency;
+    sine_wave.phase = (sine_wave.phase + angular_frequency * delta_seconds) % std::f32::consts::TAU;
+
+    // horizontal velocity is the derivative of the wave's position
+    let target_x = sine_wave.amplitude * angular_frequency * sine_wave.phase.cos();
+    accelerate_toward_velocity(
+        Vec2::new(target_x, -spawnable_component.speed.y),
+        game_parameters,
+        spawnable_component,
+        rb_vel,
+    );
+}
+
+/// Moves entity in a circle around a center point
+fn orbit_around(
+    orbit: &mut OrbitData,
+    center: Vec2,
+    transform: &Transform,
+    game_parameters: &GameParametersResource,
+    spawnable_component: &SpawnableComponent,
+    rb_vel: &mut Velocity,
+    delta_seconds: f32,
+) {
+    let position = transform.translation.xy();
+    let angle = orbit.angle.unwrap_or_else(|| {
+        let offset = position - center;
+        offset.y.atan2(offset.x)
+    }) + orbit.angular_speed * delta_seconds;
+    orbit.angle = Some(angle % std::f32::consts::TAU);
+
+    // move along the circle, correcting toward the point on the circle at the current angle
+    let target_position = center + Vec2::from_angle(angle) * orbit.radius;
+    let tangent = Vec2::from_angle(angle).perp() * orbit.angular_speed * orbit.radius;
+    accelerate_toward_velocity(
+        tangent + (target_position - position) * ORBIT_CORRECTION,
+        game_parameters,
+        spawnable_component,
+        rb_vel,
+    );
+}
+
+/// Moves entity down while switching between moving left and right
+fn zig_zag_move(
+    zig_zag: &mut ZigZagData,
+    game_parameters: &GameParametersResource,
+    spawnable_component: &SpawnableComponent,
+    rb_vel: &mut Velocity,
+    delta_seconds: f32,
+) {
+    zig_zag.timer += delta_seconds;
+    if zig_zag.timer >= zig_zag.period {
+        zig_zag.timer -= zig_zag.period;
+        zig_zag.moving_right = !zig_zag.moving_right;
+    }
+
+    let target_x = if zig_zag.moving_right {
+        spawnable_component.speed.x
+    } else {
+        -spawnable_component.speed.x
+    };
+    accelerate_toward_velocity(
+        Vec2::new(target_x, -spawnable_component.speed.y),
+        game_parameters,
+        spawnable_component,
+        rb_vel,
+    );
+}
+
+/// Moves entity to a vertical position and strafes side to side
+fn hover_at(
+    hover: &mut HoverData,
+    transform: &Transform,
+    game_parameters: &GameParametersResource,
+    spawnable_component: &SpawnableComponent,
+    rb_vel: &mut Velocity,
+) {
+    let position = transform.translation.xy();
+    if position.x >= hover.strafe_range {
+        hover.strafe_right = false;
+    } else if position.x <= -hover.strafe_range {
+        hover.strafe_right = true;
+    }
+
+    let target_x = if hover.strafe_right {
+        spawnable_component.speed.x
+    } else {
+        -spawnable_component.speed.x
+    };
+    accelerate_toward_velocity(
+        Vec2::new(target_x, (hover.y - position.y) * HOVER_CORRECTION),
+        game_parameters,
+        spawnable_component,
+        rb_vel,
+    );
+}
+
 /// Decelerates to 0 horizontal movement
 fn brake_horizontal(
     game_parameters: &GameParametersResource,
@@ -355,3 +687,220 @@ pub(super) fn attract_to_player_system(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use bevy::math::{Vec2, Vec3};
+    use bevy::prelude::Transform;
+    use bevy_rapier2d::prelude::Velocity;
+    use std::f32::consts::{FRAC_PI_2, PI, TAU};
+    use thetawave_interface::spawnable::SpawnableType;
+
+    use super::{
+        accelerate_toward, hover_at, orbit_around, sine_wave_move, zig_zag_move, HoverData,
+        OrbitCenter, OrbitData, SineWaveData, ZigZagData, HOVER_CORRECTION,
+    };
+    use crate::game::GameParametersResource;
+    use crate::spawnable::SpawnableComponent;
+
+    fn game_parameters() -> GameParametersResource {
+        ron::de::from_bytes::<GameParametersResource>(include_bytes!(
+            "../../assets/data/game_parameters.ron"
+        ))
+        .unwrap()
+    }
+
+    /// Spawnable that reaches any target velocity within its speed in one step
+    fn spawnable() -> SpawnableComponent {
+        SpawnableComponent {
+            acceleration: Vec2::splat(1000.0),
+            deceleration: Vec2::splat(1000.0),
+            speed: Vec2::new(100.0, 50.0),
+            ..SpawnableComponent::new(SpawnableType::default())
+        }
+    }
+
+    #[test]
+    fn test_accelerate_toward() {
+        let mut velocity = 0.0;
+        for expected in [3.0, 6.0, 9.0, 10.0, 10.0] {
+            accelerate_toward(&mut velocity, 10.0, 3.0, 4.0);
+            assert_eq!(velocity, expected);
+        }
+
+        // slowing down and reversing use deceleration
+        accelerate_toward(&mut velocity, 0.0, 3.0, 4.0);
+        assert_eq!(velocity, 6.0);
+        accelerate_toward(&mut velocity, -10.0, 3.0, 4.0);
+        assert_eq!(velocity, 2.0);
+    }
+
+    #[test]
+    fn test_motion_is_limited_by_max_speed() {
+        let mut game_parameters = game_parameters();
+        game_parameters.max_speed = 40.0;
+        let mut rb_vel = Velocity::default();
+        let mut zig_zag = ZigZagData {
+            period: 1.0,
+            moving_right: true,
+            timer: 0.0,
+        };
+
+        zig_zag_move(
+            &mut zig_zag,
+            &game_parameters,
+            &spawnable(),
+            &mut rb_vel,
+            0.1,
+        );
+        assert_eq!(rb_vel.linvel, Vec2::new(40.0, -40.0));
+    }
+
+    #[test]
+    fn test_sine_wave_move() {
+        let game_parameters = game_parameters();
+        let mut rb_vel = Velocity::default();
+        let mut sine_wave = SineWaveData {
+            amplitude: 5.0,
+            frequency: 1.0,
+            phase: 0.0,
+        };
+
+        // horizontal velocity follows the derivative of the wave while moving down
+        sine_wave_move(
+            &mut sine_wave,
+            &game_parameters,
+            &spawnable(),
+            &mut rb_vel,
+            0.25,
+        );
+        assert!((sine_wave.phase - FRAC_PI_2).abs() < 1e-5);
+        assert!(rb_vel.linvel.x.abs() < 1e-3);
+        assert_eq!(rb_vel.linvel.y, -50.0);
+
+        sine_wave_move(
+            &mut sine_wave,
+            &game_parameters,
+            &spawnable(),
+            &mut rb_vel,
+            0.25,
+        );
+        assert!((rb_vel.linvel.x + 5.0 * TAU).abs() < 1e-3);
+
+        // the phase wraps after a full wave
+        sine_wave_move(
+            &mut sine_wave,
+            &game_parameters,
+            &spawnable(),
+            &mut rb_vel,
+            0.75,
+        );
+        assert!(sine_wave.phase < TAU);
+        assert!((sine_wave.phase - FRAC_PI_2).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_orbit_around() {
+        let game_parameters = game_parameters();
+        let transform = Transform::from_translation(Vec3::new(10.0, 0.0, 0.0));
+        let mut rb_vel = Velocity::default();
+        let mut orbit = OrbitData {
+            center: OrbitCenter::Point(Vec2::ZERO),
+            radius: 10.0,
+            angular_speed: 1.0,
+            angle: None,
+        };
+
+        // the orbit starts at the angle of the spawnable, and moves along the circle
+        orbit_around(
+            &mut orbit,
+            Vec2::ZERO,
+            &transform,
+            &game_parameters,
+            &spawnable(),
+            &mut rb_vel,
+            0.0,
+        );
+        assert_eq!(orbit.angle, Some(0.0));
+        assert!(rb_vel.linvel.distance(Vec2::new(0.0, 10.0)) < 1e-4);
+
+        // spawnables that are off of the circle are pulled back onto it
+        orbit.angle = Some(PI);
+        orbit.angular_speed = -1.0;
+        orbit_around(
+            &mut orbit,
+            Vec2::ZERO,
+            &transform,
+            &game_parameters,
+            &spawnable(),
+            &mut rb_vel,
+            0.0,
+        );
+        assert!(rb_vel.linvel.distance(Vec2::new(-40.0, 10.0)) < 1e-4);
+    }
+
+    #[test]
+    fn test_zig_zag_switches_direction() {
+        let game_parameters = game_parameters();
+        let mut rb_vel = Velocity::default();
+        let mut zig_zag = ZigZagData {
+            period: 1.0,
+            moving_right: false,
+            timer: 0.0,
+        };
+
+        zig_zag_move(
+            &mut zig_zag,
+            &game_parameters,
+            &spawnable(),
+            &mut rb_vel,
+            0.5,
+        );
+        assert_eq!(rb_vel.linvel, Vec2::new(-100.0, -50.0));
+
+        zig_zag_move(
+            &mut zig_zag,
+            &game_parameters,
+            &spawnable(),
+            &mut rb_vel,
+            0.6,
+        );
+        assert!(zig_zag.moving_right);
+        assert!((zig_zag.timer - 0.1).abs() < 1e-5);
+        assert_eq!(rb_vel.linvel, Vec2::new(100.0, -50.0));
+    }
+
+    #[test]
+    fn test_hover_at() {
+        let game_parameters = game_parameters();
+        let mut rb_vel = Velocity::default();
+        let mut hover = HoverData {
+            y: 200.0,
+            strafe_range: 100.0,
+            strafe_right: true,
+        };
+
+        // strafing turns around at the edge of the range, while moving toward the hover position
+        let transform = Transform::from_translation(Vec3::new(120.0, 190.0, 0.0));
+        hover_at(
+            &mut hover,
+            &transform,
+            &game_parameters,
+            &spawnable(),
+            &mut rb_vel,
+        );
+        assert!(!hover.strafe_right);
+        assert_eq!(rb_vel.linvel, Vec2::new(-100.0, 10.0 * HOVER_CORRECTION));
+
+        let transform = Transform::from_translation(Vec3::new(0.0, 200.0, 0.0));
+        hover_at(
+            &mut hover,
+            &transform,
+            &game_parameters,
+            &spawnable(),
+            &mut rb_vel,
+        );
+        assert!(!hover.strafe_right);
+        assert_eq!(rb_vel.linvel, Vec2::new(-100.0, 0.0));
+    }
+}
@@ -1,110 +1,292 @@
 use super::{
     mob::{
-        BehaviorSequenceTracker, MobBehavior, MobComponent, MobSegmentComponent,
-        MobSegmentControlBehavior,
+        find_controlling_mob, BehaviorSequenceTracker, MobBehavior, MobComponent,
+        MobSegmentComponent, MobSegmentControlBehavior, MobSpawner,
     },
     SpawnableBehavior, SpawnableComponent,
 };
 use bevy::{
-    prelude::{Entity, Event, EventReader, EventWriter, Query, Res, Resource},
-    time::{Time, Timer, TimerMode},
+    math::{Vec2, Vec3Swizzles},
+    prelude::{Entity, Event, EventReader, EventWriter, Query, Res, Resource, With, Without},
+    time::Time,
+    transform::components::Transform,
 };
 use bevy_rapier2d::prelude::ImpulseJoint;
 use serde::Deserialize;
-use std::{collections::HashMap, time::Duration};
+use std::collections::HashMap;
+use thetawave_interface::{
+    health::HealthComponent, player::PlayerComponent, spawnable::SpawnPosition,
+};
+use thiserror::Error;
 
+/// A state machine of mob behaviors
 #[derive(Deserialize, Clone)]
 pub struct MobBehaviorSequence {
-    pub behaviors: Vec<MobBehaviorSequenceElement>,
+    /// State that the mob starts in
+    pub initial_state: String,
+    /// States mapped to their names
+    pub states: HashMap<String, MobBehaviorState>,
 }
 
+/// Behaviors that a mob performs while in a state, and the transitions out of the state
 #[derive(Deserialize, Clone)]
-pub struct MobBehaviorSequenceElement {
+pub struct MobBehaviorState {
+    #[serde(default)]
     pub spawnable_behaviors: Vec<SpawnableBehavior>,
+    #[serde(default)]
     pub mob_behaviors: Vec<MobBehavior>,
+    #[serde(default)]
     pub control_behaviors: Vec<MobSegmentControlBehavior>,
-    pub time: f32,
+    /// Transitions are checked in order, the first transition whose condition is met is taken
+    #[serde(default)]
+    pub transitions: Vec<MobBehaviorTransition>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct MobBehaviorTransition {
+    /// Condition that must be met to take the transition
+    pub condition: MobBehaviorCondition,
+    /// Name of the state to switch to
+    pub to: String,
+}
+
+/// Conditions for switching between mob behavior states
+#[derive(Deserialize, Clone)]
+pub enum MobBehaviorCondition {
+    /// The mob has been in the state for the given time in seconds
+    After(f32),
+    /// The closest player is within the given distance
+    PlayerWithin(f32),
+    /// No player is within the given distance
+    PlayerBeyond(f32),
+    /// Health is below the given fraction of maximum health
+    HealthBelow(f32),
+    /// Health is at or above the given fraction of maximum health
+    HealthAbove(f32),
+    /// The number of mob segments attached to the mob is at most the given count
+    SegmentsAtMost(usize),
+    /// Every position of the mob's named spawner is inside the region between `min` and `max`
+    SpawnerInRegion {
+        spawner: String,
+        min: Vec2,
+        max: Vec2,
+    },
+    /// All of the conditions are met
+    All(Vec<MobBehaviorCondition>),
+    /// Any of the conditions are met
+    Any(Vec<MobBehaviorCondition>),
+    /// The condition is not met
+    Not(Box<MobBehaviorCondition>),
+}
+
+/// State of a mob used to check conditions
+struct MobBehaviorContext<'a> {
+    time_in_state: f32,
+    closest_player_distance: Option<f32>,
+    health_percentage: f32,
+    segment_count: usize,
+    transform: &'a Transform,
+    mob_spawners: &'a HashMap<String, Vec<MobSpawner>>,
 }
 
+impl MobBehaviorCondition {
+    fn is_met(&self, context: &MobBehaviorContext) -> bool {
+        match self {
+            MobBehaviorCondition::After(time) => context.time_in_state >= *time,
+            MobBehaviorCondition::PlayerWithin(distance) => context
+                .closest_player_distance
+                .is_some_and(|player_distance| player_distance <= *distance),
+            MobBehaviorCondition::PlayerBeyond(distance) => !context
+                .closest_player_distance
+                .is_some_and(|player_distance| player_distance <= *distance),
+            MobBehaviorCondition::HealthBelow(fraction) => context.health_percentage < *fraction,
+            MobBehaviorCondition::HealthAbove(fraction) => context.health_percentage >= *fraction,
+            MobBehaviorCondition::SegmentsAtMost(count) => context.segment_count <= *count,
+            MobBehaviorCondition::SpawnerInRegion { spawner, min, max } => context
+                .mob_spawners
+                .get(spawner)
+                .is_some_and(|mob_spawners| {
+                    mob_spawners.iter().all(|mob_spawner| {
+                        let position = match mob_spawner.position {
+                            SpawnPosition::Global(coords) => coords,
+                            SpawnPosition::Local(coords) => {
+                                context.transform.translation.xy()
+                                    + context.transform.local_x().xy() * coords.x
+                                    + context.transform.local_y().xy() * coords.y
+                            }
+                        };
+                        position.cmpge(*min).all() && position.cmple(*max).all()
+                    })
+                }),
+            MobBehaviorCondition::All(conditions) => {
+                conditions.iter().all(|condition| condition.is_met(context))
+            }
+            MobBehaviorCondition::Any(conditions) => {
+                conditions.iter().any(|condition| condition.is_met(context))
+            }
+            MobBehaviorCondition::Not(condition) => !condition.is_met(context),
+        }
+    }
+}
+
+impl MobBehaviorState {
+    /// Name of the state to switch to, from the first transition whose condition is met
+    fn next_state(&self, context: &MobBehaviorContext) -> Option<&String> {
+        self.transitions
+            .iter()
+            .find(|transition| transition.condition.is_met(context))
+            .map(|transition| &transition.to)
+    }
+}
+
+/// Behavior sequences mapped to their names
 #[derive(Deserialize, Resource)]
 pub struct BehaviorSequenceResource {
-    pub sequences: HashMap<MobBehaviorSequenceType, MobBehaviorSequence>,
+    pub sequences: HashMap<String, MobBehaviorSequence>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum BehaviorSequenceError {
+    #[error("Behavior sequence \"{0}\" has no entry in behavior_sequences.ron.")]
+    UnknownSequence(String),
+    #[error("Initial state \"{state}\" of behavior sequence \"{sequence}\" does not exist.")]
+    UnknownInitialState { sequence: String, state: String },
+    #[error("State \"{state}\" of behavior sequence \"{sequence}\" transitions to \"{to}\", which does not exist.")]
+    UnknownTransitionTarget {
+        sequence: String,
+        state: String,
+        to: String,
+    },
 }
 
-#[derive(Deserialize, Debug, Hash, PartialEq, Eq, Clone)]
-pub enum MobBehaviorSequenceType {
-    Ferritharax,
-    MechaFerritharax,
-    MechaSaucetron,
+impl BehaviorSequenceResource {
+    /// Check that the sequences used by mobs exist, and that every state that the sequences
+    /// start in or transition to exists
+    pub fn validate<'a>(
+        &self,
+        used_sequences: impl IntoIterator<Item = &'a String>,
+    ) -> Result<(), BehaviorSequenceError> {
+        if let Some(sequence) = used_sequences
+            .into_iter()
+            .find(|sequence| !self.sequences.contains_key(*sequence))
+        {
+            return Err(BehaviorSequenceError::UnknownSequence(sequence.clone()));
+        }
+
+        for (sequence_name, sequence) in self.sequences.iter() {
+            if !sequence.states.contains_key(&sequence.initial_state) {
+                return Err(BehaviorSequenceError::UnknownInitialState {
+                    sequence: sequence_name.clone(),
+                    state: sequence.initial_state.clone(),
+                });
+            }
+
+            for (state_name, state) in sequence.states.iter() {
+                if let Some(transition) = state
+                    .transitions
+                    .iter()
+                    .find(|transition| !sequence.states.contains_key(&transition.to))
+                {
+                    return Err(BehaviorSequenceError::UnknownTransitionTarget {
+                        sequence: sequence_name.clone(),
+                        state: state_name.clone(),
+                        to: transition.to.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
+impl MobBehaviorUpdateEvent {
+    fn from_state(state: &MobBehaviorState, entity: Entity) -> Self {
+        MobBehaviorUpdateEvent {
+            mob_behaviors: state.mob_behaviors.clone(),
+            control_behaviors: state.control_behaviors.clone(),
+            spawnable_behaviors: state.spawnable_behaviors.clone(),
+            entity,
+        }
+    }
+}
+
+/// Switch the states of mobs using behavior sequences, and send the behaviors of new states
 pub fn mob_behavior_sequence_tracker_system(
     behavior_sequence_resource: Res<BehaviorSequenceResource>,
     time: Res<Time>,
     mut behavior_update_event_writer: EventWriter<MobBehaviorUpdateEvent>,
-    mut mob_query: Query<(Entity, &mut MobComponent)>,
+    mut mob_query: Query<(Entity, &mut MobComponent, &Transform, &HealthComponent)>,
+    player_query: Query<&Transform, (With<PlayerComponent>, Without<MobComponent>)>,
+    mob_segment_query: Query<Entity, With<MobSegmentComponent>>,
+    joint_query: Query<&ImpulseJoint>,
 ) {
-    for (entity, mut mob_component) in mob_query.iter_mut() {
-        if let Some(behavior_sequence_type) = mob_component.behavior_sequence.clone() {
-            // get behavior sequence from resource
-            let behavior_sequence =
-                behavior_sequence_resource.sequences[&behavior_sequence_type].clone();
-            if let Some(behavior_sequence_tracker) = &mut mob_component.behavior_sequence_tracker {
-                // tick the timer
-                behavior_sequence_tracker.timer.tick(time.delta());
-
-                // if timer just finished update the behaviors
-                if behavior_sequence_tracker.timer.just_finished() {
-                    // update the index
-                    behavior_sequence_tracker.index = if behavior_sequence_tracker.index
-                        == behavior_sequence.behaviors.len() - 1
-                    {
-                        0
-                    } else {
-                        behavior_sequence_tracker.index + 1
-                    };
-
-                    // reset timer
-                    behavior_sequence_tracker
-                        .timer
-                        .set_duration(Duration::from_secs_f32(
-                            behavior_sequence.behaviors[behavior_sequence_tracker.index].time,
-                        ));
-                    behavior_sequence_tracker.timer.reset();
-
-                    // update behaviors
-                    behavior_update_event_writer.send(MobBehaviorUpdateEvent {
-                        mob_behaviors: behavior_sequence.behaviors[behavior_sequence_tracker.index]
-                            .mob_behaviors
-                            .clone(),
-                        control_behaviors: behavior_sequence.behaviors
-                            [behavior_sequence_tracker.index]
-                            .control_behaviors
-                            .clone(),
-                        spawnable_behaviors: behavior_sequence.behaviors
-                            [behavior_sequence_tracker.index]
-                            .spawnable_behaviors
-                            .clone(),
-                        entity,
-                    });
-                }
-            } else {
-                // initialize behavior sequence tracker
-                mob_component.behavior_sequence_tracker = Some(BehaviorSequenceTracker {
-                    timer: Timer::from_seconds(
-                        behavior_sequence.behaviors[0].time,
-                        TimerMode::Once,
-                    ),
-                    index: 0,
-                });
+    // find the mob that each mob segment is attached to
+    let segment_mobs: Vec<Entity> = mob_segment_query
+        .iter()
+        .filter_map(|entity| {
+            find_controlling_mob(entity, &joint_query, |mob_entity| {
+                mob_query.contains(mob_entity)
+            })
+        })
+        .collect();
+
+    for (entity, mut mob_component, transform, health) in mob_query.iter_mut() {
+        let Some(behavior_sequence) = mob_component
+            .behavior_sequence
+            .as_ref()
+            .and_then(|name| behavior_sequence_resource.sequences.get(name))
+        else {
+            continue;
+        };
+
+        // states are validated when the behavior sequences are loaded
+        let next_state = if let Some(tracker) = &mob_component.behavior_sequence_tracker {
+            let state = &behavior_sequence.states[&tracker.state];
 
-                behavior_update_event_writer.send(MobBehaviorUpdateEvent {
-                    mob_behaviors: behavior_sequence.behaviors[0].mob_behaviors.clone(),
-                    control_behaviors: behavior_sequence.behaviors[0].control_behaviors.clone(),
-                    spawnable_behaviors: behavior_sequence.behaviors[0].spawnable_behaviors.clone(),
+            let context = MobBehaviorContext {
+                time_in_state: tracker.time_in_state + time.delta_seconds(),
+                closest_player_distance: player_query
+                    .iter()
+                    .map(|player_transform| {
+                        player_transform
+                            .translation
+                            .xy()
+                            .distance(transform.translation.xy())
+                    })
+                    .min_by(|a, b| a.total_cmp(b)),
+                health_percentage: health.get_health_percentage(),
+                segment_count: segment_mobs
+                    .iter()
+                    .filter(|mob_entity| **mob_entity == entity)
+                    .count(),
+                transform,
+                mob_spawners: &mob_component.mob_spawners,
+            };
+
+            state.next_state(&context).cloned()
+        } else {
+            // initialize behavior sequence tracker
+            Some(behavior_sequence.initial_state.clone())
+        };
+
+        match next_state {
+            Some(next_state) => {
+                behavior_update_event_writer.send(MobBehaviorUpdateEvent::from_state(
+                    &behavior_sequence.states[&next_state],
                     entity,
+                ));
+
+                mob_component.behavior_sequence_tracker = Some(BehaviorSequenceTracker {
+                    state: next_state,
+                    time_in_state: 0.0,
                 });
             }
+            None => {
+                if let Some(tracker) = &mut mob_component.behavior_sequence_tracker {
+                    tracker.time_in_state += time.delta_seconds();
+                }
+            }
         }
     }
 }
@@ -205,3 +387,202 @@ pub struct EntityPair {
     pub parent: Entity,
     pub entity: Entity,
 }
+
+#[cfg(test)]
+mod test {
+    use bevy::math::{Vec2, Vec3};
+    use bevy::time::{Timer, TimerMode};
+    use bevy::transform::components::Transform;
+    use ron::de::{from_bytes, from_str};
+    use std::collections::HashMap;
+    use thetawave_interface::spawnable::{EnemyMobType, MobType, SpawnPosition};
+
+    use super::{
+        BehaviorSequenceError, BehaviorSequenceResource, MobBehaviorContext, MobBehaviorState,
+    };
+    use crate::spawnable::mob::{MobData, MobSpawner};
+
+    fn behavior_sequences_res() -> BehaviorSequenceResource {
+        from_bytes::<BehaviorSequenceResource>(include_bytes!(
+            "../../assets/data/behavior_sequences.ron"
+        ))
+        .unwrap()
+    }
+
+    fn state(transitions: &str) -> MobBehaviorState {
+        from_str::<MobBehaviorState>(&format!("(transitions: {transitions})")).unwrap()
+    }
+
+    fn context<'a>(
+        transform: &'a Transform,
+        mob_spawners: &'a HashMap<String, Vec<MobSpawner>>,
+    ) -> MobBehaviorContext<'a> {
+        MobBehaviorContext {
+            time_in_state: 0.0,
+            closest_player_distance: None,
+            health_percentage: 1.0,
+            segment_count: 0,
+            transform,
+            mob_spawners,
+        }
+    }
+
+    #[test]
+    fn test_behavior_sequence_data_is_valid() {
+        let mobs =
+            from_bytes::<HashMap<MobType, MobData>>(include_bytes!("../../assets/data/mobs.ron"))
+                .unwrap();
+
+        assert_eq!(
+            behavior_sequences_res().validate(
+                mobs.values()
+                    .filter_map(|mob_data| mob_data.behavior_sequence.as_ref())
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_missing_sequences_and_states() {
+        assert_eq!(
+            behavior_sequences_res().validate([&"Unknown".to_string()]),
+            Err(BehaviorSequenceError::UnknownSequence(
+                "Unknown".to_string()
+            ))
+        );
+
+        let mut behavior_sequences = behavior_sequences_res();
+        let sequence = behavior_sequences
+            .sequences
+            .get_mut("MissileLauncher")
+            .unwrap();
+        sequence.initial_state = "fly".to_string();
+        assert_eq!(
+            behavior_sequences.validate([]),
+            Err(BehaviorSequenceError::UnknownInitialState {
+                sequence: "MissileLauncher".to_string(),
+                state: "fly".to_string(),
+            })
+        );
+
+        let sequence = behavior_sequences
+            .sequences
+            .get_mut("MissileLauncher")
+            .unwrap();
+        sequence.initial_state = "approach".to_string();
+        sequence.states.get_mut("approach").unwrap().transitions[0].to = "fire".to_string();
+        assert_eq!(
+            behavior_sequences.validate([]),
+            Err(BehaviorSequenceError::UnknownTransitionTarget {
+                sequence: "MissileLauncher".to_string(),
+                state: "approach".to_string(),
+                to: "fire".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_first_met_transition_is_taken() {
+        let state = state(
+            r#"[
+                (condition: HealthBelow(0.5), to: "hurt"),
+                (condition: After(2.0), to: "next"),
+                (condition: After(1.0), to: "early"),
+            ]"#,
+        );
+        let transform = Transform::default();
+        let mob_spawners = HashMap::new();
+        let mut context = context(&transform, &mob_spawners);
+
+        assert_eq!(state.next_state(&context), None);
+
+        context.time_in_state = 1.5;
+        assert_eq!(state.next_state(&context), Some(&"early".to_string()));
+
+        context.time_in_state = 2.0;
+        assert_eq!(state.next_state(&context), Some(&"next".to_string()));
+
+        context.health_percentage = 0.25;
+        assert_eq!(state.next_state(&context), Some(&"hurt".to_string()));
+    }
+
+    #[test]
+    fn test_player_distance_conditions() {
+        let within = state(r#"[(condition: PlayerWithin(100.0), to: "near")]"#);
+        let beyond = state(r#"[(condition: PlayerBeyond(100.0), to: "far")]"#);
+        let transform = Transform::default();
+        let mob_spawners = HashMap::new();
+        let mut context = context(&transform, &mob_spawners);
+
+        // no players are beyond every distance, and within none
+        assert_eq!(within.next_state(&context), None);
+        assert_eq!(beyond.next_state(&context), Some(&"far".to_string()));
+
+        context.closest_player_distance = Some(100.0);
+        assert_eq!(within.next_state(&context), Some(&"near".to_string()));
+        assert_eq!(beyond.next_state(&context), None);
+
+        context.closest_player_distance = Some(150.0);
+        assert_eq!(within.next_state(&context), None);
+        assert_eq!(beyond.next_state(&context), Some(&"far".to_string()));
+    }
+
+    #[test]
+    fn test_health_and_segment_conditions() {
+        let state = state(
+            r#"[
+                (condition: All([HealthAbove(0.5), SegmentsAtMost(2)]), to: "healthy"),
+                (condition: Not(SegmentsAtMost(2)), to: "armored"),
+                (condition: Any([HealthBelow(0.1), SegmentsAtMost(0)]), to: "desperate"),
+            ]"#,
+        );
+        let transform = Transform::default();
+        let mob_spawners = HashMap::new();
+        let mut context = context(&transform, &mob_spawners);
+
+        context.segment_count = 3;
+        assert_eq!(state.next_state(&context), Some(&"armored".to_string()));
+
+        context.segment_count = 2;
+        context.health_percentage = 0.5;
+        assert_eq!(state.next_state(&context), Some(&"healthy".to_string()));
+
+        context.health_percentage = 0.3;
+        assert_eq!(state.next_state(&context), None);
+
+        context.segment_count = 0;
+        assert_eq!(state.next_state(&context), Some(&"desperate".to_string()));
+    }
+
+    #[test]
+    fn test_spawner_in_region_condition() {
+        let state = state(
+            r#"[(
+                condition: SpawnerInRegion(spawner: "missile", min: (-100.0, -100.0), max: (100.0, 100.0)),
+                to: "launch",
+            )]"#,
+        );
+        let mob_spawners = HashMap::from([(
+            "missile".to_string(),
+            vec![MobSpawner {
+                mob_type: MobType::Enemy(EnemyMobType::Missile),
+                timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+                position: SpawnPosition::Local(Vec2::new(0.0, -70.0)),
+            }],
+        )]);
+
+        let transform = Transform::from_translation(Vec3::new(0.0, 200.0, 0.0));
+        assert_eq!(state.next_state(&context(&transform, &mob_spawners)), None);
+
+        // the spawner is below the mob, so it enters the region before the mob does
+        let transform = Transform::from_translation(Vec3::new(0.0, 160.0, 0.0));
+        assert_eq!(
+            state.next_state(&context(&transform, &mob_spawners)),
+            Some(&"launch".to_string())
+        );
+
+        // spawners that the mob doesn't have are never in the region
+        let no_spawners = HashMap::new();
+        assert_eq!(state.next_state(&context(&transform, &no_spawners)), None);
+    }
+}
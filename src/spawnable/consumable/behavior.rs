@@ -14,6 +14,7 @@ use thetawave_interface::{
     health::HealthComponent,
     player::{PlayerComponent, PlayerInventoryComponent, PlayerOutgoingDamageComponent},
     spawnable::{ConsumableType, EffectType, TextEffectType},
+    status_effect::{ApplyStatusEffectEvent, StatusEffectsComponent},
 };
 
 use super::ConsumableEffect;
@@ -36,12 +37,14 @@ pub fn consumable_execute_behavior_system(
             &Transform,
             &mut HealthComponent,
             &mut PlayerOutgoingDamageComponent,
+            Option<&mut StatusEffectsComponent>,
         ),
         With<PlayerComponent>,
     >,
     mut collision_events: EventReader<SortedCollisionEvent>,
     mut spawn_effect_event_writer: EventWriter<SpawnEffectEvent>,
     mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+    mut apply_status_effect_event_writer: EventWriter<ApplyStatusEffectEvent>,
     game_parameters_res: Res<GameParametersResource>,
 ) {
     // put all collision events in a vector first (so that they can be looked at multiple times)
@@ -67,6 +70,7 @@ pub fn consumable_execute_behavior_system(
                         &game_parameters_res,
                         consumable_component.consumable_type.clone(),
                         &mut sound_effect_event_writer,
+                        &mut apply_status_effect_event_writer,
                     );
                 }
             }
@@ -88,6 +92,7 @@ fn apply_effects_on_impact(
             &Transform,
             &mut HealthComponent,
             &mut PlayerOutgoingDamageComponent,
+            Option<&mut StatusEffectsComponent>,
         ),
         With<PlayerComponent>,
     >,
@@ -96,6 +101,7 @@ fn apply_effects_on_impact(
     game_parameters_res: &GameParametersResource,
     consumable_type: ConsumableType,
     sound_effect_event_writer: &mut EventWriter<PlaySoundEffectEvent>,
+    apply_status_effect_event_writer: &mut EventWriter<ApplyStatusEffectEvent>,
 ) {
     for collision_event in collision_events.iter() {
         if let SortedCollisionEvent::PlayerToConsumableIntersection {
@@ -142,6 +148,7 @@ fn apply_effects_on_impact(
                     _,
                     mut health_component,
                     mut player_damage,
+                    mut status_effects,
                 ) in player_query.iter_mut()
                 {
                     if *player_entity == player_entity_q {
@@ -165,6 +172,22 @@ fn apply_effects_on_impact(
                                 ConsumableEffect::GainProjectiles(projectile) => {
                                     player_damage.projectile_count += *projectile;
                                 }
+                                ConsumableEffect::GainLives(lives) => {
+                                    if let Some(player_lives) = player_inventory.lives.as_mut() {
+                                        *player_lives += *lives;
+                                    }
+                                }
+                                ConsumableEffect::ApplyStatusEffect(status_effect) => {
+                                    apply_status_effect_event_writer.send(ApplyStatusEffectEvent {
+                                        target: player_entity_q,
+                                        status_effect: status_effect.clone(),
+                                    });
+                                }
+                                ConsumableEffect::ClearStatusEffects => {
+                                    if let Some(status_effects) = status_effects.as_mut() {
+                                        status_effects.clear();
+                                    }
+                                }
                             }
                         }
                     }
@@ -14,6 +14,7 @@ use thetawave_interface::{
     game::options::GameOptions,
     spawnable::{ConsumableType, SpawnableType},
     states::GameCleanup,
+    status_effect::StatusEffectData,
 };
 
 use crate::{
@@ -38,6 +39,12 @@ pub enum ConsumableEffect {
     GainArmor(usize),
     GainMoney(usize),
     GainProjectiles(usize),
+    /// Gain lives, does nothing if the run doesn't use lives
+    GainLives(usize),
+    /// Apply a status effect to the player
+    ApplyStatusEffect(StatusEffectData),
+    /// Remove all status effects from the player
+    ClearStatusEffects,
 }
 
 /// Core component for a consumable
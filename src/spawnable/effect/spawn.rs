@@ -105,7 +105,9 @@ fn spawn_text_effect(
     // create text
     let text = Text::from_section(
         match text_effect_type {
-            TextEffectType::DamageDealt => effect_text.unwrap_or("0".to_string()),
+            TextEffectType::DamageDealt(_) | TextEffectType::CriticalDamageDealt(_) => {
+                effect_text.unwrap_or("0".to_string())
+            }
 
             TextEffectType::ConsumableCollected(_) => text_effect_data.text.clone(),
         },
@@ -1,13 +1,33 @@
 use bevy::log::info;
 use bevy::prelude::{
-    in_state, App, Commands, Component, EventReader, IntoSystemConfigs, Plugin, Query, Update, With,
+    in_state, App, Commands, Component, DetectChangesMut, EventReader, EventWriter,
+    IntoSystemConfigs, Plugin, Query, Res, ResMut, Transform, Update, With,
 };
 use serde::Deserialize;
 use thetawave_interface::{
-    health::HealthComponent, player::PlayerComponent, spawnable::ItemComponent, states,
+    abilities::{AbilitySlotIDComponent, UpgradeAbilityEvent},
+    health::HealthComponent,
+    objective::Objective,
+    player::{
+        PlayerAttractionComponent, PlayerComponent, PlayerIDComponent, PlayerInventoryComponent,
+        PlayerMovementComponent, PlayerOutgoingDamageComponent,
+    },
+    spawnable::ItemComponent,
+    states,
 };
 
-use crate::collision::SortedCollisionEvent;
+use crate::{
+    assets::ItemAssets, collision::SortedCollisionEvent, game::GameParametersResource,
+    run::CurrentRunProgressResource,
+};
+
+use super::{
+    modifier::StatModifier,
+    satellite::{
+        satellite_orbit_system, spawn_satellite, spread_satellite_angle, SatelliteComponent,
+        SatelliteData,
+    },
+};
 
 pub struct ItemBehaviorPlugin;
 
@@ -24,6 +44,19 @@ impl Plugin for ItemBehaviorPlugin {
                 .run_if(in_state(states::GameStates::Playing))
                 .chain(),
         );
+
+        app.add_systems(
+            Update,
+            (
+                on_collect_modify_stats_system,
+                on_collect_spawn_satellite_system,
+                on_collect_gain_lives_system,
+                on_collect_upgrade_ability_system,
+                satellite_orbit_system,
+            )
+                .run_if(in_state(states::AppStates::Game))
+                .run_if(in_state(states::GameStates::Playing)),
+        );
     }
 }
 
@@ -31,6 +64,14 @@ impl Plugin for ItemBehaviorPlugin {
 pub enum ItemBehavior {
     OnCollectIncreaseMaxHealth(usize),
     OnCollectFullHeal,
+    /// Add to or multiply stats of the collecting player or the level objective
+    OnCollectModifyStats(Vec<StatModifier>),
+    /// Spawn a satellite that orbits the collecting player
+    OnCollectSpawnSatellite(SatelliteData),
+    /// Give the collecting player extra lives, if the run uses lives
+    OnCollectGainLives(usize),
+    /// Level up the collecting player's ability in the slot
+    OnCollectUpgradeAbility(AbilitySlotIDComponent),
 }
 
 #[derive(Component)]
@@ -39,6 +80,18 @@ pub struct OnCollectIncreaseMaxHealth(pub usize);
 #[derive(Component)]
 pub struct OnCollectFullHeal;
 
+#[derive(Component)]
+pub struct OnCollectModifyStats(pub Vec<StatModifier>);
+
+#[derive(Component)]
+pub struct OnCollectSpawnSatellite(pub SatelliteData);
+
+#[derive(Component)]
+pub struct OnCollectGainLives(pub usize);
+
+#[derive(Component)]
+pub struct OnCollectUpgradeAbility(pub AbilitySlotIDComponent);
+
 pub fn on_collect_increase_max_health_system(
     mut commands: Commands,
     mut collision_events: EventReader<SortedCollisionEvent>,
@@ -84,3 +137,164 @@ pub fn on_collect_full_heal_system(
         }
     }
 }
+
+#[allow(clippy::type_complexity)]
+pub fn on_collect_modify_stats_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<SortedCollisionEvent>,
+    item_query: Query<&OnCollectModifyStats, With<ItemComponent>>,
+    mut player_query: Query<
+        (
+            &mut PlayerOutgoingDamageComponent,
+            &mut PlayerMovementComponent,
+            &mut PlayerAttractionComponent,
+            &mut HealthComponent,
+            &mut PlayerInventoryComponent,
+        ),
+        With<PlayerComponent>,
+    >,
+    mut run_resource: ResMut<CurrentRunProgressResource>,
+) {
+    for event in collision_events.read() {
+        if let SortedCollisionEvent::PlayerToItemIntersection {
+            player_entity,
+            item_entity,
+        } = event
+        {
+            if let Ok(modify_stats) = item_query.get(*item_entity) {
+                if let Ok((
+                    mut outgoing_damage,
+                    mut movement,
+                    mut attraction,
+                    mut health,
+                    mut inventory,
+                )) = player_query.get_mut(*player_entity)
+                {
+                    for stat_modifier in modify_stats.0.iter() {
+                        stat_modifier.apply_to_player(
+                            &mut outgoing_damage,
+                            &mut movement,
+                            &mut attraction,
+                            &mut health,
+                        );
+
+                        if let Some(Objective::Defense(defense_data)) = run_resource
+                            .current_level
+                            .as_mut()
+                            .and_then(|level| level.objective.as_mut())
+                        {
+                            stat_modifier.apply_to_defense(defense_data);
+                        }
+                    }
+
+                    // recalculate the cooldown multiplier from the (possibly) modified base multiplier
+                    inventory.set_changed();
+
+                    info!("Applied {} stat modifiers", modify_stats.0.len());
+                    commands.entity(*item_entity).despawn();
+                }
+            }
+        }
+    }
+}
+
+pub fn on_collect_gain_lives_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<SortedCollisionEvent>,
+    item_query: Query<&OnCollectGainLives, With<ItemComponent>>,
+    mut player_query: Query<&mut PlayerInventoryComponent, With<PlayerComponent>>,
+) {
+    for event in collision_events.read() {
+        if let SortedCollisionEvent::PlayerToItemIntersection {
+            player_entity,
+            item_entity,
+        } = event
+        {
+            if let Ok(gain_lives) = item_query.get(*item_entity) {
+                if let Ok(mut inventory) = player_query.get_mut(*player_entity) {
+                    if let Some(lives) = inventory.lives.as_mut() {
+                        *lives += gain_lives.0;
+                        info!("Gained {} lives", gain_lives.0);
+                    }
+                    commands.entity(*item_entity).despawn();
+                }
+            }
+        }
+    }
+}
+
+pub fn on_collect_upgrade_ability_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<SortedCollisionEvent>,
+    item_query: Query<&OnCollectUpgradeAbility, With<ItemComponent>>,
+    player_query: Query<&PlayerIDComponent, With<PlayerComponent>>,
+    mut upgrade_ability_event_writer: EventWriter<UpgradeAbilityEvent>,
+) {
+    for event in collision_events.read() {
+        if let SortedCollisionEvent::PlayerToItemIntersection {
+            player_entity,
+            item_entity,
+        } = event
+        {
+            if let Ok(upgrade_ability) = item_query.get(*item_entity) {
+                if let Ok(player_id) = player_query.get(*player_entity) {
+                    upgrade_ability_event_writer.send(UpgradeAbilityEvent {
+                        player_id: *player_id,
+                        ability_slot_id: upgrade_ability.0,
+                    });
+                    commands.entity(*item_entity).despawn();
+                }
+            }
+        }
+    }
+}
+
+pub fn on_collect_spawn_satellite_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<SortedCollisionEvent>,
+    item_query: Query<(&OnCollectSpawnSatellite, &ItemComponent)>,
+    player_query: Query<&Transform, With<PlayerComponent>>,
+    mut satellite_query: Query<(Entity, &mut SatelliteComponent)>,
+    item_assets: Res<ItemAssets>,
+    game_parameters: Res<GameParametersResource>,
+) {
+    for event in collision_events.read() {
+        if let SortedCollisionEvent::PlayerToItemIntersection {
+            player_entity,
+            item_entity,
+        } = event
+        {
+            if let Ok((spawn_satellite_behavior, item)) = item_query.get(*item_entity) {
+                if let Ok(player_transform) = player_query.get(*player_entity) {
+                    // spread the existing satellites and the new one evenly around the player
+                    let mut existing_satellites: Vec<_> = satellite_query
+                        .iter_mut()
+                        .filter(|(_, satellite)| satellite.player == *player_entity)
+                        .collect();
+                    existing_satellites.sort_by_key(|(entity, _)| *entity);
+
+                    let count = existing_satellites.len() + 1;
+                    let base_angle = existing_satellites
+                        .first()
+                        .map_or(0.0, |(_, satellite)| satellite.angle);
+                    for (idx, (_, satellite)) in existing_satellites.iter_mut().enumerate() {
+                        satellite.angle = spread_satellite_angle(base_angle, idx, count);
+                    }
+
+                    spawn_satellite(
+                        &mut commands,
+                        &spawn_satellite_behavior.0,
+                        &item.item_type,
+                        *player_entity,
+                        player_transform,
+                        spread_satellite_angle(base_angle, count - 1, count),
+                        &item_assets,
+                        &game_parameters,
+                    );
+                    info!("Spawned satellite");
+                    commands.entity(*item_entity).despawn();
+                }
+            }
+        }
+    }
+}
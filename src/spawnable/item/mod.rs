@@ -20,6 +20,8 @@ use self::{
 use super::{InitialMotion, SpawnableBehavior, SpawnableComponent};
 
 mod behavior;
+mod modifier;
+mod satellite;
 mod spawn;
 
 pub struct ItemPlugin;
@@ -0,0 +1,335 @@
+//! Generic stat modifiers that items apply to players and the level objective when collected.
+use bevy::math::Vec2;
+use serde::Deserialize;
+use thetawave_interface::{
+    health::HealthComponent,
+    objective::DefenseData,
+    player::{PlayerAttractionComponent, PlayerMovementComponent, PlayerOutgoingDamageComponent},
+};
+
+/// Operation applied to the current value of a stat
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub enum StatOperation {
+    /// Add the value to the stat
+    Add(f32),
+    /// Multiply the stat by the value
+    Multiply(f32),
+}
+
+impl StatOperation {
+    fn apply_f32(self, value: f32) -> f32 {
+        match self {
+            StatOperation::Add(amount) => value + amount,
+            StatOperation::Multiply(amount) => value * amount,
+        }
+    }
+
+    /// Applies the operation and rounds to the nearest non-negative integer
+    fn apply_usize(self, value: usize) -> usize {
+        self.apply_f32(value as f32).round().max(0.0) as usize
+    }
+
+    /// Applies the operation to both axes
+    fn apply_vec2(self, value: Vec2) -> Vec2 {
+        match self {
+            StatOperation::Add(amount) => value + Vec2::splat(amount),
+            StatOperation::Multiply(amount) => value * amount,
+        }
+    }
+}
+
+/// A stat that can be modified by an item, paired with the operation applied to it
+#[derive(Deserialize, Clone, Debug)]
+pub enum StatModifier {
+    // `PlayerOutgoingDamageComponent`
+    CollisionDamage(StatOperation),
+    WeaponDamage(StatOperation),
+    ProjectileSpeed(StatOperation),
+    ProjectileDespawnTime(StatOperation),
+    ProjectileSize(StatOperation),
+    ProjectileCount(StatOperation),
+    /// Modifies the base cooldown multiplier, the cooldown multiplier is recalculated from it
+    CooldownMultiplier(StatOperation),
+    CriticalChance(StatOperation),
+    CriticalMultiplier(StatOperation),
+    // `PlayerMovementComponent`
+    Acceleration(StatOperation),
+    Deceleration(StatOperation),
+    Speed(StatOperation),
+    // `PlayerAttractionComponent`
+    AttractionDistance(StatOperation),
+    AttractionAcceleration(StatOperation),
+    // `HealthComponent`
+    /// Increases to max health also heal the player by the amount gained
+    MaxHealth(StatOperation),
+    MaxShields(StatOperation),
+    ShieldsRechargeTime(StatOperation),
+    // `DefenseData` of the current level's objective
+    MaxDefense(StatOperation),
+    Defense(StatOperation),
+}
+
+impl StatModifier {
+    /// Apply the modifier to the stats of a player, modifiers for other stats are ignored
+    pub fn apply_to_player(
+        &self,
+        outgoing_damage: &mut PlayerOutgoingDamageComponent,
+        movement: &mut PlayerMovementComponent,
+        attraction: &mut PlayerAttractionComponent,
+        health: &mut HealthComponent,
+    ) {
+        match *self {
+            StatModifier::CollisionDamage(op) => {
+                outgoing_damage.collision_damage = op.apply_usize(outgoing_damage.collision_damage);
+            }
+            StatModifier::WeaponDamage(op) => {
+                outgoing_damage.weapon_damage = op.apply_usize(outgoing_damage.weapon_damage);
+            }
+            StatModifier::ProjectileSpeed(op) => {
+                outgoing_damage.projectile_speed = op.apply_f32(outgoing_damage.projectile_speed);
+            }
+            StatModifier::ProjectileDespawnTime(op) => {
+                outgoing_damage.projectile_despawn_time =
+                    op.apply_f32(outgoing_damage.projectile_despawn_time);
+            }
+            StatModifier::ProjectileSize(op) => {
+                outgoing_damage.projectile_size = op.apply_f32(outgoing_damage.projectile_size);
+            }
+            StatModifier::ProjectileCount(op) => {
+                outgoing_damage.projectile_count =
+                    op.apply_usize(outgoing_damage.projectile_count).max(1);
+            }
+            StatModifier::CooldownMultiplier(op) => {
+                outgoing_damage.base_cooldown_multiplier =
+                    op.apply_f32(outgoing_damage.base_cooldown_multiplier);
+            }
+            StatModifier::CriticalChance(op) => {
+                outgoing_damage.critical_chance = op
+                    .apply_f32(outgoing_damage.critical_chance)
+                    .clamp(0.0, 1.0);
+            }
+            StatModifier::CriticalMultiplier(op) => {
+                outgoing_damage.critical_multiplier =
+                    op.apply_f32(outgoing_damage.critical_multiplier);
+            }
+            StatModifier::Acceleration(op) => {
+                movement.acceleration = op.apply_vec2(movement.acceleration);
+            }
+            StatModifier::Deceleration(op) => {
+                movement.deceleration = op.apply_vec2(movement.deceleration);
+            }
+            StatModifier::Speed(op) => {
+                movement.speed = op.apply_vec2(movement.speed);
+            }
+            StatModifier::AttractionDistance(op) => {
+                attraction.distance = op.apply_f32(attraction.distance);
+            }
+            StatModifier::AttractionAcceleration(op) => {
+                attraction.acceleration = op.apply_f32(attraction.acceleration);
+            }
+            StatModifier::MaxHealth(op) => {
+                let old_max_health = health.get_max_health();
+                health.set_max_health(op.apply_usize(old_max_health));
+                health.heal(health.get_max_health().saturating_sub(old_max_health));
+            }
+            StatModifier::MaxShields(op) => {
+                health.set_max_shields(op.apply_usize(health.get_max_shields()));
+            }
+            StatModifier::ShieldsRechargeTime(op) => {
+                health.set_shields_recharge_time(op.apply_f32(health.get_shields_recharge_time()));
+            }
+            StatModifier::MaxDefense(_) | StatModifier::Defense(_) => {}
+        }
+    }
+
+    /// Apply the modifier to a defense objective, modifiers for other stats are ignored
+    pub fn apply_to_defense(&self, defense_data: &mut DefenseData) {
+        match *self {
+            StatModifier::MaxDefense(op) => {
+                defense_data.set_max_defense(op.apply_usize(defense_data.max_defense));
+            }
+            StatModifier::Defense(op) => {
+                let new_defense = op.apply_usize(defense_data.defense);
+                if new_defense > defense_data.defense {
+                    defense_data.gain_defense(new_defense - defense_data.defense);
+                } else {
+                    defense_data.take_damage(defense_data.defense - new_defense);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::math::Vec2;
+    use thetawave_interface::{
+        health::{DamageType, HealthComponent},
+        objective::DefenseData,
+        player::{
+            PlayerAttractionComponent, PlayerMovementComponent, PlayerOutgoingDamageComponent,
+        },
+        spawnable::SpawnPosition,
+    };
+
+    use super::{StatModifier, StatOperation};
+
+    /// Player stats that modifiers are applied to
+    struct PlayerStats {
+        outgoing_damage: PlayerOutgoingDamageComponent,
+        movement: PlayerMovementComponent,
+        attraction: PlayerAttractionComponent,
+        health: HealthComponent,
+    }
+
+    impl PlayerStats {
+        fn new() -> Self {
+            PlayerStats {
+                outgoing_damage: PlayerOutgoingDamageComponent {
+                    collision_damage: 10,
+                    weapon_damage: 10,
+                    projectile_speed: 500.0,
+                    projectile_spawn_position: SpawnPosition::Local(Vec2::ZERO),
+                    projectile_despawn_time: 1.0,
+                    projectile_size: 1.0,
+                    projectile_count: 1,
+                    base_cooldown_multiplier: 1.0,
+                    cooldown_multiplier: 1.0,
+                    critical_chance: 0.1,
+                    critical_multiplier: 2.0,
+                },
+                movement: PlayerMovementComponent {
+                    acceleration: Vec2::splat(10.0),
+                    deceleration: Vec2::splat(10.0),
+                    speed: Vec2::splat(100.0),
+                    movement_enabled: true,
+                },
+                attraction: PlayerAttractionComponent {
+                    distance: 100.0,
+                    acceleration: 10.0,
+                },
+                health: HealthComponent::new(100, 20, 1.0),
+            }
+        }
+
+        fn apply(&mut self, modifier: StatModifier) {
+            modifier.apply_to_player(
+                &mut self.outgoing_damage,
+                &mut self.movement,
+                &mut self.attraction,
+                &mut self.health,
+            );
+        }
+    }
+
+    #[test]
+    fn test_apply_usize_rounds_to_nearest_non_negative_integer() {
+        assert_eq!(StatOperation::Multiply(1.25).apply_usize(10), 13);
+        assert_eq!(StatOperation::Multiply(1.24).apply_usize(10), 12);
+        assert_eq!(StatOperation::Add(0.5).apply_usize(2), 3);
+        assert_eq!(StatOperation::Add(-0.4).apply_usize(2), 2);
+        assert_eq!(StatOperation::Add(-5.0).apply_usize(2), 0);
+    }
+
+    #[test]
+    fn test_apply_vec2_modifies_both_axes() {
+        assert_eq!(
+            StatOperation::Add(5.0).apply_vec2(Vec2::new(1.0, 2.0)),
+            Vec2::new(6.0, 7.0)
+        );
+        assert_eq!(
+            StatOperation::Multiply(2.0).apply_vec2(Vec2::new(1.0, 2.0)),
+            Vec2::new(2.0, 4.0)
+        );
+    }
+
+    #[test]
+    fn test_critical_chance_is_clamped() {
+        let mut stats = PlayerStats::new();
+
+        stats.apply(StatModifier::CriticalChance(StatOperation::Add(2.0)));
+        assert_eq!(stats.outgoing_damage.critical_chance, 1.0);
+
+        stats.apply(StatModifier::CriticalChance(StatOperation::Add(-3.0)));
+        assert_eq!(stats.outgoing_damage.critical_chance, 0.0);
+    }
+
+    #[test]
+    fn test_projectile_count_is_at_least_one() {
+        let mut stats = PlayerStats::new();
+
+        stats.apply(StatModifier::ProjectileCount(StatOperation::Add(-5.0)));
+        assert_eq!(stats.outgoing_damage.projectile_count, 1);
+    }
+
+    #[test]
+    fn test_max_health_increase_heals_by_amount_gained() {
+        let mut stats = PlayerStats::new();
+        stats.health.take_damage(50, DamageType::Collision);
+        let health = stats.health.get_health();
+
+        stats.apply(StatModifier::MaxHealth(StatOperation::Multiply(1.5)));
+
+        assert_eq!(stats.health.get_max_health(), 150);
+        assert_eq!(stats.health.get_health(), health + 50);
+    }
+
+    #[test]
+    fn test_max_health_decrease_clamps_health() {
+        let mut stats = PlayerStats::new();
+
+        stats.apply(StatModifier::MaxHealth(StatOperation::Add(-40.0)));
+
+        assert_eq!(stats.health.get_max_health(), 60);
+        assert_eq!(stats.health.get_health(), 60);
+    }
+
+    #[test]
+    fn test_defense_modifiers_only_apply_to_defense() {
+        let mut stats = PlayerStats::new();
+        stats.apply(StatModifier::Defense(StatOperation::Add(-50.0)));
+        assert_eq!(stats.health.get_health(), 100);
+
+        let mut defense_data = DefenseData {
+            defense: 50,
+            max_defense: 100,
+        };
+        StatModifier::WeaponDamage(StatOperation::Add(5.0)).apply_to_defense(&mut defense_data);
+        assert_eq!(defense_data.defense, 50);
+    }
+
+    #[test]
+    fn test_defense_gains_and_loses_defense() {
+        let mut defense_data = DefenseData {
+            defense: 50,
+            max_defense: 100,
+        };
+
+        StatModifier::Defense(StatOperation::Add(20.0)).apply_to_defense(&mut defense_data);
+        assert_eq!(defense_data.defense, 70);
+
+        // defense goes down through `take_damage`, so it never drops below zero
+        StatModifier::Defense(StatOperation::Add(-30.0)).apply_to_defense(&mut defense_data);
+        assert_eq!(defense_data.defense, 40);
+        StatModifier::Defense(StatOperation::Add(-100.0)).apply_to_defense(&mut defense_data);
+        assert_eq!(defense_data.defense, 0);
+
+        // gains are limited by the max defense
+        StatModifier::Defense(StatOperation::Add(150.0)).apply_to_defense(&mut defense_data);
+        assert_eq!(defense_data.defense, 100);
+    }
+
+    #[test]
+    fn test_max_defense_clamps_defense() {
+        let mut defense_data = DefenseData {
+            defense: 80,
+            max_defense: 100,
+        };
+
+        StatModifier::MaxDefense(StatOperation::Multiply(0.5)).apply_to_defense(&mut defense_data);
+
+        assert_eq!(defense_data.max_defense, 50);
+        assert_eq!(defense_data.defense, 50);
+    }
+}
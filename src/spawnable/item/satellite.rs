@@ -0,0 +1,173 @@
+//! Satellites are granted by items and orbit around the player that collected them.
+use std::f32::consts::TAU;
+
+use bevy::{
+    core::Name,
+    math::{Vec2, Vec3, Vec3Swizzles},
+    prelude::{
+        default, Commands, Component, DespawnRecursiveExt, Entity, Query, Res, Transform, With,
+        Without,
+    },
+    sprite::{SpriteBundle, TextureAtlas},
+    time::Time,
+};
+use bevy_rapier2d::prelude::Velocity;
+use serde::Deserialize;
+use thetawave_interface::{
+    player::PlayerComponent,
+    spawnable::ItemType,
+    states::GameCleanup,
+    weapon::{WeaponData, WeaponsComponent},
+};
+
+use crate::{assets::ItemAssets, game::GameParametersResource};
+
+/// Data describing a satellite that orbits the player
+#[derive(Deserialize, Clone)]
+pub struct SatelliteData {
+    /// Distance from the center of the player
+    pub orbit_radius: f32,
+    /// Speed of the orbit in radians per second
+    pub angular_speed: f32,
+    /// Size multiplier of the satellite sprite
+    pub size: f32,
+    /// Weapons automatically fired by the satellite
+    #[serde(default)]
+    pub weapons: Vec<WeaponData>,
+}
+
+/// Component for entities orbiting a player
+#[derive(Component)]
+pub struct SatelliteComponent {
+    /// Player that the satellite orbits
+    pub player: Entity,
+    /// Current angle of the satellite around the player in radians
+    pub angle: f32,
+    /// Distance from the center of the player
+    pub orbit_radius: f32,
+    /// Speed of the orbit in radians per second
+    pub angular_speed: f32,
+}
+
+/// Angle of the satellite at `idx` when `count` satellites are spread evenly around a player,
+/// starting from `base_angle`
+pub fn spread_satellite_angle(base_angle: f32, idx: usize, count: usize) -> f32 {
+    (base_angle + TAU * idx as f32 / count.max(1) as f32).rem_euclid(TAU)
+}
+
+/// Spawn a satellite orbiting the given player at an angle in radians
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_satellite(
+    commands: &mut Commands,
+    satellite_data: &SatelliteData,
+    item_type: &ItemType,
+    player_entity: Entity,
+    player_transform: &Transform,
+    angle: f32,
+    item_assets: &ItemAssets,
+    game_parameters: &GameParametersResource,
+) {
+    let position =
+        player_transform.translation.xy() + Vec2::from_angle(angle) * satellite_data.orbit_radius;
+
+    let mut satellite = commands.spawn_empty();
+
+    satellite
+        .insert(SpriteBundle {
+            texture: item_assets.get_image(item_type),
+            transform: Transform {
+                translation: position.extend(player_transform.translation.z),
+                scale: Vec3::new(
+                    game_parameters.sprite_scale * satellite_data.size,
+                    game_parameters.sprite_scale * satellite_data.size,
+                    1.0,
+                ),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(TextureAtlas {
+            layout: item_assets.get_texture_atlas_layout(item_type),
+            ..default()
+        })
+        .insert(SatelliteComponent {
+            player: player_entity,
+            angle,
+            orbit_radius: satellite_data.orbit_radius,
+            angular_speed: satellite_data.angular_speed,
+        })
+        .insert(Velocity::default())
+        .insert(GameCleanup)
+        .insert(Name::new("Satellite"));
+
+    if !satellite_data.weapons.is_empty() {
+        satellite.insert(WeaponsComponent::from(satellite_data.weapons.clone()));
+    }
+}
+
+/// Moves satellites around their players, and despawns satellites whose player no longer exists
+pub fn satellite_orbit_system(
+    mut commands: Commands,
+    mut satellite_query: Query<
+        (Entity, &mut SatelliteComponent, &mut Transform),
+        Without<PlayerComponent>,
+    >,
+    player_query: Query<&Transform, With<PlayerComponent>>,
+    time: Res<Time>,
+) {
+    for (entity, mut satellite, mut satellite_transform) in satellite_query.iter_mut() {
+        if let Ok(player_transform) = player_query.get(satellite.player) {
+            satellite.angle =
+                (satellite.angle + satellite.angular_speed * time.delta_seconds()).rem_euclid(TAU);
+
+            let position = player_transform.translation.xy()
+                + Vec2::from_angle(satellite.angle) * satellite.orbit_radius;
+
+            satellite_transform.translation = position.extend(satellite_transform.translation.z);
+        } else {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    use super::spread_satellite_angle;
+
+    #[test]
+    fn test_satellites_are_spread_evenly() {
+        for count in 1..=6 {
+            let angles: Vec<f32> = (0..count)
+                .map(|idx| spread_satellite_angle(FRAC_PI_2, idx, count))
+                .collect();
+
+            for (idx, angle) in angles.iter().enumerate() {
+                let next_angle = angles[(idx + 1) % count];
+                let gap = (next_angle - angle).rem_euclid(2.0 * PI);
+                let expected_gap = if count == 1 {
+                    0.0
+                } else {
+                    2.0 * PI / count as f32
+                };
+                assert!(
+                    (gap - expected_gap).abs() < 1e-4,
+                    "gap of {} between satellites {} and {} of {}",
+                    gap,
+                    idx,
+                    (idx + 1) % count,
+                    count
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_third_satellite_is_not_on_top_of_the_first() {
+        let first = spread_satellite_angle(0.0, 0, 3);
+        let third = spread_satellite_angle(0.0, 2, 3);
+
+        assert!((third - first).abs() > 1.0);
+    }
+}
@@ -19,7 +19,10 @@ use crate::{
 use thetawave_interface::spawnable::AttractToClosestPlayerComponent;
 
 use super::{
-    behavior::{ItemBehavior, OnCollectFullHeal, OnCollectIncreaseMaxHealth},
+    behavior::{
+        ItemBehavior, OnCollectFullHeal, OnCollectGainLives, OnCollectIncreaseMaxHealth,
+        OnCollectModifyStats, OnCollectSpawnSatellite, OnCollectUpgradeAbility,
+    },
     ItemResource,
 };
 
@@ -139,6 +142,18 @@ fn add_item_behavior_components(
             ItemBehavior::OnCollectFullHeal => {
                 item.insert(OnCollectFullHeal);
             }
+            ItemBehavior::OnCollectModifyStats(stat_modifiers) => {
+                item.insert(OnCollectModifyStats(stat_modifiers.clone()));
+            }
+            ItemBehavior::OnCollectSpawnSatellite(satellite_data) => {
+                item.insert(OnCollectSpawnSatellite(satellite_data.clone()));
+            }
+            ItemBehavior::OnCollectGainLives(v) => {
+                item.insert(OnCollectGainLives(*v));
+            }
+            ItemBehavior::OnCollectUpgradeAbility(ability_slot_id) => {
+                item.insert(OnCollectUpgradeAbility(*ability_slot_id));
+            }
         };
     }
 }
@@ -9,16 +9,17 @@ use bevy::{
 use serde::Deserialize;
 use thetawave_interface::{
     audio::{PlaySoundEffectEvent, SoundEffectType},
-    health::{DamageDealtEvent, HealthComponent},
+    health::{DamageDealtEvent, DamageType, HealthComponent},
     player::PlayerIncomingDamageComponent,
     spawnable::{EffectType, MobDestroyedEvent, SpawnItemEvent, SpawnMobEvent, SpawnPosition},
+    status_effect::{ApplyStatusEffectEvent, StatusEffectData},
 };
 
 use super::{BossComponent, MobComponent};
 use crate::{
     collision::SortedCollisionEvent,
     game::GameParametersResource,
-    loot::LootDropsResource,
+    loot::LootDrops,
     spawnable::{SpawnConsumableEvent, SpawnEffectEvent},
 };
 
@@ -28,6 +29,8 @@ pub enum MobBehavior {
     SpawnMob(String),
     ExplodeOnImpact,
     DealDamageToPlayerOnImpact,
+    /// Apply status effects to players that collide with the mob
+    ApplyStatusEffectsToPlayerOnImpact(Vec<StatusEffectData>),
     ReceiveDamageOnImpact,
     DieAtZeroHealth,
 }
@@ -58,7 +61,8 @@ pub fn mob_execute_behavior_system(
     mut spawn_mob_event_writer: EventWriter<SpawnMobEvent>,
     mut mob_destroyed_event_writer: EventWriter<MobDestroyedEvent>,
     mut damage_dealt_event_writer: EventWriter<DamageDealtEvent>,
-    loot_drops_resource: Res<LootDropsResource>,
+    mut apply_status_effect_event_writer: EventWriter<ApplyStatusEffectEvent>,
+    loot_drops: LootDrops,
     mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
     game_parameters: Res<GameParametersResource>,
 ) {
@@ -104,6 +108,8 @@ pub fn mob_execute_behavior_system(
                                 position,
                                 rotation: mob_transform.rotation, // passed rotation of the parent mob
                                 boss: false,
+                                path: None,
+                                parent: Some(entity),
                             });
                         }
                     }
@@ -122,11 +128,20 @@ pub fn mob_execute_behavior_system(
                 MobBehavior::DealDamageToPlayerOnImpact => {
                     deal_damage_to_player_on_impact(
                         entity,
+                        mob_component.collision_damage_type,
                         &collision_events_vec,
                         &player_query,
                         &mut damage_dealt_event_writer,
                     );
                 }
+                MobBehavior::ApplyStatusEffectsToPlayerOnImpact(status_effects) => {
+                    apply_status_effects_to_player_on_impact(
+                        entity,
+                        &status_effects,
+                        &collision_events_vec,
+                        &mut apply_status_effect_event_writer,
+                    );
+                }
                 MobBehavior::ReceiveDamageOnImpact => {
                     receive_damage_on_impact(
                         entity,
@@ -157,7 +172,7 @@ pub fn mob_execute_behavior_system(
                         });
 
                         // drop loot
-                        loot_drops_resource.spawn_loot_drops(
+                        loot_drops.spawn_loot_drops(
                             &mob_component.loot_drops,
                             &mut spawn_consumable_event_writer,
                             &mut spawn_item_event_writer,
@@ -201,6 +216,9 @@ fn receive_damage_on_impact(
                             damage_dealt_event_writer.send(DamageDealtEvent {
                                 damage: *player_damage,
                                 target: *mob_entity,
+                                source: Some(*player_entity),
+                                damage_type: DamageType::Collision,
+                                is_critical: false,
                             });
                         }
                     }
@@ -214,19 +232,25 @@ fn receive_damage_on_impact(
                     damage_dealt_event_writer.send(DamageDealtEvent {
                         damage: *mob_damage_2,
                         target: *mob_entity_1,
+                        source: None,
+                        damage_type: DamageType::Collision,
+                        is_critical: false,
                     });
                 }
             }
             SortedCollisionEvent::MobToMobSegmentContact {
                 mob_entity,
                 mob_damage: _,
-                mob_segment_entity: _,
+                mob_segment_entity,
                 mob_segment_damage,
             } => {
                 if entity == *mob_entity && *mob_segment_damage > 0 {
                     damage_dealt_event_writer.send(DamageDealtEvent {
                         damage: *mob_segment_damage,
                         target: *mob_entity,
+                        source: Some(*mob_segment_entity),
+                        damage_type: DamageType::Collision,
+                        is_critical: false,
                     });
                 }
             }
@@ -239,6 +263,7 @@ fn receive_damage_on_impact(
 /// Deal damage to colliding entity on impact
 fn deal_damage_to_player_on_impact(
     entity: Entity,
+    damage_type: DamageType,
     collision_events: &[&SortedCollisionEvent],
     player_query: &Query<(Entity, &PlayerIncomingDamageComponent)>,
     damage_dealt_event_writer: &mut EventWriter<DamageDealtEvent>,
@@ -260,6 +285,9 @@ fn deal_damage_to_player_on_impact(
                         damage_dealt_event_writer.send(DamageDealtEvent {
                             damage,
                             target: player_entity_q,
+                            source: Some(entity),
+                            damage_type,
+                            is_critical: false,
                         });
                     }
                 }
@@ -268,6 +296,33 @@ fn deal_damage_to_player_on_impact(
     }
 }
 
+/// Apply status effects to colliding players on impact
+fn apply_status_effects_to_player_on_impact(
+    entity: Entity,
+    status_effects: &[StatusEffectData],
+    collision_events: &[&SortedCollisionEvent],
+    apply_status_effect_event_writer: &mut EventWriter<ApplyStatusEffectEvent>,
+) {
+    for collision_event in collision_events.iter() {
+        if let SortedCollisionEvent::PlayerToMobContact {
+            player_entity,
+            mob_entity,
+            player_damage: _,
+            mob_damage: _,
+        } = collision_event
+        {
+            if entity == *mob_entity {
+                for status_effect in status_effects {
+                    apply_status_effect_event_writer.send(ApplyStatusEffectEvent {
+                        target: *player_entity,
+                        status_effect: status_effect.clone(),
+                    });
+                }
+            }
+        }
+    }
+}
+
 /// Explode spawnable on impact
 #[allow(clippy::too_many_arguments)]
 fn explode_on_impact(
@@ -0,0 +1,281 @@
+use bevy::{
+    math::Vec3,
+    prelude::{Commands, Entity, EventWriter, Query, Res, Transform},
+};
+use bevy_rapier2d::prelude::ImpulseJoint;
+use serde::Deserialize;
+use thetawave_interface::{
+    audio::ChangeBackgroundMusicEvent,
+    health::HealthComponent,
+    spawnable::{EffectType, MobSegmentType},
+};
+
+use crate::{
+    game::GameParametersResource,
+    run::BGMusicTransition,
+    spawnable::{InitialMotion, SpawnEffectEvent},
+    weapon::AttackPatternComponent,
+};
+
+use super::{find_controlling_mob, BossComponent, MobComponent, MobSegmentComponent};
+
+/// Condition that switches a boss to its next phase
+#[derive(Deserialize, Clone, Debug)]
+pub enum BossPhaseTrigger {
+    /// Health of the boss drops below the given fraction of its maximum health
+    HealthBelow(f32),
+    /// None of the given mob segments are still attached to the boss
+    SegmentsDestroyed(Vec<MobSegmentType>),
+}
+
+impl BossPhaseTrigger {
+    fn is_triggered(&self, health_percentage: f32, attached_segments: &[MobSegmentType]) -> bool {
+        match self {
+            BossPhaseTrigger::HealthBelow(fraction) => health_percentage < *fraction,
+            BossPhaseTrigger::SegmentsDestroyed(mob_segment_types) => mob_segment_types
+                .iter()
+                .all(|mob_segment_type| !attached_segments.contains(mob_segment_type)),
+        }
+    }
+}
+
+/// Data about a phase of a boss, fields that are `None` are unchanged from the previous phase
+#[derive(Deserialize, Clone, Debug)]
+pub struct BossPhaseData {
+    /// Condition that starts the phase
+    pub trigger: BossPhaseTrigger,
+    /// Name of the behavior sequence that the boss switches to
+    #[serde(default)]
+    pub behavior_sequence: Option<String>,
+    /// Attack pattern from `attack_patterns.ron` that the boss switches to
+    #[serde(default)]
+    pub attack_pattern: Option<String>,
+    /// Effect spawned on the boss when the phase starts
+    #[serde(default)]
+    pub transition_effect: Option<EffectType>,
+    /// Music to change to when the phase starts
+    #[serde(default)]
+    pub bg_music_transition: Option<BGMusicTransition>,
+}
+
+impl BossPhaseData {
+    /// Combine with the phase that follows it, fields of the next phase that are `Some` replace this phase's fields
+    fn merge(self, next: BossPhaseData) -> BossPhaseData {
+        BossPhaseData {
+            trigger: next.trigger,
+            behavior_sequence: next.behavior_sequence.or(self.behavior_sequence),
+            attack_pattern: next.attack_pattern.or(self.attack_pattern),
+            transition_effect: next.transition_effect.or(self.transition_effect),
+            bg_music_transition: next.bg_music_transition.or(self.bg_music_transition),
+        }
+    }
+}
+
+/// Advance `phase` past the following phases whose triggers are met, in order
+/// Returns the combined phase data of every phase that started, or `None` if none did
+fn advance_phases(
+    phases: &[BossPhaseData],
+    phase: &mut usize,
+    health_percentage: f32,
+    attached_segments: &[MobSegmentType],
+) -> Option<BossPhaseData> {
+    // skip over phases whose triggers are already met, such as when a large amount of damage is taken at once
+    let triggered_phases: Vec<BossPhaseData> = phases
+        .iter()
+        .skip(*phase)
+        .take_while(|phase| {
+            phase
+                .trigger
+                .is_triggered(health_percentage, attached_segments)
+        })
+        .cloned()
+        .collect();
+
+    *phase += triggered_phases.len();
+
+    triggered_phases.into_iter().reduce(BossPhaseData::merge)
+}
+
+/// Switch bosses to their next phases when the triggers of the phases are met
+#[allow(clippy::too_many_arguments)]
+pub fn boss_phase_system(
+    mut commands: Commands,
+    mut boss_query: Query<(
+        Entity,
+        &mut BossComponent,
+        &mut MobComponent,
+        &HealthComponent,
+        &Transform,
+    )>,
+    mob_segment_query: Query<(Entity, &MobSegmentComponent)>,
+    joint_query: Query<&ImpulseJoint>,
+    game_parameters: Res<GameParametersResource>,
+    mut spawn_effect_event_writer: EventWriter<SpawnEffectEvent>,
+    mut change_bg_music_event_writer: EventWriter<ChangeBackgroundMusicEvent>,
+) {
+    // find the boss that each mob segment is attached to
+    let attached_segments: Vec<(Entity, MobSegmentType)> = mob_segment_query
+        .iter()
+        .filter_map(|(entity, mob_segment)| {
+            find_controlling_mob(entity, &joint_query, |mob_entity| {
+                boss_query.contains(mob_entity)
+            })
+            .map(|boss_entity| (boss_entity, mob_segment.mob_segment_type.clone()))
+        })
+        .collect();
+
+    for (entity, mut boss, mut mob, health, transform) in boss_query.iter_mut() {
+        let boss_segments: Vec<MobSegmentType> = attached_segments
+            .iter()
+            .filter(|(boss_entity, _)| *boss_entity == entity)
+            .map(|(_, mob_segment_type)| mob_segment_type.clone())
+            .collect();
+
+        let mut current_phase = boss.phase;
+        let Some(phase) = advance_phases(
+            &boss.phases,
+            &mut current_phase,
+            health.get_health_percentage(),
+            &boss_segments,
+        ) else {
+            continue;
+        };
+        boss.phase = current_phase;
+
+        // the behavior sequence tracker is reinitialized with the new sequence
+        if let Some(behavior_sequence) = phase.behavior_sequence {
+            mob.behavior_sequence = Some(behavior_sequence);
+            mob.behavior_sequence_tracker = None;
+        }
+
+        if let Some(attack_pattern) = phase.attack_pattern {
+            commands
+                .entity(entity)
+                .insert(AttackPatternComponent::new(attack_pattern));
+        }
+
+        if let Some(effect_type) = phase.transition_effect {
+            spawn_effect_event_writer.send(SpawnEffectEvent {
+                effect_type,
+                transform: Transform {
+                    translation: transform.translation,
+                    scale: Vec3::new(
+                        game_parameters.sprite_scale,
+                        game_parameters.sprite_scale,
+                        1.0,
+                    ),
+                    ..Default::default()
+                },
+                initial_motion: InitialMotion::default(),
+                text: None,
+            });
+        }
+
+        if let Some(bg_music_transition) = &phase.bg_music_transition {
+            change_bg_music_event_writer.send(bg_music_transition.into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use thetawave_interface::spawnable::{EffectType, EnemyMobSegmentType, MobSegmentType};
+
+    use super::{advance_phases, BossPhaseData, BossPhaseTrigger};
+
+    const RIGHT_CLAW: MobSegmentType =
+        MobSegmentType::Enemy(EnemyMobSegmentType::MechaSaucetronRightClaw);
+    const LEFT_CLAW: MobSegmentType =
+        MobSegmentType::Enemy(EnemyMobSegmentType::MechaSaucetronLeftClaw);
+
+    fn phase(trigger: BossPhaseTrigger) -> BossPhaseData {
+        BossPhaseData {
+            trigger,
+            behavior_sequence: None,
+            attack_pattern: None,
+            transition_effect: None,
+            bg_music_transition: None,
+        }
+    }
+
+    /// Phases like the MechaSaucetron's, an enraged phase at low health and a desperate phase without claws
+    fn phases() -> Vec<BossPhaseData> {
+        vec![
+            BossPhaseData {
+                behavior_sequence: Some("Enraged".to_string()),
+                transition_effect: Some(EffectType::MobExplosion),
+                ..phase(BossPhaseTrigger::HealthBelow(0.6))
+            },
+            BossPhaseData {
+                attack_pattern: Some("desperate".to_string()),
+                transition_effect: Some(EffectType::BarrierGlow),
+                ..phase(BossPhaseTrigger::SegmentsDestroyed(vec![
+                    RIGHT_CLAW, LEFT_CLAW,
+                ]))
+            },
+        ]
+    }
+
+    #[test]
+    fn test_health_below_trigger() {
+        let trigger = BossPhaseTrigger::HealthBelow(0.6);
+
+        assert!(trigger.is_triggered(0.5, &[]));
+        assert!(!trigger.is_triggered(0.6, &[]));
+        assert!(!trigger.is_triggered(1.0, &[]));
+    }
+
+    #[test]
+    fn test_segments_destroyed_trigger() {
+        let trigger = BossPhaseTrigger::SegmentsDestroyed(vec![RIGHT_CLAW, LEFT_CLAW]);
+
+        assert!(!trigger.is_triggered(1.0, &[RIGHT_CLAW, LEFT_CLAW]));
+        assert!(!trigger.is_triggered(1.0, &[LEFT_CLAW]));
+        assert!(trigger.is_triggered(1.0, &[]));
+        assert!(trigger.is_triggered(
+            1.0,
+            &[MobSegmentType::Enemy(
+                EnemyMobSegmentType::MechaSaucetronBody
+            )]
+        ));
+    }
+
+    #[test]
+    fn test_phases_advance_in_order() {
+        let phases = phases();
+        let mut current_phase = 0;
+
+        // the second phase's trigger is met, but the first phase hasn't started yet
+        assert!(advance_phases(&phases, &mut current_phase, 1.0, &[]).is_none());
+        assert_eq!(current_phase, 0);
+
+        let started = advance_phases(&phases, &mut current_phase, 0.5, &[RIGHT_CLAW]).unwrap();
+        assert_eq!(current_phase, 1);
+        assert_eq!(started.behavior_sequence.as_deref(), Some("Enraged"));
+        assert!(started.attack_pattern.is_none());
+
+        // phases that already started aren't started again
+        assert!(advance_phases(&phases, &mut current_phase, 0.4, &[RIGHT_CLAW]).is_none());
+
+        let started = advance_phases(&phases, &mut current_phase, 0.4, &[]).unwrap();
+        assert_eq!(current_phase, 2);
+        assert_eq!(started.attack_pattern.as_deref(), Some("desperate"));
+        assert!(started.behavior_sequence.is_none());
+
+        assert!(advance_phases(&phases, &mut current_phase, 0.0, &[]).is_none());
+    }
+
+    #[test]
+    fn test_skipped_phases_carry_over() {
+        let phases = phases();
+        let mut current_phase = 0;
+
+        // both phases start in the same frame, changes of the first phase are kept
+        let started = advance_phases(&phases, &mut current_phase, 0.5, &[]).unwrap();
+        assert_eq!(current_phase, 2);
+        assert_eq!(started.behavior_sequence.as_deref(), Some("Enraged"));
+        assert_eq!(started.attack_pattern.as_deref(), Some("desperate"));
+        // the last phase wins when both phases set a field
+        assert_eq!(started.transition_effect, Some(EffectType::BarrierGlow));
+    }
+}
@@ -11,7 +11,7 @@ use rand::{thread_rng, Rng};
 use serde::Deserialize;
 use thetawave_interface::{
     audio::{PlaySoundEffectEvent, SoundEffectType},
-    health::{DamageDealtEvent, HealthComponent},
+    health::{DamageDealtEvent, DamageType, HealthComponent},
     player::PlayerIncomingDamageComponent,
     spawnable::{
         EffectType, MobDestroyedEvent, MobSegmentDestroyedEvent, SpawnItemEvent, SpawnPosition,
@@ -22,7 +22,7 @@ use thetawave_interface::{
 use crate::{
     collision::SortedCollisionEvent,
     game::GameParametersResource,
-    loot::LootDropsResource,
+    loot::LootDrops,
     spawnable::{
         behavior_sequence::EntityPair, SpawnConsumableEvent, SpawnEffectEvent, SpawnMobEvent,
     },
@@ -79,7 +79,7 @@ pub fn mob_segment_execute_behavior_system(
     )>,
     mut spawn_effect_event_writer: EventWriter<SpawnEffectEvent>,
     player_query: Query<(Entity, &PlayerIncomingDamageComponent)>,
-    loot_drops_resource: Res<LootDropsResource>,
+    loot_drops: LootDrops,
     mut spawn_consumable_event_writer: EventWriter<SpawnConsumableEvent>,
     mut spawn_item_event_writer: EventWriter<SpawnItemEvent>,
     mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
@@ -109,6 +109,7 @@ pub fn mob_segment_execute_behavior_system(
                 MobSegmentBehavior::DealDamageToPlayerOnImpact => {
                     deal_damage_to_player_on_impact(
                         entity,
+                        mob_segment_component.collision_damage_type,
                         &collision_events_vec,
                         &player_query,
                         &mut damage_dealt_event_writer,
@@ -144,7 +145,7 @@ pub fn mob_segment_execute_behavior_system(
                         });
 
                         // drop loot
-                        loot_drops_resource.spawn_loot_drops(
+                        loot_drops.spawn_loot_drops(
                             &mob_segment_component.consumable_drops,
                             &mut spawn_consumable_event_writer,
                             &mut spawn_item_event_writer,
@@ -207,6 +208,8 @@ pub fn mob_segment_execute_behavior_system(
                                 position,
                                 rotation: mob_segment_transform.rotation, // passed rotation of the parent mob
                                 boss: false,
+                                path: None,
+                                parent: Some(entity),
                             });
                         }
                     }
@@ -299,6 +302,7 @@ pub fn mob_segment_apply_disconnected_behaviors_system(
 /// Deal damage to colliding entity on impact
 fn deal_damage_to_player_on_impact(
     entity: Entity,
+    damage_type: DamageType,
     collision_events: &[&SortedCollisionEvent],
     player_query: &Query<(Entity, &PlayerIncomingDamageComponent)>,
     damage_dealt_event_writer: &mut EventWriter<DamageDealtEvent>,
@@ -320,6 +324,9 @@ fn deal_damage_to_player_on_impact(
                         damage_dealt_event_writer.send(DamageDealtEvent {
                             damage,
                             target: player_entity_q,
+                            source: Some(entity),
+                            damage_type,
+                            is_critical: false,
                         });
                     }
                 }
@@ -349,6 +356,9 @@ fn receive_damage_on_impact(
                             damage_dealt_event_writer.send(DamageDealtEvent {
                                 damage: *player_damage,
                                 target: *mob_segment_entity,
+                                source: Some(*player_entity),
+                                damage_type: DamageType::Collision,
+                                is_critical: false,
                             });
                         }
                     }
@@ -357,13 +367,16 @@ fn receive_damage_on_impact(
             SortedCollisionEvent::MobToMobSegmentContact {
                 mob_segment_entity,
                 mob_segment_damage: _,
-                mob_entity: _,
+                mob_entity,
                 mob_damage,
             } => {
                 if entity == *mob_segment_entity && *mob_damage > 0 {
                     damage_dealt_event_writer.send(DamageDealtEvent {
                         damage: *mob_damage,
                         target: *mob_segment_entity,
+                        source: Some(*mob_entity),
+                        damage_type: DamageType::Collision,
+                        is_critical: false,
                     });
                 }
             }
@@ -375,6 +388,9 @@ fn receive_damage_on_impact(
                     damage_dealt_event_writer.send(DamageDealtEvent {
                         damage: *mob_segment_damage_2,
                         target: *mob_segment_entity_1,
+                        source: None,
+                        damage_type: DamageType::Collision,
+                        is_critical: false,
                     });
                 }
             }
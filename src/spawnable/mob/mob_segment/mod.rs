@@ -13,11 +13,12 @@ use serde::Deserialize;
 use std::collections::{hash_map::Entry, HashMap};
 use thetawave_interface::{
     audio::CollisionSoundType,
-    health::HealthComponent,
+    health::{DamageResistances, DamageType, HealthComponent},
     objective::DefenseInteraction,
     spawnable::{MobSegmentType, SpawnableType},
     states::GameCleanup,
-    weapon::{WeaponData, WeaponsComponent},
+    targeting::TargetingData,
+    weapon::{BeamWeaponData, WeaponData, WeaponsComponent},
 };
 
 use crate::collision::{
@@ -28,7 +29,8 @@ use crate::{
     assets::MobAssets,
     game::GameParametersResource,
     loot::DropListType,
-    spawnable::SpawnableComponent,
+    spawnable::{targeting::TargetingComponent, SpawnableComponent},
+    weapon::AttackPatternComponent,
 };
 
 mod behavior;
@@ -52,6 +54,7 @@ pub struct MobSegmentsResource {
 pub struct MobSegmentComponent {
     pub mob_segment_type: MobSegmentType,
     pub collision_damage: usize,
+    pub collision_damage_type: DamageType,
     pub collision_sound: CollisionSoundType,
     pub defense_interaction: Option<DefenseInteraction>,
     pub consumable_drops: DropListType,
@@ -81,6 +84,7 @@ impl From<&MobSegmentData> for MobSegmentComponent {
         MobSegmentComponent {
             mob_segment_type: mob_segment_data.mob_segment_type.clone(),
             collision_damage: mob_segment_data.collision_damage,
+            collision_damage_type: mob_segment_data.collision_damage_type,
             collision_sound: mob_segment_data.collision_sound,
             defense_interaction: mob_segment_data.defense_interaction.clone(),
             consumable_drops: mob_segment_data.consumable_drops.clone(),
@@ -90,12 +94,25 @@ impl From<&MobSegmentData> for MobSegmentComponent {
     }
 }
 
+impl MobSegmentComponent {
+    /// Whether the mob segment is destroyed when its health reaches zero
+    pub fn is_destructible(&self) -> bool {
+        self.behaviors
+            .iter()
+            .any(|behavior| matches!(behavior, MobSegmentBehavior::DieAtZeroHealth))
+    }
+}
+
 #[derive(Deserialize)]
 pub struct MobSegmentData {
     pub animation: AnimationData,
     pub colliders: Vec<ColliderData>,
     pub mob_segment_type: MobSegmentType,
     pub collision_damage: usize,
+    #[serde(default = "super::default_collision_damage_type")]
+    pub collision_damage_type: DamageType,
+    #[serde(default)]
+    pub resistances: DamageResistances,
     #[serde(default)]
     pub collision_sound: CollisionSoundType,
     #[serde(default)]
@@ -110,6 +127,12 @@ pub struct MobSegmentData {
     pub mob_spawners: Option<HashMap<String, Vec<MobSpawnerData>>>,
     #[serde(default)]
     pub weapons: Option<Vec<WeaponData>>,
+    #[serde(default)]
+    pub beam_weapons: Option<Vec<BeamWeaponData>>,
+    #[serde(default)]
+    pub attack_pattern: Option<String>,
+    #[serde(default)]
+    pub targeting: TargetingData,
     #[serde(default = "default_mob_segment_density")]
     pub density: f32,
 }
@@ -120,13 +143,15 @@ fn default_mob_segment_density() -> f32 {
 
 impl From<&MobSegmentData> for HealthComponent {
     fn from(mob_segment_data: &MobSegmentData) -> Self {
-        HealthComponent::new(mob_segment_data.health, 0, 0.0)
+        let mut health = HealthComponent::new(mob_segment_data.health, 0, 0.0);
+        health.set_resistances(mob_segment_data.resistances.clone());
+        health
     }
 }
 
 impl MobSegmentData {
     pub fn get_weapon_component(&self) -> Option<WeaponsComponent> {
-        self.weapons.clone().map(WeaponsComponent::from)
+        WeaponsComponent::new(self.weapons.clone(), self.beam_weapons.clone())
     }
 }
 
@@ -197,6 +222,10 @@ pub fn spawn_mob_segment(
         })
         .insert(MobSegmentComponent::from(mob_segment_data))
         .insert(HealthComponent::from(mob_segment_data))
+        .insert(TargetingComponent::new(
+            &mob_segment_data.targeting,
+            mob_segment_type.get_faction(),
+        ))
         .insert(SpawnableComponent::new(SpawnableType::MobSegment(
             mob_segment_type.clone(),
         )))
@@ -210,6 +239,10 @@ pub fn spawn_mob_segment(
         mob_segment.insert(weapon_component);
     }
 
+    if let Some(attack_pattern) = &mob_segment_data.attack_pattern {
+        mob_segment.insert(AttackPatternComponent::new(attack_pattern.clone()));
+    }
+
     let mob_segment_entity = mob_segment.id();
 
     if let Some(mob_segment_anchor_points) = mob_segment_data.mob_segment_anchor_points.clone() {
@@ -4,7 +4,7 @@ use bevy::{
     log::info,
     math::{Quat, Vec2, Vec3},
     prelude::{
-        default, BuildChildren, Commands, Component, Event, EventReader, EventWriter, Query, Res,
+        default, BuildChildren, Commands, Component, Entity, EventReader, EventWriter, Query, Res,
         Resource, Transform,
     },
     sprite::{Sprite, SpriteBundle, TextureAtlas},
@@ -14,9 +14,10 @@ use bevy_rapier2d::{
     geometry::ColliderMassProperties,
     prelude::{
         ActiveEvents, CoefficientCombineRule, Collider, CollisionGroups, Friction, Group,
-        LockedAxes, Restitution, RevoluteJointBuilder, RigidBody, Velocity,
+        ImpulseJoint, LockedAxes, Restitution, RevoluteJointBuilder, RigidBody, Velocity,
     },
 };
+use boss_phase::BossPhaseData;
 use mob_segment::{spawn_mob_segment, MobSegmentBehavior};
 use serde::Deserialize;
 use std::collections::{hash_map::Entry, HashMap};
@@ -26,32 +27,40 @@ use crate::{
     assets::MobAssets,
     game::GameParametersResource,
     loot::DropListType,
-    spawnable::{SpawnableBehavior, SpawnableComponent},
+    spawnable::{targeting::TargetingComponent, SpawnableBehavior, SpawnableComponent},
+    weapon::AttackPatternComponent,
 };
 
 mod behavior;
+mod boss_phase;
 mod mob_segment;
 pub(crate) use self::mob_segment::MobSegmentComponent;
 pub(in crate::spawnable) use self::{
     behavior::{mob_execute_behavior_system, MobBehavior, MobSegmentControlBehavior},
+    boss_phase::boss_phase_system,
     mob_segment::{
         mob_segment_apply_disconnected_behaviors_system, mob_segment_execute_behavior_system,
         MobSegmentsResource,
     },
 };
 
-use super::{behavior_sequence::MobBehaviorSequenceType, InitialMotion};
+use super::InitialMotion;
 use crate::collision::{
     HORIZONTAL_BARRIER_COLLIDER_GROUP, MOB_COLLIDER_GROUP, SPAWNABLE_COLLIDER_GROUP,
 };
 use thetawave_interface::{
     audio::CollisionSoundType,
     game::options::GameOptions,
-    health::HealthComponent,
+    health::{DamageResistances, DamageType, HealthComponent},
     objective::DefenseInteraction,
-    spawnable::{MobDestroyedEvent, MobSegmentType, MobType, SpawnMobEvent, SpawnPosition},
+    path::{FollowedPath, PathData},
+    spawnable::{
+        BossesDestroyedEvent, MobDestroyedEvent, MobSegmentType, MobType, SpawnMobEvent,
+        SpawnPosition,
+    },
     states::GameCleanup,
-    weapon::{WeaponData, WeaponsComponent},
+    targeting::TargetingData,
+    weapon::{BeamWeaponData, WeaponData, WeaponsComponent},
 };
 
 /// Core component for mobs
@@ -67,14 +76,16 @@ pub struct MobComponent {
     >,
     /// Control behaviors currently in use
     pub control_behaviors: Vec<MobSegmentControlBehavior>,
-    /// Behavior sequence that the mob is using
-    pub behavior_sequence: Option<MobBehaviorSequenceType>,
+    /// Name of the behavior sequence that the mob is using
+    pub behavior_sequence: Option<String>,
     /// Tracks the behavior sequence of the mob
     pub behavior_sequence_tracker: Option<BehaviorSequenceTracker>,
     /// Tracks available mob spawning patterns for the mob
     pub mob_spawners: HashMap<String, Vec<MobSpawner>>,
     /// Damage dealt to other factions on collision
     pub collision_damage: usize,
+    /// Type of the damage dealt on collision
+    pub collision_damage_type: DamageType,
     pub collision_sound: CollisionSoundType,
     /// Damage dealt to defense objective, after reaching bottom of arena
     pub defense_interaction: Option<DefenseInteraction>,
@@ -102,12 +113,13 @@ impl From<&MobData> for MobComponent {
         MobComponent {
             mob_type: mob_data.mob_type.clone(),
             behaviors: mob_data.mob_behaviors.clone(),
-            behavior_sequence: mob_data.behavior_sequence_type.clone(),
+            behavior_sequence: mob_data.behavior_sequence.clone(),
             mob_segment_behaviors: mob_data.mob_segment_behaviors.clone(),
             control_behaviors: mob_data.control_behaviors.clone(),
             behavior_sequence_tracker: None,
             mob_spawners,
             collision_damage: mob_data.collision_damage,
+            collision_damage_type: mob_data.collision_damage_type,
             collision_sound: mob_data.collision_sound,
             defense_interaction: mob_data.defense_interaction.clone(),
             loot_drops: mob_data.consumable_drops.clone(),
@@ -115,8 +127,44 @@ impl From<&MobData> for MobComponent {
     }
 }
 
+/// Maximum number of joints between a mob segment and the mob that controls it
+const MAX_JOINT_DEPTH: usize = 16;
+
+/// Tags a mob as a boss, and tracks the phases of the boss
 #[derive(Component)]
-pub struct BossComponent;
+pub struct BossComponent {
+    /// Index of the current phase, the boss spawns in phase 0
+    pub phase: usize,
+    /// Phases that the boss switches to in order, starting with phase 1
+    pub phases: Vec<BossPhaseData>,
+}
+
+impl BossComponent {
+    /// Total number of phases, including the phase that the boss spawns in
+    pub fn phase_count(&self) -> usize {
+        self.phases.len() + 1
+    }
+}
+
+/// Mob or mob segment that spawned the mob, used by behaviors that follow their parent
+#[derive(Component)]
+pub struct MobParentComponent(pub Entity);
+
+/// Find the mob that an entity belongs to, by following the joints of mob segments
+pub(crate) fn find_controlling_mob(
+    entity: Entity,
+    joint_query: &Query<&ImpulseJoint>,
+    is_mob: impl Fn(Entity) -> bool,
+) -> Option<Entity> {
+    let mut current = entity;
+    for _ in 0..MAX_JOINT_DEPTH {
+        if is_mob(current) {
+            return Some(current);
+        }
+        current = joint_query.get(current).ok()?.parent;
+    }
+    None
+}
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct MobSpawner {
@@ -143,8 +191,10 @@ pub struct MobSpawnerData {
 }
 
 pub struct BehaviorSequenceTracker {
-    pub timer: Timer,
-    pub index: usize,
+    /// Name of the current state
+    pub state: String,
+    /// Time in seconds that the mob has been in the current state
+    pub time_in_state: f32,
 }
 
 /// Data about mob entities that can be stored in data ron file
@@ -155,8 +205,8 @@ pub struct MobData {
     /// List of spawnable behaviors that are performed
     #[serde(default)]
     pub spawnable_behaviors: Vec<SpawnableBehavior>,
-    /// Behavior sequence type
-    pub behavior_sequence_type: Option<MobBehaviorSequenceType>,
+    /// Name of the behavior sequence from `behavior_sequences.ron`
+    pub behavior_sequence: Option<String>,
     /// List of mob behaviors that are performed
     #[serde(default)]
     pub mob_behaviors: Vec<MobBehavior>,
@@ -200,6 +250,12 @@ pub struct MobData {
     /// Damage dealt to other factions through attacks
     #[serde(default)]
     pub collision_damage: usize,
+    /// Type of the damage dealt on collision, mobs that should bypass shields use `Collision`
+    #[serde(default = "default_collision_damage_type")]
+    pub collision_damage_type: DamageType,
+    /// Fraction of damage resisted for each damage type
+    #[serde(default)]
+    pub resistances: DamageResistances,
     /// Damage dealt to defense objective, after reaching bottom of arena
     #[serde(default)]
     pub collision_sound: CollisionSoundType,
@@ -220,6 +276,18 @@ pub struct MobData {
     /// projectile spawners that the mob can use
     #[serde(default)]
     pub weapons: Option<Vec<WeaponData>>,
+    /// beam weapons that the mob can use
+    #[serde(default)]
+    pub beam_weapons: Option<Vec<BeamWeaponData>>,
+    /// name of the attack pattern from `attack_patterns.ron` that fires the weapons
+    #[serde(default)]
+    pub attack_pattern: Option<String>,
+    /// phases that the mob switches to when spawned as a boss
+    #[serde(default)]
+    pub boss_phases: Vec<BossPhaseData>,
+    /// how the mob chooses which hostile entity to target
+    #[serde(default)]
+    pub targeting: TargetingData,
     #[serde(default = "default_mob_density")]
     pub density: f32,
 }
@@ -228,15 +296,21 @@ fn default_mob_density() -> f32 {
     1.0
 }
 
+fn default_collision_damage_type() -> DamageType {
+    DamageType::Kinetic
+}
+
 impl From<&MobData> for HealthComponent {
     fn from(mob_data: &MobData) -> Self {
-        HealthComponent::new(mob_data.health, 0, 0.0)
+        let mut health = HealthComponent::new(mob_data.health, 0, 0.0);
+        health.set_resistances(mob_data.resistances.clone());
+        health
     }
 }
 
 impl MobData {
     pub fn get_weapon_component(&self) -> Option<WeaponsComponent> {
-        self.weapons.clone().map(WeaponsComponent::from)
+        WeaponsComponent::new(self.weapons.clone(), self.beam_weapons.clone())
     }
 }
 
@@ -293,6 +367,8 @@ pub fn spawn_mob_system(
             event.position,
             event.rotation,
             event.boss,
+            event.path.clone(),
+            event.parent,
             &mut commands,
             &game_parameters,
             &game_options,
@@ -338,6 +414,8 @@ pub fn spawn_mob(
     position: Vec2,
     rotation: Quat,
     boss: bool,
+    path: Option<PathData>,
+    parent: Option<Entity>,
     commands: &mut Commands,
     game_parameters: &GameParametersResource,
     game_options: &GameOptions,
@@ -345,6 +423,10 @@ pub fn spawn_mob(
     // Get data from mob resource
     let mob_data = &mob_resource.mobs[mob_type];
 
+    // mobs on a path are spawned at their starting points along it
+    let path = path.map(FollowedPath::new);
+    let position = path.as_ref().map_or(position, |path| path.sample().0);
+
     // create mob entity
     let mut mob = commands.spawn_empty();
 
@@ -389,14 +471,36 @@ pub fn spawn_mob(
     })
     .insert(MobComponent::from(mob_data))
     .insert(HealthComponent::from(mob_data))
-    .insert(SpawnableComponent::from(mob_data))
+    .insert(TargetingComponent::new(
+        &mob_data.targeting,
+        mob_type.get_faction(),
+    ))
     .insert(ActiveEvents::COLLISION_EVENTS)
     .insert(GameCleanup)
     .insert(ColliderMassProperties::Density(mob_data.density))
     .insert(Name::new(mob_data.mob_type.to_string()));
 
+    // following a path replaces the mob's movement behaviors
+    let mut spawnable_component = SpawnableComponent::from(mob_data);
+    if let Some(path) = path {
+        spawnable_component
+            .behaviors
+            .retain(|behavior| !behavior.is_movement());
+        spawnable_component
+            .behaviors
+            .push(SpawnableBehavior::FollowPath(path));
+    }
+    mob.insert(spawnable_component);
+
+    if let Some(parent) = parent {
+        mob.insert(MobParentComponent(parent));
+    }
+
     if boss {
-        mob.insert(BossComponent);
+        mob.insert(BossComponent {
+            phase: 0,
+            phases: mob_data.boss_phases.clone(),
+        });
     }
 
     if !mob_data.can_rotate {
@@ -407,6 +511,10 @@ pub fn spawn_mob(
         mob.insert(weapon_component);
     }
 
+    if let Some(attack_pattern) = &mob_data.attack_pattern {
+        mob.insert(AttackPatternComponent::new(attack_pattern.clone()));
+    }
+
     // spawn thruster as child if mob has thruster
     if let Some(thruster) = &mob_data.thruster {
         mob.with_children(|parent| {
@@ -470,9 +578,6 @@ pub fn spawn_mob(
     }
 }
 
-#[derive(Event)]
-pub struct BossesDestroyedEvent;
-
 pub fn check_boss_mobs_system(
     boss_mobs_query: Query<&BossComponent>,
     mut mob_destroyed_event_reader: EventReader<MobDestroyedEvent>,
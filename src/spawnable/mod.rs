@@ -10,7 +10,7 @@ use bevy::state::condition::in_state;
 use bevy::time::{Time, Timer};
 use bevy_rapier2d::prelude::Velocity;
 use mob::{
-    check_boss_mobs_system, mob_execute_behavior_system,
+    boss_phase_system, check_boss_mobs_system, mob_execute_behavior_system,
     mob_segment_apply_disconnected_behaviors_system, mob_segment_execute_behavior_system,
     spawn_mob_system, MobData, MobSegmentsResource, MobsResource,
 };
@@ -18,10 +18,11 @@ use rand::{thread_rng, Rng};
 use ron::de::from_bytes;
 use serde::Deserialize;
 use std::collections::HashMap;
-use thetawave_interface::spawnable::{ConsumableType, MobType, ProjectileType};
 use thetawave_interface::spawnable::{
-    MobDestroyedEvent, MobSegmentDestroyedEvent, SpawnMobEvent, SpawnableType,
+    BossesDestroyedEvent, MobDestroyedEvent, MobSegmentDestroyedEvent, ProjectileHitEvent,
+    SpawnMobEvent, SpawnableType,
 };
+use thetawave_interface::spawnable::{ConsumableType, MobType, ProjectileType};
 use thetawave_interface::states;
 mod behavior;
 mod behavior_sequence;
@@ -30,15 +31,22 @@ mod effect;
 mod item;
 mod mob;
 mod projectile;
+mod targeting;
 use self::behavior::attract_to_player_system;
 use self::item::ItemPlugin;
 use self::{
-    behavior::{spawnable_execute_behavior_system, spawnable_set_target_behavior_system},
+    behavior::{
+        spawnable_execute_behavior_system, spawnable_follow_path_system,
+        spawnable_set_target_behavior_system,
+    },
     behavior_sequence::{
         mob_behavior_sequence_tracker_system, mob_behavior_sequence_update_system,
     },
     consumable::{consumable_execute_behavior_system, spawn_consumable_system},
-    projectile::{projectile_execute_behavior_system, spawn_projectile_system},
+    projectile::{
+        projectile_execute_behavior_system, projectile_homing_system, spawn_projectile_system,
+    },
+    targeting::{targeting_aggro_system, update_targeting_system},
 };
 
 // TODO: move to interface, or change to use events for sending information between modules
@@ -49,11 +57,12 @@ pub(crate) use self::consumable::{
 };
 pub(crate) use self::effect::{EffectsResource, SpawnEffectEvent};
 pub(crate) use self::mob::{
-    BossComponent, BossesDestroyedEvent, MobComponent, MobSegmentComponent,
+    find_controlling_mob, BossComponent, MobComponent, MobParentComponent, MobSegmentComponent,
 };
 pub(crate) use self::projectile::{
     FireWeaponEvent, ProjectileComponent, ProjectileData, ProjectileResource,
 };
+pub(crate) use self::targeting::{hostile_targets, select_target, TargetsQuery};
 
 /// (de)spawns items, mobs, the player, etc. Also executes many of their behaviors. Without this
 /// plugin, very little with spawn into the game once it begins.
@@ -61,19 +70,28 @@ pub(super) struct SpawnablePlugin;
 
 impl Plugin for SpawnablePlugin {
     fn build(&self, app: &mut App) {
+        let behavior_sequences = from_bytes::<BehaviorSequenceResource>(include_bytes!(
+            "../../assets/data/behavior_sequences.ron"
+        ))
+        .expect("Failed to parse BehaviorSequenceResource from 'behavior_sequences.ron'");
+        let mobs =
+            from_bytes::<HashMap<MobType, MobData>>(include_bytes!("../../assets/data/mobs.ron"))
+                .expect("Failed to parse MobsResource from 'mobs.ron'");
+
+        if let Err(e) = behavior_sequences.validate(mobs.values().flat_map(|mob_data| {
+            mob_data.behavior_sequence.iter().chain(
+                mob_data
+                    .boss_phases
+                    .iter()
+                    .filter_map(|phase| phase.behavior_sequence.as_ref()),
+            )
+        })) {
+            panic!("Invalid behavior sequence data. {e}");
+        }
+
+        app.insert_resource(behavior_sequences);
+        app.insert_resource(MobsResource { mobs });
         app.insert_resource(
-            from_bytes::<BehaviorSequenceResource>(include_bytes!(
-                "../../assets/data/behavior_sequences.ron"
-            ))
-            .expect("Failed to parse BehaviorSequenceResource from 'behavior_sequences.ron'"),
-        )
-        .insert_resource(MobsResource {
-            mobs: from_bytes::<HashMap<MobType, MobData>>(include_bytes!(
-                "../../assets/data/mobs.ron"
-            ))
-            .expect("Failed to parse MobsResource from 'mobs.ron'"),
-        })
-        .insert_resource(
             from_bytes::<MobSegmentsResource>(include_bytes!("../../assets/data/mob_segments.ron"))
                 .expect("Failed to parse MobSegmentsResource from 'mob_segments.ron'"),
         )
@@ -96,6 +114,7 @@ impl Plugin for SpawnablePlugin {
             .add_event::<MobBehaviorUpdateEvent>()
             .add_event::<MobDestroyedEvent>()
             .add_event::<MobSegmentDestroyedEvent>()
+            .add_event::<ProjectileHitEvent>()
             .add_event::<BossesDestroyedEvent>();
 
         app.add_plugins((EffectPlugin, ItemPlugin));
@@ -108,21 +127,33 @@ impl Plugin for SpawnablePlugin {
                 mob_behavior_sequence_tracker_system,
                 mob_behavior_sequence_update_system,
                 spawnable_execute_behavior_system.in_set(GameUpdateSet::ExecuteBehavior),
+                spawnable_follow_path_system.in_set(GameUpdateSet::Movement),
                 mob_execute_behavior_system.in_set(GameUpdateSet::ExecuteBehavior),
                 mob_segment_apply_disconnected_behaviors_system
                     .in_set(GameUpdateSet::ApplyDisconnectedBehaviors),
                 mob_segment_execute_behavior_system.in_set(GameUpdateSet::ExecuteBehavior),
                 projectile_execute_behavior_system.in_set(GameUpdateSet::ExecuteBehavior),
+                projectile_homing_system.in_set(GameUpdateSet::Movement),
                 consumable_execute_behavior_system.in_set(GameUpdateSet::ExecuteBehavior),
                 spawn_projectile_system,
                 spawn_consumable_system, // event generated in mob execute behavior
                 spawn_mob_system,        // event generated in mob execute behavior
                 check_boss_mobs_system.after(spawn_mob_system),
+                boss_phase_system,
                 attract_to_player_system,
             )
                 .run_if(in_state(states::AppStates::Game))
                 .run_if(in_state(states::GameStates::Playing)),
         );
+
+        app.add_systems(
+            Update,
+            (targeting_aggro_system, update_targeting_system)
+                .chain()
+                .before(GameUpdateSet::SetTargetBehavior)
+                .run_if(in_state(states::AppStates::Game))
+                .run_if(in_state(states::GameStates::Playing)),
+        );
     }
 }
 
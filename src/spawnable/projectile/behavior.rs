@@ -3,18 +3,22 @@ use crate::{
     spawnable::{MobComponent, MobSegmentComponent, SpawnEffectEvent},
 };
 use bevy::{
+    math::Vec2,
     prelude::{
-        default, Commands, DespawnRecursiveExt, Entity, EventReader, EventWriter, Query, Res,
-        Transform,
+        default, Commands, DespawnRecursiveExt, Entity, EventReader, EventWriter, Mut, Quat, Query,
+        Res, Transform, Vec3Swizzles, Without,
     },
     time::Time,
 };
+use bevy_rapier2d::prelude::{Collider, Velocity};
+use rand::{thread_rng, Rng};
 use serde::Deserialize;
 use thetawave_interface::{
     audio::{PlaySoundEffectEvent, SoundEffectType},
-    health::DamageDealtEvent,
-    player::PlayerComponent,
-    spawnable::{EffectType, Faction, ProjectileType},
+    health::{DamageDealtEvent, DamageType},
+    player::{PlayerComponent, PlayerOutgoingDamageComponent},
+    spawnable::{EffectType, Faction, ProjectileHitEvent, ProjectileType},
+    status_effect::{ApplyStatusEffectEvent, StatusEffectData},
 };
 
 use super::ProjectileComponent;
@@ -26,14 +30,33 @@ pub enum ProjectileBehavior {
     ExplodeOnContact,
     DealDamageOnIntersection,
     DealDamageOnContact,
-    TimedDespawn { despawn_time: f32 },
+    /// Apply status effects to players and mobs of other factions that are hit
+    ApplyStatusEffectsOnImpact(Vec<StatusEffectData>),
+    /// Pass through up to `count` targets instead of being destroyed,
+    /// multiplying the damage of the projectile by `damage_multiplier` after each one
+    Pierce {
+        count: usize,
+        damage_multiplier: f32,
+    },
+    /// Bounce off of up to `count` targets or arena barriers instead of being destroyed
+    Ricochet {
+        count: usize,
+    },
+    TimedDespawn {
+        despawn_time: f32,
+    },
 }
 
 /// Manages executing behaviors of all projectiles
 #[allow(clippy::too_many_arguments)]
 pub fn projectile_execute_behavior_system(
     mut commands: Commands,
-    mut projectile_query: Query<(Entity, &Transform, &mut ProjectileComponent)>,
+    mut projectile_query: Query<(
+        Entity,
+        &mut Transform,
+        &mut Velocity,
+        &mut ProjectileComponent,
+    )>,
     player_query: Query<(Entity, &PlayerComponent)>,
     mob_query: Query<(Entity, &MobComponent)>,
     mob_segment_query: Query<(Entity, &MobSegmentComponent)>,
@@ -42,21 +65,62 @@ pub fn projectile_execute_behavior_system(
     time: Res<Time>,
     mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
     mut damage_dealt_event_writer: EventWriter<DamageDealtEvent>,
+    mut apply_status_effect_event_writer: EventWriter<ApplyStatusEffectEvent>,
+    mut projectile_hit_event_writer: EventWriter<ProjectileHitEvent>,
+    outgoing_damage_query: Query<&PlayerOutgoingDamageComponent>,
+    collider_query: Query<(&Transform, &Collider), Without<ProjectileComponent>>,
 ) {
     // Put all collision events in a vec so they can be read more than once
     let collision_events_vec: Vec<_> = collision_events.read().collect();
     // iterate through all projectiles
-    for (projectile_entity, projectile_transform, mut projectile_component) in
-        projectile_query.iter_mut()
+    for (
+        projectile_entity,
+        mut projectile_transform,
+        mut projectile_velocity,
+        mut projectile_component,
+    ) in projectile_query.iter_mut()
     {
+        // ignore collisions with targets that the projectile has already hit
+        let projectile_collision_events: Vec<&SortedCollisionEvent> = collision_events_vec
+            .iter()
+            .filter(|collision_event| {
+                !hostile_impact_target(projectile_entity, collision_event)
+                    .is_some_and(|target| projectile_component.hits.contains(&target))
+            })
+            .copied()
+            .collect();
+
+        let had_hit = !projectile_component.hits.is_empty();
+        let survives_impact = resolve_impacts(
+            projectile_entity,
+            &mut projectile_component,
+            &mut projectile_transform,
+            &mut projectile_velocity,
+            &projectile_collision_events,
+            &collider_query,
+        );
+        if !had_hit && !projectile_component.hits.is_empty() {
+            projectile_hit_event_writer.send(ProjectileHitEvent {
+                projectile: projectile_entity,
+                source: projectile_component.source,
+            });
+        }
+        let projectile_transform = &*projectile_transform;
+
         let projectile_type = projectile_component.projectile_type;
+        let projectile_damage_source = ProjectileDamageSource {
+            source: projectile_component.source,
+            damage_type: projectile_component.damage_type,
+            outgoing_damage: outgoing_damage_query.get(projectile_component.source).ok(),
+        };
         for behavior in projectile_component.behaviors.clone() {
             match behavior {
                 ProjectileBehavior::ExplodeOnIntersection => explode_on_intersection(
                     &mut commands,
                     projectile_entity,
                     projectile_transform,
-                    &collision_events_vec,
+                    &projectile_collision_events,
+                    !survives_impact,
                     &mut spawn_effect_event_writer,
                     &mut sound_effect_event_writer,
                 ),
@@ -64,13 +128,15 @@ pub fn projectile_execute_behavior_system(
                     &mut commands,
                     projectile_entity,
                     projectile_transform,
-                    &collision_events_vec,
+                    &projectile_collision_events,
+                    !survives_impact,
                     &mut spawn_effect_event_writer,
                     &mut sound_effect_event_writer,
                 ),
                 ProjectileBehavior::DealDamageOnContact => deal_damage_on_contact(
                     projectile_entity,
-                    &collision_events_vec,
+                    &projectile_damage_source,
+                    &projectile_collision_events,
                     &player_query,
                     &mob_query,
                     &mob_segment_query,
@@ -79,13 +145,24 @@ pub fn projectile_execute_behavior_system(
                 ),
                 ProjectileBehavior::DealDamageOnIntersection => deal_damage_on_intersection(
                     projectile_entity,
-                    &collision_events_vec,
+                    &projectile_damage_source,
+                    &projectile_collision_events,
                     &player_query,
                     &mob_query,
                     &mob_segment_query,
                     &mut sound_effect_event_writer,
                     &mut damage_dealt_event_writer,
                 ),
+                ProjectileBehavior::ApplyStatusEffectsOnImpact(status_effects) => {
+                    apply_status_effects_on_impact(
+                        projectile_entity,
+                        &status_effects,
+                        &projectile_collision_events,
+                        &mut apply_status_effect_event_writer,
+                    )
+                }
+                // impacts are resolved before other behaviors are performed
+                ProjectileBehavior::Pierce { .. } | ProjectileBehavior::Ricochet { .. } => {}
                 ProjectileBehavior::TimedDespawn { despawn_time } => {
                     projectile_component.time_alive += time.delta_seconds();
                     if projectile_component.time_alive > despawn_time {
@@ -151,9 +228,42 @@ pub fn projectile_execute_behavior_system(
     }
 }
 
+/// Information about where a projectile's damage came from, used to create `DamageDealtEvent`s
+struct ProjectileDamageSource<'a> {
+    /// Entity that fired the projectile
+    source: Entity,
+    /// Type of damage dealt by the projectile
+    damage_type: DamageType,
+    /// Damage stats of the source, if it was fired by a player
+    outgoing_damage: Option<&'a PlayerOutgoingDamageComponent>,
+}
+
+impl ProjectileDamageSource<'_> {
+    /// Create a damage event, rolling for a critical hit if the projectile was fired by a player
+    fn damage_dealt_event(&self, damage: usize, target: Entity) -> DamageDealtEvent {
+        let is_critical = self.outgoing_damage.is_some_and(|outgoing_damage| {
+            thread_rng().gen_bool(outgoing_damage.critical_chance.clamp(0.0, 1.0) as f64)
+        });
+
+        DamageDealtEvent {
+            damage: match self.outgoing_damage {
+                Some(outgoing_damage) if is_critical => {
+                    (damage as f32 * outgoing_damage.critical_multiplier).round() as usize
+                }
+                _ => damage,
+            },
+            target,
+            source: Some(self.source),
+            damage_type: self.damage_type,
+            is_critical,
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn deal_damage_on_contact(
     projectile: Entity,
+    projectile_damage_source: &ProjectileDamageSource,
     collision_events: &[&SortedCollisionEvent],
     player_query: &Query<(Entity, &PlayerComponent)>,
     mob_query: &Query<(Entity, &MobComponent)>,
@@ -177,10 +287,10 @@ fn deal_damage_on_contact(
                         sound_effect_type: SoundEffectType::PlayerHit,
                     });
                     if player_query.contains(*player_entity) && *projectile_damage > 0 {
-                        damage_dealt_event_writer.send(DamageDealtEvent {
-                            damage: *projectile_damage,
-                            target: *player_entity,
-                        });
+                        damage_dealt_event_writer.send(
+                            projectile_damage_source
+                                .damage_dealt_event(*projectile_damage, *player_entity),
+                        );
                     }
                 }
             }
@@ -204,10 +314,10 @@ fn deal_damage_on_contact(
                         sound_effect_type: SoundEffectType::BulletDing,
                     });
                     if mob_query.contains(*mob_entity) && *projectile_damage > 0 {
-                        damage_dealt_event_writer.send(DamageDealtEvent {
-                            damage: *projectile_damage,
-                            target: *mob_entity,
-                        });
+                        damage_dealt_event_writer.send(
+                            projectile_damage_source
+                                .damage_dealt_event(*projectile_damage, *mob_entity),
+                        );
                     }
                 }
             }
@@ -230,10 +340,10 @@ fn deal_damage_on_contact(
                         sound_effect_type: SoundEffectType::BulletDing,
                     });
                     if mob_segment_query.contains(*mob_segment_entity) && *projectile_damage > 0 {
-                        damage_dealt_event_writer.send(DamageDealtEvent {
-                            damage: *projectile_damage,
-                            target: *mob_segment_entity,
-                        });
+                        damage_dealt_event_writer.send(
+                            projectile_damage_source
+                                .damage_dealt_event(*projectile_damage, *mob_segment_entity),
+                        );
                     }
                 }
             }
@@ -245,6 +355,7 @@ fn deal_damage_on_contact(
 #[allow(clippy::too_many_arguments)]
 fn deal_damage_on_intersection(
     projectile: Entity,
+    projectile_damage_source: &ProjectileDamageSource,
     collision_events: &[&SortedCollisionEvent],
     player_query: &Query<(Entity, &PlayerComponent)>,
     mob_query: &Query<(Entity, &MobComponent)>,
@@ -266,10 +377,10 @@ fn deal_damage_on_intersection(
                     && *projectile_damage > 0
                 {
                     // deal damage to player
-                    damage_dealt_event_writer.send(DamageDealtEvent {
-                        damage: *projectile_damage,
-                        target: *player_entity,
-                    });
+                    damage_dealt_event_writer.send(
+                        projectile_damage_source
+                            .damage_dealt_event(*projectile_damage, *player_entity),
+                    );
                     sound_effect_event_writer.send(PlaySoundEffectEvent {
                         sound_effect_type: SoundEffectType::PlayerHit,
                     });
@@ -290,10 +401,10 @@ fn deal_damage_on_intersection(
                     && *projectile_damage > 0
                 {
                     // deal damage to mob
-                    damage_dealt_event_writer.send(DamageDealtEvent {
-                        damage: *projectile_damage,
-                        target: *mob_entity,
-                    });
+                    damage_dealt_event_writer.send(
+                        projectile_damage_source
+                            .damage_dealt_event(*projectile_damage, *mob_entity),
+                    );
                 }
             }
             SortedCollisionEvent::MobSegmentToProjectileIntersection {
@@ -309,10 +420,10 @@ fn deal_damage_on_intersection(
                     && *projectile_damage > 0
                 {
                     // deal damage to mob
-                    damage_dealt_event_writer.send(DamageDealtEvent {
-                        damage: *projectile_damage,
-                        target: *mob_segment_entity,
-                    });
+                    damage_dealt_event_writer.send(
+                        projectile_damage_source
+                            .damage_dealt_event(*projectile_damage, *mob_segment_entity),
+                    );
                 }
             }
             _ => {}
@@ -320,12 +431,197 @@ fn deal_damage_on_intersection(
     }
 }
 
+/// Apply status effects to the players and mobs hit by a projectile of a different faction
+fn apply_status_effects_on_impact(
+    projectile: Entity,
+    status_effects: &[StatusEffectData],
+    collision_events: &[&SortedCollisionEvent],
+    apply_status_effect_event_writer: &mut EventWriter<ApplyStatusEffectEvent>,
+) {
+    for target in collision_events
+        .iter()
+        .filter_map(|collision_event| hostile_impact_target(projectile, collision_event))
+    {
+        for status_effect in status_effects {
+            apply_status_effect_event_writer.send(ApplyStatusEffectEvent {
+                target,
+                status_effect: status_effect.clone(),
+            });
+        }
+    }
+}
+
+/// Track new hits of a projectile, and pierce or ricochet off of them if able
+/// Returns true if the projectile survives all of its impacts
+fn resolve_impacts(
+    projectile: Entity,
+    projectile_component: &mut ProjectileComponent,
+    transform: &mut Mut<Transform>,
+    velocity: &mut Mut<Velocity>,
+    collision_events: &[&SortedCollisionEvent],
+    collider_query: &Query<(&Transform, &Collider), Without<ProjectileComponent>>,
+) -> bool {
+    let pierce = projectile_component
+        .behaviors
+        .iter()
+        .find_map(|behavior| match behavior {
+            ProjectileBehavior::Pierce {
+                count,
+                damage_multiplier,
+            } => Some((*count, *damage_multiplier)),
+            _ => None,
+        });
+    let ricochet = projectile_component
+        .behaviors
+        .iter()
+        .find_map(|behavior| match behavior {
+            ProjectileBehavior::Ricochet { count } => Some(*count),
+            _ => None,
+        });
+
+    // projectiles that can't pierce or ricochet are always destroyed on impact
+    let mut survives = pierce.is_some() || ricochet.is_some();
+
+    for collision_event in collision_events.iter() {
+        if let Some(target) = hostile_impact_target(projectile, collision_event) {
+            if projectile_component.hits.contains(&target) {
+                continue;
+            }
+            projectile_component.hits.push(target);
+
+            match pierce {
+                Some((count, damage_multiplier)) if projectile_component.pierces < count => {
+                    projectile_component.pierces += 1;
+                    projectile_component.damage =
+                        (projectile_component.damage as f32 * damage_multiplier).round() as usize;
+                }
+                _ => match ricochet {
+                    Some(count) if projectile_component.ricochets < count => {
+                        projectile_component.ricochets += 1;
+                        ricochet_off(target, transform, velocity, collider_query);
+                    }
+                    _ => survives = false,
+                },
+            }
+        } else if let SortedCollisionEvent::ProjectileToBarrierContact {
+            projectile_entity,
+            barrier_entity,
+        }
+        | SortedCollisionEvent::ProjectileToBarrierIntersection {
+            projectile_entity,
+            barrier_entity,
+        } = collision_event
+        {
+            if projectile == *projectile_entity
+                && ricochet.is_some_and(|count| projectile_component.ricochets < count)
+            {
+                projectile_component.ricochets += 1;
+                ricochet_off(*barrier_entity, transform, velocity, collider_query);
+            }
+        }
+    }
+
+    survives
+}
+
+/// Reflect the velocity of a projectile off of the surface of an entity's collider
+fn ricochet_off(
+    entity: Entity,
+    transform: &mut Mut<Transform>,
+    velocity: &mut Mut<Velocity>,
+    collider_query: &Query<(&Transform, &Collider), Without<ProjectileComponent>>,
+) {
+    let Ok((entity_transform, collider)) = collider_query.get(entity) else {
+        return;
+    };
+
+    let offset = transform.translation.xy() - entity_transform.translation.xy();
+
+    // use the nearest face of box colliders, otherwise bounce directly away from the center
+    let normal = match collider.as_cuboid() {
+        Some(cuboid) => {
+            let half_extents = cuboid.half_extents() * entity_transform.scale.xy();
+            if (offset.x / half_extents.x).abs() > (offset.y / half_extents.y).abs() {
+                Vec2::new(offset.x.signum(), 0.0)
+            } else {
+                Vec2::new(0.0, offset.y.signum())
+            }
+        }
+        None => offset.normalize_or_zero(),
+    };
+
+    // only reflect if the projectile is still moving into the surface, since physics may
+    // have already bounced solid projectiles
+    let normal_speed = velocity.linvel.dot(normal);
+    if normal_speed < 0.0 {
+        velocity.linvel -= 2.0 * normal_speed * normal;
+        transform.rotation = Quat::from_rotation_z(velocity.linvel.y.atan2(velocity.linvel.x));
+    }
+}
+
+/// Returns the player or mob hit by the projectile in the collision, if it is of a different faction
+fn hostile_impact_target(
+    projectile: Entity,
+    collision_event: &SortedCollisionEvent,
+) -> Option<Entity> {
+    match collision_event {
+        SortedCollisionEvent::PlayerToProjectileContact {
+            player_entity,
+            projectile_entity,
+            projectile_faction,
+            projectile_damage: _,
+        }
+        | SortedCollisionEvent::PlayerToProjectileIntersection {
+            player_entity,
+            projectile_entity,
+            projectile_faction,
+            projectile_damage: _,
+        } => (projectile == *projectile_entity
+            && matches!(*projectile_faction, Faction::Neutral | Faction::Enemy))
+        .then_some(*player_entity),
+        SortedCollisionEvent::MobToProjectileContact {
+            mob_entity,
+            projectile_entity,
+            mob_faction,
+            projectile_faction,
+            projectile_damage: _,
+            projectile_source: _,
+        }
+        | SortedCollisionEvent::MobToProjectileIntersection {
+            mob_entity,
+            projectile_entity,
+            mob_faction,
+            projectile_faction,
+            projectile_damage: _,
+            projectile_source: _,
+        } => (projectile == *projectile_entity && mob_faction != projectile_faction)
+            .then_some(*mob_entity),
+        SortedCollisionEvent::MobSegmentToProjectileContact {
+            mob_segment_entity,
+            projectile_entity,
+            mob_segment_faction,
+            projectile_faction,
+            projectile_damage: _,
+        }
+        | SortedCollisionEvent::MobSegmentToProjectileIntersection {
+            mob_segment_entity,
+            projectile_entity,
+            mob_segment_faction,
+            projectile_faction,
+            projectile_damage: _,
+        } => (projectile == *projectile_entity && mob_segment_faction != projectile_faction)
+            .then_some(*mob_segment_entity),
+        _ => None,
+    }
+}
+
 /// Explode a specific projectile on impact
 fn explode_on_intersection(
     commands: &mut Commands,
     projectile: Entity,
     transform: &Transform,
     collision_events: &[&SortedCollisionEvent],
+    despawn: bool,
     spawn_effect_event_writer: &mut EventWriter<SpawnEffectEvent>,
     sound_effect_event_writer: &mut EventWriter<PlaySoundEffectEvent>,
 ) {
@@ -351,8 +647,10 @@ fn explode_on_intersection(
                         ..default()
                     });
 
-                    // despawn blast
-                    commands.entity(projectile).despawn_recursive();
+                    // despawn blast, unless it survives the impact by piercing or ricocheting
+                    if despawn {
+                        commands.entity(projectile).despawn_recursive();
+                    }
                 }
             }
 
@@ -402,8 +700,10 @@ fn explode_on_intersection(
                         Faction::Neutral => {}
                     }
 
-                    // despawn blast
-                    commands.entity(projectile).despawn_recursive();
+                    // despawn blast, unless it survives the impact by piercing or ricocheting
+                    if despawn {
+                        commands.entity(projectile).despawn_recursive();
+                    }
                 }
             }
             SortedCollisionEvent::MobSegmentToProjectileIntersection {
@@ -451,8 +751,10 @@ fn explode_on_intersection(
                         Faction::Neutral => {}
                     }
 
-                    // despawn blast
-                    commands.entity(projectile).despawn_recursive();
+                    // despawn blast, unless it survives the impact by piercing or ricocheting
+                    if despawn {
+                        commands.entity(projectile).despawn_recursive();
+                    }
                 }
             }
             _ => {}
@@ -466,6 +768,7 @@ fn explode_on_contact(
     projectile: Entity,
     transform: &Transform,
     collision_events: &[&SortedCollisionEvent],
+    despawn: bool,
     spawn_effect_event_writer: &mut EventWriter<SpawnEffectEvent>,
     sound_effect_event_writer: &mut EventWriter<PlaySoundEffectEvent>,
 ) {
@@ -491,8 +794,10 @@ fn explode_on_contact(
                         ..default()
                     });
 
-                    // despawn blast
-                    commands.entity(projectile).despawn_recursive();
+                    // despawn blast, unless it survives the impact by piercing or ricocheting
+                    if despawn {
+                        commands.entity(projectile).despawn_recursive();
+                    }
 
                     continue;
                 }
@@ -538,8 +843,10 @@ fn explode_on_contact(
                         Faction::Neutral => {}
                     }
 
-                    // despawn blast
-                    commands.entity(projectile).despawn_recursive();
+                    // despawn blast, unless it survives the impact by piercing or ricocheting
+                    if despawn {
+                        commands.entity(projectile).despawn_recursive();
+                    }
                     continue;
                 }
             }
@@ -583,8 +890,10 @@ fn explode_on_contact(
                         Faction::Neutral => {}
                     }
 
-                    // despawn blast
-                    commands.entity(projectile).despawn_recursive();
+                    // despawn blast, unless it survives the impact by piercing or ricocheting
+                    if despawn {
+                        commands.entity(projectile).despawn_recursive();
+                    }
                     continue;
                 }
             }
@@ -632,3 +941,302 @@ fn explode_on_contact(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use bevy::{
+        ecs::system::RunSystemOnce,
+        math::{Vec2, Vec3},
+        prelude::{Entity, Query, Transform, With, Without, World},
+    };
+    use bevy_rapier2d::prelude::{Collider, Velocity};
+    use thetawave_interface::{
+        health::DamageType,
+        spawnable::{Faction, ProjectileType},
+    };
+
+    use super::{resolve_impacts, ricochet_off, ProjectileBehavior};
+    use crate::{collision::SortedCollisionEvent, spawnable::projectile::ProjectileComponent};
+
+    /// Spawn an ally projectile at the origin moving up
+    fn spawn_projectile(world: &mut World, behaviors: Vec<ProjectileBehavior>) -> Entity {
+        let source = world.spawn_empty().id();
+        world
+            .spawn((
+                Transform::default(),
+                Velocity::linear(Vec2::new(0.0, 100.0)),
+                ProjectileComponent {
+                    projectile_type: ProjectileType::Blast(Faction::Ally),
+                    behaviors,
+                    damage: 10,
+                    damage_type: DamageType::Energy,
+                    time_alive: 0.0,
+                    source,
+                    hits: vec![],
+                    pierces: 0,
+                    ricochets: 0,
+                },
+            ))
+            .id()
+    }
+
+    /// Spawn an entity with a collider that projectiles can hit or ricochet off of
+    fn spawn_target(world: &mut World, position: Vec2, collider: Collider) -> Entity {
+        world
+            .spawn((Transform::from_translation(position.extend(0.0)), collider))
+            .id()
+    }
+
+    fn hit_mob(projectile: Entity, mob: Entity) -> SortedCollisionEvent {
+        SortedCollisionEvent::MobToProjectileIntersection {
+            projectile_source: Entity::PLACEHOLDER,
+            mob_entity: mob,
+            projectile_entity: projectile,
+            mob_faction: Faction::Enemy,
+            projectile_faction: Faction::Ally,
+            projectile_damage: 10,
+        }
+    }
+
+    fn resolve(
+        world: &mut World,
+        projectile: Entity,
+        collision_events: Vec<SortedCollisionEvent>,
+    ) -> bool {
+        world.run_system_once(
+            move |mut projectile_query: Query<(
+                &mut Transform,
+                &mut Velocity,
+                &mut ProjectileComponent,
+            )>,
+                  collider_query: Query<(&Transform, &Collider), Without<ProjectileComponent>>| {
+                let (mut transform, mut velocity, mut projectile_component) =
+                    projectile_query.get_mut(projectile).unwrap();
+                let collision_events: Vec<_> = collision_events.iter().collect();
+                resolve_impacts(
+                    projectile,
+                    &mut projectile_component,
+                    &mut transform,
+                    &mut velocity,
+                    &collision_events,
+                    &collider_query,
+                )
+            },
+        )
+    }
+
+    fn bounce(world: &mut World, projectile: Entity, entity: Entity) {
+        world.run_system_once(
+            move |mut projectile_query: Query<
+                (&mut Transform, &mut Velocity),
+                With<ProjectileComponent>,
+            >,
+                  collider_query: Query<(&Transform, &Collider), Without<ProjectileComponent>>| {
+                let (mut transform, mut velocity) = projectile_query.get_mut(projectile).unwrap();
+                ricochet_off(entity, &mut transform, &mut velocity, &collider_query);
+            },
+        );
+    }
+
+    #[test]
+    fn test_projectile_is_destroyed_on_impact() {
+        let mut world = World::new();
+        let projectile = spawn_projectile(&mut world, vec![]);
+        let mob = spawn_target(&mut world, Vec2::new(0.0, 10.0), Collider::ball(5.0));
+
+        assert!(!resolve(
+            &mut world,
+            projectile,
+            vec![hit_mob(projectile, mob)]
+        ));
+        assert_eq!(
+            world.get::<ProjectileComponent>(projectile).unwrap().hits,
+            vec![mob]
+        );
+    }
+
+    #[test]
+    fn test_collisions_of_other_projectiles_are_ignored() {
+        let mut world = World::new();
+        let projectile = spawn_projectile(
+            &mut world,
+            vec![ProjectileBehavior::Pierce {
+                count: 1,
+                damage_multiplier: 0.5,
+            }],
+        );
+        let other_projectile = spawn_projectile(&mut world, vec![]);
+        let mob = spawn_target(&mut world, Vec2::new(0.0, 10.0), Collider::ball(5.0));
+
+        assert!(resolve(
+            &mut world,
+            projectile,
+            vec![hit_mob(other_projectile, mob)]
+        ));
+        let projectile_component = world.get::<ProjectileComponent>(projectile).unwrap();
+        assert!(projectile_component.hits.is_empty());
+        assert_eq!(projectile_component.pierces, 0);
+        assert_eq!(projectile_component.damage, 10);
+    }
+
+    #[test]
+    fn test_pierce_passes_through_up_to_count_targets() {
+        let mut world = World::new();
+        let projectile = spawn_projectile(
+            &mut world,
+            vec![ProjectileBehavior::Pierce {
+                count: 1,
+                damage_multiplier: 0.5,
+            }],
+        );
+        let mob_1 = spawn_target(&mut world, Vec2::new(0.0, 10.0), Collider::ball(5.0));
+        let mob_2 = spawn_target(&mut world, Vec2::new(0.0, 30.0), Collider::ball(5.0));
+
+        assert!(resolve(
+            &mut world,
+            projectile,
+            vec![hit_mob(projectile, mob_1)]
+        ));
+        let projectile_component = world.get::<ProjectileComponent>(projectile).unwrap();
+        assert_eq!(projectile_component.pierces, 1);
+        assert_eq!(projectile_component.damage, 5);
+
+        // hitting the same target again doesn't use up a pierce
+        assert!(resolve(
+            &mut world,
+            projectile,
+            vec![hit_mob(projectile, mob_1)]
+        ));
+
+        assert!(!resolve(
+            &mut world,
+            projectile,
+            vec![hit_mob(projectile, mob_2)]
+        ));
+        assert_eq!(
+            world.get::<ProjectileComponent>(projectile).unwrap().hits,
+            vec![mob_1, mob_2]
+        );
+    }
+
+    #[test]
+    fn test_ricochet_after_pierces_are_used() {
+        let mut world = World::new();
+        let projectile = spawn_projectile(
+            &mut world,
+            vec![
+                ProjectileBehavior::Pierce {
+                    count: 1,
+                    damage_multiplier: 1.0,
+                },
+                ProjectileBehavior::Ricochet { count: 1 },
+            ],
+        );
+        let mob_1 = spawn_target(&mut world, Vec2::new(0.0, 10.0), Collider::ball(5.0));
+        let mob_2 = spawn_target(&mut world, Vec2::new(0.0, 30.0), Collider::ball(5.0));
+
+        assert!(resolve(
+            &mut world,
+            projectile,
+            vec![hit_mob(projectile, mob_1), hit_mob(projectile, mob_2)]
+        ));
+        let projectile_component = world.get::<ProjectileComponent>(projectile).unwrap();
+        assert_eq!(projectile_component.pierces, 1);
+        assert_eq!(projectile_component.ricochets, 1);
+        assert_eq!(
+            world.get::<Velocity>(projectile).unwrap().linvel,
+            Vec2::new(0.0, -100.0)
+        );
+    }
+
+    #[test]
+    fn test_ricochet_off_barrier_is_not_a_hit() {
+        let mut world = World::new();
+        let projectile =
+            spawn_projectile(&mut world, vec![ProjectileBehavior::Ricochet { count: 1 }]);
+        let barrier = spawn_target(
+            &mut world,
+            Vec2::new(0.0, 20.0),
+            Collider::cuboid(100.0, 5.0),
+        );
+        let barrier_contact = || SortedCollisionEvent::ProjectileToBarrierContact {
+            projectile_entity: projectile,
+            barrier_entity: barrier,
+        };
+
+        assert!(resolve(&mut world, projectile, vec![barrier_contact()]));
+        let projectile_component = world.get::<ProjectileComponent>(projectile).unwrap();
+        assert!(projectile_component.hits.is_empty());
+        assert_eq!(projectile_component.ricochets, 1);
+        assert_eq!(
+            world.get::<Velocity>(projectile).unwrap().linvel,
+            Vec2::new(0.0, -100.0)
+        );
+
+        // without ricochets left, barriers are passed through
+        world.get_mut::<Velocity>(projectile).unwrap().linvel = Vec2::new(0.0, 100.0);
+        assert!(resolve(&mut world, projectile, vec![barrier_contact()]));
+        assert_eq!(
+            world.get::<Velocity>(projectile).unwrap().linvel,
+            Vec2::new(0.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn test_ricochet_off_nearest_cuboid_face() {
+        let mut world = World::new();
+        let projectile = spawn_projectile(&mut world, vec![]);
+        world.get_mut::<Velocity>(projectile).unwrap().linvel = Vec2::new(100.0, 50.0);
+        // the projectile is to the left of a tall box, so it bounces off of the left face
+        let wall = spawn_target(
+            &mut world,
+            Vec2::new(10.0, 0.0),
+            Collider::cuboid(5.0, 50.0),
+        );
+
+        bounce(&mut world, projectile, wall);
+
+        assert_eq!(
+            world.get::<Velocity>(projectile).unwrap().linvel,
+            Vec2::new(-100.0, 50.0)
+        );
+        let rotation = world.get::<Transform>(projectile).unwrap().rotation;
+        assert!((rotation * Vec3::X)
+            .truncate()
+            .abs_diff_eq(Vec2::new(-100.0, 50.0).normalize(), 1e-5));
+    }
+
+    #[test]
+    fn test_ricochet_off_ball_away_from_center() {
+        let mut world = World::new();
+        let projectile = spawn_projectile(&mut world, vec![]);
+        world.get_mut::<Velocity>(projectile).unwrap().linvel = Vec2::new(100.0, 0.0);
+        let ball = spawn_target(&mut world, Vec2::new(10.0, 0.0), Collider::ball(5.0));
+
+        bounce(&mut world, projectile, ball);
+
+        assert_eq!(
+            world.get::<Velocity>(projectile).unwrap().linvel,
+            Vec2::new(-100.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_ricochet_off_ignores_projectiles_moving_away() {
+        let mut world = World::new();
+        let projectile = spawn_projectile(&mut world, vec![]);
+        world.get_mut::<Velocity>(projectile).unwrap().linvel = Vec2::new(-100.0, 0.0);
+        let ball = spawn_target(&mut world, Vec2::new(10.0, 0.0), Collider::ball(5.0));
+
+        bounce(&mut world, projectile, ball);
+
+        assert_eq!(
+            world.get::<Velocity>(projectile).unwrap().linvel,
+            Vec2::new(-100.0, 0.0)
+        );
+        assert_eq!(
+            world.get::<Transform>(projectile).unwrap().rotation,
+            Transform::default().rotation
+        );
+    }
+}
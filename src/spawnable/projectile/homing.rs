@@ -0,0 +1,321 @@
+use bevy::{
+    math::Vec2,
+    prelude::{Component, Entity, Quat, Query, Res, Transform, Vec3Swizzles},
+    time::{Time, Timer, TimerMode},
+};
+use bevy_rapier2d::prelude::Velocity;
+use thetawave_interface::weapon::HomingData;
+
+use crate::spawnable::targeting::{hostile_targets, select_target, TargetsQuery};
+
+use super::ProjectileComponent;
+
+/// Steers a projectile toward a hostile target
+#[derive(Component)]
+pub struct ProjectileHomingComponent {
+    /// Turn rate, range, and retargeting data
+    pub data: HomingData,
+    /// Entity currently being homed in on
+    pub target: Option<Entity>,
+    /// Tracks time until the projectile starts homing
+    pub delay_timer: Timer,
+    /// Tracks time until the projectile searches for a closer target
+    pub retarget_timer: Option<Timer>,
+}
+
+impl From<&HomingData> for ProjectileHomingComponent {
+    fn from(data: &HomingData) -> Self {
+        ProjectileHomingComponent {
+            data: data.clone(),
+            target: None,
+            delay_timer: Timer::from_seconds(data.delay, TimerMode::Once),
+            retarget_timer: data
+                .retarget_period
+                .map(|period| Timer::from_seconds(period, TimerMode::Repeating)),
+        }
+    }
+}
+
+/// Turn homing projectiles toward a target of a hostile faction, chosen by the homing policy
+pub fn projectile_homing_system(
+    mut projectile_query: Query<(
+        &ProjectileComponent,
+        &mut ProjectileHomingComponent,
+        &mut Transform,
+        &mut Velocity,
+    )>,
+    targets_query: TargetsQuery,
+    time: Res<Time>,
+) {
+    let targets = targets_query.targets();
+
+    for (projectile, mut homing, mut transform, mut velocity) in projectile_query.iter_mut() {
+        homing.delay_timer.tick(time.delta());
+        if !homing.delay_timer.finished() {
+            continue;
+        }
+
+        let faction = projectile.projectile_type.get_faction();
+        let position = transform.translation.xy();
+
+        // drop the target if it no longer exists or has left the acquisition range
+        let current_target = homing.target.and_then(|target| {
+            targets
+                .iter()
+                .find(|candidate| candidate.entity == target)
+                .filter(|candidate| {
+                    position.distance(candidate.position) <= homing.data.acquisition_range
+                })
+                .map(|candidate| candidate.position)
+        });
+
+        let retarget = homing
+            .retarget_timer
+            .as_mut()
+            .is_some_and(|timer| timer.tick(time.delta()).just_finished());
+
+        let target_position = if current_target.is_none() || retarget {
+            let new_target = select_target(
+                homing.data.policy,
+                position,
+                hostile_targets(&targets, faction).filter(|candidate| {
+                    position.distance(candidate.position) <= homing.data.acquisition_range
+                }),
+            );
+
+            homing.target = new_target.map(|candidate| candidate.entity);
+            new_target.map(|candidate| candidate.position)
+        } else {
+            current_target
+        };
+
+        let Some(target_position) = target_position else {
+            continue;
+        };
+
+        let speed = velocity.linvel.length();
+        if speed == 0.0 {
+            continue;
+        }
+
+        // turn toward the target, limited by the turn rate, while keeping the same speed
+        let current_angle = velocity.linvel.y.atan2(velocity.linvel.x);
+        let angle_to_target = velocity.linvel.angle_between(target_position - position);
+        let max_turn = homing.data.turn_rate * time.delta_seconds();
+        let new_angle = current_angle + angle_to_target.clamp(-max_turn, max_turn);
+
+        velocity.linvel = Vec2::from_angle(new_angle) * speed;
+        transform.rotation = Quat::from_rotation_z(new_angle);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use bevy::{
+        app::{App, Update},
+        math::{Vec2, Vec3},
+        prelude::{Entity, Transform},
+        time::TimeUpdateStrategy,
+        MinimalPlugins,
+    };
+    use bevy_rapier2d::prelude::Velocity;
+    use thetawave_interface::{
+        health::{DamageType, HealthComponent},
+        player::PlayerComponent,
+        spawnable::{Faction, ProjectileType},
+        targeting::TargetingPolicy,
+        weapon::HomingData,
+    };
+
+    use super::{projectile_homing_system, ProjectileHomingComponent};
+    use crate::spawnable::ProjectileComponent;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+                0.1,
+            )))
+            .add_systems(Update, projectile_homing_system);
+        app
+    }
+
+    fn homing_data(retarget_period: Option<f32>) -> HomingData {
+        HomingData {
+            policy: TargetingPolicy::Nearest,
+            turn_rate: 1.0,
+            acquisition_range: 500.0,
+            retarget_period,
+            delay: 0.0,
+        }
+    }
+
+    /// Spawn an enemy projectile at the origin moving right at 100 units per second
+    fn spawn_projectile(app: &mut App, homing_data: HomingData) -> Entity {
+        let source = app.world_mut().spawn_empty().id();
+        app.world_mut()
+            .spawn((
+                ProjectileComponent {
+                    projectile_type: ProjectileType::Blast(Faction::Enemy),
+                    behaviors: vec![],
+                    damage: 10,
+                    damage_type: DamageType::Energy,
+                    time_alive: 0.0,
+                    source,
+                    hits: vec![],
+                    pierces: 0,
+                    ricochets: 0,
+                },
+                ProjectileHomingComponent::from(&homing_data),
+                Transform::default(),
+                Velocity::linear(Vec2::new(100.0, 0.0)),
+            ))
+            .id()
+    }
+
+    fn spawn_player(app: &mut App, position: Vec2) -> Entity {
+        app.world_mut()
+            .spawn((
+                PlayerComponent,
+                Transform::from_translation(position.extend(0.0)),
+                HealthComponent::new(100, 0, 1.0),
+            ))
+            .id()
+    }
+
+    fn get_angle(app: &App, projectile: Entity) -> f32 {
+        let linvel = app.world().get::<Velocity>(projectile).unwrap().linvel;
+        linvel.y.atan2(linvel.x)
+    }
+
+    fn get_target(app: &App, projectile: Entity) -> Option<Entity> {
+        app.world()
+            .get::<ProjectileHomingComponent>(projectile)
+            .unwrap()
+            .target
+    }
+
+    #[test]
+    fn test_turn_is_limited_by_turn_rate() {
+        let mut app = app();
+        let projectile = spawn_projectile(&mut app, homing_data(None));
+        let player = spawn_player(&mut app, Vec2::new(0.0, 100.0));
+
+        // the first update has no elapsed time
+        app.update();
+        assert_eq!(get_target(&app, projectile), Some(player));
+        assert!(get_angle(&app, projectile).abs() < 1e-5);
+
+        app.update();
+        assert!((get_angle(&app, projectile) - 0.1).abs() < 1e-5);
+
+        // speed and rotation follow the turned velocity
+        let linvel = app.world().get::<Velocity>(projectile).unwrap().linvel;
+        assert!((linvel.length() - 100.0).abs() < 1e-3);
+        let rotation = app.world().get::<Transform>(projectile).unwrap().rotation;
+        assert!((rotation * Vec3::X)
+            .truncate()
+            .abs_diff_eq(linvel / 100.0, 1e-5));
+    }
+
+    #[test]
+    fn test_turn_does_not_overshoot_target() {
+        let mut app = app();
+        let projectile = spawn_projectile(&mut app, homing_data(None));
+        spawn_player(&mut app, Vec2::from_angle(0.05) * 100.0);
+
+        app.update();
+        app.update();
+
+        assert!((get_angle(&app, projectile) - 0.05).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_homing_waits_for_delay() {
+        let mut app = app();
+        let projectile = spawn_projectile(
+            &mut app,
+            HomingData {
+                delay: 0.15,
+                ..homing_data(None)
+            },
+        );
+        spawn_player(&mut app, Vec2::new(0.0, 100.0));
+
+        app.update();
+        app.update();
+        assert!(get_angle(&app, projectile).abs() < 1e-5);
+
+        app.update();
+        assert!(get_angle(&app, projectile) > 0.0);
+    }
+
+    #[test]
+    fn test_targets_out_of_range_are_ignored() {
+        let mut app = app();
+        let projectile = spawn_projectile(&mut app, homing_data(None));
+        let player = spawn_player(&mut app, Vec2::new(0.0, 600.0));
+
+        app.update();
+        assert_eq!(get_target(&app, projectile), None);
+
+        // targets that leave the acquisition range are dropped
+        app.world_mut()
+            .get_mut::<Transform>(player)
+            .unwrap()
+            .translation = Vec3::new(0.0, 100.0, 0.0);
+        app.update();
+        assert_eq!(get_target(&app, projectile), Some(player));
+
+        app.world_mut()
+            .get_mut::<Transform>(player)
+            .unwrap()
+            .translation = Vec3::new(0.0, 600.0, 0.0);
+        app.update();
+        assert_eq!(get_target(&app, projectile), None);
+    }
+
+    #[test]
+    fn test_target_is_kept_without_retarget_period() {
+        let mut app = app();
+        let projectile = spawn_projectile(&mut app, homing_data(None));
+        let player_1 = spawn_player(&mut app, Vec2::new(0.0, 100.0));
+        let player_2 = spawn_player(&mut app, Vec2::new(0.0, 300.0));
+
+        app.update();
+        assert_eq!(get_target(&app, projectile), Some(player_1));
+
+        app.world_mut()
+            .get_mut::<Transform>(player_2)
+            .unwrap()
+            .translation = Vec3::new(0.0, 50.0, 0.0);
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(get_target(&app, projectile), Some(player_1));
+    }
+
+    #[test]
+    fn test_closer_target_is_chosen_after_retarget_period() {
+        let mut app = app();
+        let projectile = spawn_projectile(&mut app, homing_data(Some(0.25)));
+        let player_1 = spawn_player(&mut app, Vec2::new(0.0, 100.0));
+        let player_2 = spawn_player(&mut app, Vec2::new(0.0, 300.0));
+
+        app.update();
+        assert_eq!(get_target(&app, projectile), Some(player_1));
+
+        app.world_mut()
+            .get_mut::<Transform>(player_2)
+            .unwrap()
+            .translation = Vec3::new(0.0, 50.0, 0.0);
+        app.update();
+        app.update();
+        assert_eq!(get_target(&app, projectile), Some(player_1));
+
+        app.update();
+        assert_eq!(get_target(&app, projectile), Some(player_2));
+    }
+}
@@ -2,8 +2,8 @@ use bevy::{
     color::Srgba,
     math::{EulerRot, Mat2},
     prelude::{
-        Commands, Component, Entity, Event, EventReader, EventWriter, Name, Quat, Res, Resource,
-        Sprite, Timer, TimerMode, Transform, Vec2, Vec3Swizzles,
+        Commands, Component, Entity, Event, EventReader, EventWriter, Name, Quat, Query, Res,
+        Resource, Sprite, Timer, TimerMode, Transform, Vec2, Vec3Swizzles,
     },
     sprite::{SpriteBundle, TextureAtlas},
     utils::default,
@@ -16,9 +16,11 @@ use std::collections::HashMap;
 use thetawave_interface::{
     audio::PlaySoundEffectEvent,
     game::options::GameOptions,
+    health::DamageType,
     spawnable::{Faction, ProjectileType, SpawnableType},
     states::GameCleanup,
-    weapon::WeaponProjectileData,
+    targeting::TargetingPolicy,
+    weapon::{SpreadPattern, WeaponProjectileData},
 };
 
 use crate::collision::{
@@ -29,15 +31,21 @@ use crate::{
     animation::{AnimationComponent, AnimationData},
     assets::ProjectileAssets,
     game::GameParametersResource,
-    spawnable::{SpawnableBehavior, SpawnableComponent},
+    spawnable::{
+        targeting::{hostile_targets, select_target, TargetingComponent, TargetsQuery},
+        SpawnableBehavior, SpawnableComponent,
+    },
     weapon::WeaponProjectileInitialVelocitiesExt,
 };
 
 mod behavior;
+mod homing;
 
 pub(in crate::spawnable) use self::behavior::{
     projectile_execute_behavior_system, ProjectileBehavior,
 };
+pub(in crate::spawnable) use self::homing::projectile_homing_system;
+use self::homing::ProjectileHomingComponent;
 
 use super::{mob::ColliderData, InitialMotion};
 
@@ -58,10 +66,18 @@ pub struct ProjectileComponent {
     pub behaviors: Vec<ProjectileBehavior>,
     /// Damage dealt to target
     pub damage: usize,
+    /// Type of the damage dealt to target
+    pub damage_type: DamageType,
     /// Time the projectile has existed
     pub time_alive: f32,
     /// Entity that fired the projectile
     pub source: Entity,
+    /// Players and mobs that have already been hit by the projectile
+    pub hits: Vec<Entity>,
+    /// Number of targets the projectile has pierced through
+    pub pierces: usize,
+    /// Number of times the projectile has ricocheted
+    pub ricochets: usize,
 }
 
 /// Data about mob entities that can be stored in data ron file
@@ -73,6 +89,8 @@ pub struct ProjectileData {
     pub spawnable_behaviors: Vec<SpawnableBehavior>,
     /// List of projectile behaviors that are performed
     pub projectile_behaviors: Vec<ProjectileBehavior>,
+    /// Type of the damage dealt by the projectile
+    pub damage_type: DamageType,
     /// Animation (currently loops single animation in specified direction)
     pub animation: AnimationData,
     /// Z level of transform of projectile
@@ -103,6 +121,9 @@ pub struct ProjectileResource {
 }
 
 /// Spawns projectiles from events
+/// Projectiles with the aimed burst spread pattern are aimed at the target of the source,
+/// or at the nearest hostile target if the source doesn't have a `TargetingComponent`
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_projectile_system(
     mut commands: Commands,
     mut fire_weapon_event_reader: EventReader<FireWeaponEvent>,
@@ -111,12 +132,49 @@ pub fn spawn_projectile_system(
     projectile_assets: Res<ProjectileAssets>,
     game_parameters: Res<GameParametersResource>,
     game_options: Res<GameOptions>,
+    targets_query: TargetsQuery,
+    targeting_query: Query<&TargetingComponent>,
 ) {
     for event in fire_weapon_event_reader.read() {
+        let mut weapon_projectile_data = event.weapon_projectile_data.clone();
+
+        if matches!(
+            weapon_projectile_data.spread_pattern,
+            SpreadPattern::AimedBurst(_)
+        ) {
+            let faction = weapon_projectile_data.ammunition.get_faction();
+            let source_position = event.source_transform.translation.xy();
+            let targets = targets_query.targets();
+
+            let target_position = match targeting_query
+                .get(event.source_entity)
+                .ok()
+                .and_then(|targeting| targeting.target)
+            {
+                Some(entity) => targets
+                    .iter()
+                    .find(|target| target.entity == entity)
+                    .map(|target| target.position),
+                None => select_target(
+                    TargetingPolicy::Nearest,
+                    source_position,
+                    hostile_targets(&targets, faction),
+                )
+                .map(|target| target.position),
+            };
+
+            // aim relative to the rotation of the source, since it is added when spawning
+            if let Some(target_position) = target_position {
+                let aim = target_position - source_position;
+                weapon_projectile_data.direction =
+                    aim.y.atan2(aim.x) - event.source_transform.rotation.to_euler(EulerRot::ZYX).0;
+            }
+        }
+
         spawn_projectile_from_weapon(
             &mut commands,
             &mut sound_effect_event_writer,
-            event.weapon_projectile_data.clone(),
+            weapon_projectile_data,
             event.initial_motion.clone(),
             event.source_entity,
             event.source_transform,
@@ -154,6 +212,42 @@ pub fn spawn_projectile_from_weapon(
         despawn_time: weapon_projectile_data.despawn_time,
     });
 
+    // Extend the pierce behavior of the ammunition with the weapon's extra pierces
+    if weapon_projectile_data.extra_pierce > 0 {
+        if let Some(ProjectileBehavior::Pierce { count, .. }) = projectile_behaviors
+            .iter_mut()
+            .find(|behavior| matches!(behavior, ProjectileBehavior::Pierce { .. }))
+        {
+            *count += weapon_projectile_data.extra_pierce;
+        } else {
+            projectile_behaviors.push(ProjectileBehavior::Pierce {
+                count: weapon_projectile_data.extra_pierce,
+                damage_multiplier: 1.0,
+            });
+        }
+    }
+
+    // Extend the ricochet behavior of the ammunition with the weapon's extra ricochets
+    if weapon_projectile_data.extra_ricochet > 0 {
+        if let Some(ProjectileBehavior::Ricochet { count }) = projectile_behaviors
+            .iter_mut()
+            .find(|behavior| matches!(behavior, ProjectileBehavior::Ricochet { .. }))
+        {
+            *count += weapon_projectile_data.extra_ricochet;
+        } else {
+            projectile_behaviors.push(ProjectileBehavior::Ricochet {
+                count: weapon_projectile_data.extra_ricochet,
+            });
+        }
+    }
+
+    // Apply the weapon's status effects in addition to any applied by the ammunition
+    if !weapon_projectile_data.status_effects.is_empty() {
+        projectile_behaviors.push(ProjectileBehavior::ApplyStatusEffectsOnImpact(
+            weapon_projectile_data.status_effects.clone(),
+        ));
+    }
+
     // Create the transform for spawned projectiles
     let projectile_transform = Transform {
         translation: match weapon_projectile_data.position {
@@ -241,8 +335,12 @@ pub fn spawn_projectile_from_weapon(
                 projectile_type: projectile_data.projectile_type,
                 behaviors: projectile_behaviors.clone(),
                 damage: weapon_projectile_data.damage,
+                damage_type: projectile_data.damage_type,
                 time_alive: 0.0,
                 source: source_entity,
+                hits: vec![],
+                pierces: 0,
+                ricochets: 0,
             })
             .insert(SpawnableComponent {
                 spawnable_type: SpawnableType::Projectile(projectile_data.projectile_type),
@@ -267,6 +365,10 @@ pub fn spawn_projectile_from_weapon(
         if !projectile_data.is_solid {
             projectile.insert(Sensor);
         }
+
+        if let Some(homing_data) = &weapon_projectile_data.homing {
+            projectile.insert(ProjectileHomingComponent::from(homing_data));
+        }
     }
 }
 
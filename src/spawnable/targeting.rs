@@ -0,0 +1,489 @@
+//! Shared target selection used by mobs, mob segments, and homing or aimed projectiles
+use std::collections::HashMap;
+
+use bevy::{
+    ecs::system::SystemParam,
+    math::Vec2,
+    prelude::{Component, Entity, EventReader, Query, Res, Transform, Vec3Swizzles, With, Without},
+    time::{Time, Timer, TimerMode},
+};
+use rand::{seq::IteratorRandom, thread_rng};
+use thetawave_interface::{
+    abilities::DecoyComponent,
+    health::{DamageDealtEvent, HealthComponent},
+    player::{PlayerComponent, PlayerDownedComponent, PlayerRespawnComponent},
+    spawnable::Faction,
+    targeting::{TargetingData, TargetingPolicy},
+};
+
+use super::{MobComponent, MobSegmentComponent, ProjectileComponent};
+
+/// An entity that can be targeted
+#[derive(Clone, Copy, Debug)]
+pub struct Target {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub faction: Faction,
+    pub health: usize,
+    /// Decoys are chosen over all other targets
+    pub is_decoy: bool,
+}
+
+/// Queries for all targetable entities
+/// Downed and respawning players can't be targeted, decoys are targeted as allies
+#[derive(SystemParam)]
+#[allow(clippy::type_complexity)]
+pub struct TargetsQuery<'w, 's> {
+    players: Query<
+        'w,
+        's,
+        (Entity, &'static Transform, &'static HealthComponent),
+        (
+            With<PlayerComponent>,
+            Without<PlayerDownedComponent>,
+            Without<PlayerRespawnComponent>,
+            Without<ProjectileComponent>,
+        ),
+    >,
+    mobs: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Transform,
+            &'static HealthComponent,
+            &'static MobComponent,
+        ),
+        Without<ProjectileComponent>,
+    >,
+    mob_segments: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Transform,
+            &'static HealthComponent,
+            &'static MobSegmentComponent,
+        ),
+        Without<ProjectileComponent>,
+    >,
+    decoys: Query<
+        'w,
+        's,
+        (Entity, &'static Transform, &'static HealthComponent),
+        (With<DecoyComponent>, Without<ProjectileComponent>),
+    >,
+}
+
+impl TargetsQuery<'_, '_> {
+    /// Collect all targetable entities along with their positions, factions, and health
+    pub fn targets(&self) -> Vec<Target> {
+        self.players
+            .iter()
+            .map(|(entity, transform, health)| Target {
+                entity,
+                position: transform.translation.xy(),
+                faction: Faction::Ally,
+                health: health.get_health(),
+                is_decoy: false,
+            })
+            .chain(
+                self.mobs
+                    .iter()
+                    .map(|(entity, transform, health, mob)| Target {
+                        entity,
+                        position: transform.translation.xy(),
+                        faction: mob.mob_type.get_faction(),
+                        health: health.get_health(),
+                        is_decoy: false,
+                    }),
+            )
+            .chain(
+                self.mob_segments
+                    .iter()
+                    .map(|(entity, transform, health, mob_segment)| Target {
+                        entity,
+                        position: transform.translation.xy(),
+                        faction: mob_segment.mob_segment_type.get_faction(),
+                        health: health.get_health(),
+                        is_decoy: false,
+                    }),
+            )
+            .chain(
+                self.decoys
+                    .iter()
+                    .map(|(entity, transform, health)| Target {
+                        entity,
+                        position: transform.translation.xy(),
+                        faction: Faction::Ally,
+                        health: health.get_health(),
+                        is_decoy: true,
+                    }),
+            )
+            .collect()
+    }
+}
+
+/// Targets that are hostile to the faction
+pub fn hostile_targets(targets: &[Target], faction: Faction) -> impl Iterator<Item = &Target> {
+    targets
+        .iter()
+        .filter(move |target| faction.is_hostile_to(&target.faction))
+}
+
+/// Choose a target from the candidates using a policy, decoys are chosen over all other candidates
+/// `LastAttacker` and `Alternate` depend on the state in `TargetingComponent`, so they choose the nearest target
+pub fn select_target<'a>(
+    policy: TargetingPolicy,
+    position: Vec2,
+    candidates: impl Iterator<Item = &'a Target>,
+) -> Option<&'a Target> {
+    let candidates: Vec<&Target> = candidates.collect();
+    let has_decoy = candidates.iter().any(|target| target.is_decoy);
+    let candidates = candidates
+        .into_iter()
+        .filter(|target| target.is_decoy || !has_decoy);
+
+    match policy {
+        TargetingPolicy::Nearest | TargetingPolicy::LastAttacker | TargetingPolicy::Alternate => {
+            candidates.min_by(|a, b| {
+                a.position
+                    .distance(position)
+                    .total_cmp(&b.position.distance(position))
+            })
+        }
+        TargetingPolicy::LowestHealth => candidates.min_by(|a, b| {
+            a.health.cmp(&b.health).then(
+                a.position
+                    .distance(position)
+                    .total_cmp(&b.position.distance(position)),
+            )
+        }),
+        TargetingPolicy::Random => candidates.choose(&mut thread_rng()),
+    }
+}
+
+/// Tracks the target of a mob or mob segment
+#[derive(Component)]
+pub struct TargetingComponent {
+    /// Policy and aggro settings
+    pub data: TargetingData,
+    /// Faction of the targeting entity, only hostile entities are targeted
+    pub faction: Faction,
+    /// Entity currently being targeted
+    pub target: Option<Entity>,
+    /// Entity that most recently dealt damage
+    last_attacker: Option<Entity>,
+    /// Damage dealt by each entity since the target last changed
+    aggro: HashMap<Entity, usize>,
+    /// Whether the target drew aggro, aggro targets are kept until they are no longer targetable
+    is_aggro_target: bool,
+    /// Tracks time until switching targets with the `Alternate` policy
+    alternate_timer: Timer,
+}
+
+impl TargetingComponent {
+    pub fn new(data: &TargetingData, faction: Faction) -> Self {
+        Self {
+            data: data.clone(),
+            faction,
+            target: None,
+            last_attacker: None,
+            aggro: HashMap::new(),
+            is_aggro_target: false,
+            alternate_timer: Timer::from_seconds(data.alternate_period, TimerMode::Repeating),
+        }
+    }
+
+    /// Switch to a new target, resetting the aggro built up against the old one
+    fn set_target(&mut self, target: Option<Entity>) {
+        if self.target != target {
+            self.aggro.clear();
+            self.is_aggro_target = false;
+        }
+        self.target = target;
+    }
+}
+
+/// Track damage dealt to entities with targeting, switching targets to attackers that deal enough damage
+pub fn targeting_aggro_system(
+    mut damage_dealt_event_reader: EventReader<DamageDealtEvent>,
+    mut targeting_query: Query<&mut TargetingComponent>,
+) {
+    for event in damage_dealt_event_reader.read() {
+        let Some(source) = event.source else {
+            continue;
+        };
+
+        if let Ok(mut targeting) = targeting_query.get_mut(event.target) {
+            targeting.last_attacker = Some(source);
+
+            let aggro = targeting.aggro.entry(source).or_default();
+            *aggro += event.damage;
+            let aggro = *aggro;
+
+            if targeting
+                .data
+                .aggro_threshold
+                .is_some_and(|threshold| aggro >= threshold)
+            {
+                targeting.set_target(Some(source));
+                targeting.is_aggro_target = true;
+            }
+        }
+    }
+}
+
+/// Update the target of each entity with targeting based on its policy
+/// `Nearest` and `LowestHealth` targets are chosen again every frame, other targets and targets that drew aggro
+/// are kept until they are no longer targetable
+pub fn update_targeting_system(
+    mut targeting_query: Query<(&mut TargetingComponent, &Transform)>,
+    targets_query: TargetsQuery,
+    time: Res<Time>,
+) {
+    let targets = targets_query.targets();
+
+    for (mut targeting, transform) in targeting_query.iter_mut() {
+        let position = transform.translation.xy();
+        let faction = targeting.faction;
+        let is_targetable = |entity: Entity| {
+            hostile_targets(&targets, faction).any(|target| target.entity == entity)
+        };
+
+        // drop targets that are gone, or that aren't hostile, such as mobs dealing collision damage
+        let mut target = targeting.target.filter(|entity| is_targetable(*entity));
+        let is_aggro_target = target.is_some() && targeting.is_aggro_target;
+
+        match targeting.data.policy {
+            TargetingPolicy::Nearest | TargetingPolicy::LowestHealth if !is_aggro_target => {
+                target = None;
+            }
+            TargetingPolicy::LastAttacker => {
+                if let Some(last_attacker) = targeting.last_attacker.filter(|e| is_targetable(*e)) {
+                    target = Some(last_attacker);
+                }
+            }
+            TargetingPolicy::Alternate => {
+                if targeting.alternate_timer.tick(time.delta()).just_finished() {
+                    // cycle through the targets in a consistent order
+                    let mut candidates: Vec<Entity> = hostile_targets(&targets, faction)
+                        .map(|target| target.entity)
+                        .collect();
+                    candidates.sort();
+                    if let Some(idx) =
+                        target.and_then(|entity| candidates.iter().position(|e| *e == entity))
+                    {
+                        target = Some(candidates[(idx + 1) % candidates.len()]);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        // decoys draw the attention of every entity with targeting
+        let is_targeting_decoy = target.is_some_and(|entity| {
+            hostile_targets(&targets, faction)
+                .any(|target| target.entity == entity && target.is_decoy)
+        });
+        if !is_targeting_decoy && hostile_targets(&targets, faction).any(|target| target.is_decoy) {
+            target = None;
+        }
+
+        if target.is_none() {
+            target = select_target(
+                targeting.data.policy,
+                position,
+                hostile_targets(&targets, faction),
+            )
+            .map(|target| target.entity);
+        }
+
+        targeting.set_target(target);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::app::{App, Update};
+    use bevy::math::{Vec2, Vec3};
+    use bevy::prelude::{Entity, Events, IntoSystemConfigs, Transform, World};
+    use bevy::MinimalPlugins;
+    use thetawave_interface::health::{DamageDealtEvent, DamageType, HealthComponent};
+    use thetawave_interface::player::PlayerComponent;
+    use thetawave_interface::spawnable::Faction;
+    use thetawave_interface::targeting::{TargetingData, TargetingPolicy};
+
+    use super::{
+        hostile_targets, select_target, targeting_aggro_system, update_targeting_system, Target,
+        TargetingComponent,
+    };
+
+    fn target(idx: u32, position: Vec2, health: usize) -> Target {
+        Target {
+            entity: Entity::from_raw(idx),
+            position,
+            faction: Faction::Ally,
+            health,
+            is_decoy: false,
+        }
+    }
+
+    #[test]
+    fn test_select_nearest_target() {
+        let targets = [
+            target(0, Vec2::new(100.0, 0.0), 10),
+            target(1, Vec2::new(0.0, 20.0), 50),
+            target(2, Vec2::new(-50.0, -50.0), 5),
+        ];
+
+        let selected = select_target(TargetingPolicy::Nearest, Vec2::ZERO, targets.iter());
+
+        assert_eq!(selected.map(|t| t.entity), Some(Entity::from_raw(1)));
+    }
+
+    #[test]
+    fn test_select_lowest_health_target_breaks_ties_by_distance() {
+        let targets = [
+            target(0, Vec2::new(100.0, 0.0), 5),
+            target(1, Vec2::new(0.0, 20.0), 50),
+            target(2, Vec2::new(-10.0, 0.0), 5),
+        ];
+
+        let selected = select_target(TargetingPolicy::LowestHealth, Vec2::ZERO, targets.iter());
+
+        assert_eq!(selected.map(|t| t.entity), Some(Entity::from_raw(2)));
+    }
+
+    #[test]
+    fn test_select_decoy_over_closer_targets() {
+        let mut decoy = target(1, Vec2::new(500.0, 0.0), 100);
+        decoy.is_decoy = true;
+        let targets = [target(0, Vec2::new(1.0, 0.0), 1), decoy];
+
+        for policy in [
+            TargetingPolicy::Nearest,
+            TargetingPolicy::LowestHealth,
+            TargetingPolicy::Random,
+        ] {
+            let selected = select_target(policy, Vec2::ZERO, targets.iter());
+            assert_eq!(selected.map(|t| t.entity), Some(Entity::from_raw(1)));
+        }
+    }
+
+    #[test]
+    fn test_select_random_target_from_candidates() {
+        let targets = [
+            target(0, Vec2::new(100.0, 0.0), 10),
+            target(1, Vec2::new(0.0, 20.0), 50),
+        ];
+
+        let selected = select_target(TargetingPolicy::Random, Vec2::ZERO, targets.iter());
+
+        assert!(selected.is_some_and(|t| targets.iter().any(|c| c.entity == t.entity)));
+    }
+
+    #[test]
+    fn test_select_no_target_without_candidates() {
+        assert!(select_target(TargetingPolicy::Nearest, Vec2::ZERO, [].iter()).is_none());
+    }
+
+    #[test]
+    fn test_hostile_targets_only_include_hostile_factions() {
+        let mut neutral = target(1, Vec2::ZERO, 10);
+        neutral.faction = Faction::Neutral;
+        let mut enemy = target(2, Vec2::ZERO, 10);
+        enemy.faction = Faction::Enemy;
+        let targets = [target(0, Vec2::ZERO, 10), neutral, enemy];
+
+        let hostile: Vec<Entity> = hostile_targets(&targets, Faction::Enemy)
+            .map(|t| t.entity)
+            .collect();
+
+        assert_eq!(hostile, vec![Entity::from_raw(0)]);
+    }
+
+    fn app_with_targeting() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_event::<DamageDealtEvent>()
+            .add_systems(
+                Update,
+                (targeting_aggro_system, update_targeting_system).chain(),
+            );
+        app
+    }
+
+    fn spawn_player(world: &mut World, position: Vec2) -> Entity {
+        world
+            .spawn((
+                PlayerComponent,
+                Transform::from_translation(position.extend(0.0)),
+                HealthComponent::new(100, 0, 1.0),
+            ))
+            .id()
+    }
+
+    fn spawn_targeting_mob(world: &mut World, data: TargetingData) -> Entity {
+        world
+            .spawn((
+                TargetingComponent::new(&data, Faction::Enemy),
+                Transform::from_translation(Vec3::ZERO),
+            ))
+            .id()
+    }
+
+    fn get_target(app: &App, mob: Entity) -> Option<Entity> {
+        app.world().get::<TargetingComponent>(mob).unwrap().target
+    }
+
+    #[test]
+    fn test_nearest_target_is_chosen_again_every_frame() {
+        let mut app = app_with_targeting();
+        let player_1 = spawn_player(app.world_mut(), Vec2::new(10.0, 0.0));
+        let player_2 = spawn_player(app.world_mut(), Vec2::new(100.0, 0.0));
+        let mob = spawn_targeting_mob(app.world_mut(), TargetingData::default());
+
+        app.update();
+        assert_eq!(get_target(&app, mob), Some(player_1));
+
+        app.world_mut()
+            .get_mut::<Transform>(player_1)
+            .unwrap()
+            .translation = Vec3::new(200.0, 0.0, 0.0);
+        app.update();
+        assert_eq!(get_target(&app, mob), Some(player_2));
+    }
+
+    #[test]
+    fn test_aggro_target_is_kept_over_nearest() {
+        let mut app = app_with_targeting();
+        let player_1 = spawn_player(app.world_mut(), Vec2::new(10.0, 0.0));
+        let player_2 = spawn_player(app.world_mut(), Vec2::new(100.0, 0.0));
+        let mob = spawn_targeting_mob(
+            app.world_mut(),
+            TargetingData {
+                aggro_threshold: Some(5),
+                ..Default::default()
+            },
+        );
+
+        app.update();
+        assert_eq!(get_target(&app, mob), Some(player_1));
+
+        app.world_mut()
+            .resource_mut::<Events<DamageDealtEvent>>()
+            .send(DamageDealtEvent {
+                damage: 5,
+                target: mob,
+                source: Some(player_2),
+                damage_type: DamageType::Kinetic,
+                is_critical: false,
+            });
+        app.update();
+        assert_eq!(get_target(&app, mob), Some(player_2));
+
+        app.update();
+        assert_eq!(get_target(&app, mob), Some(player_2));
+    }
+}
@@ -0,0 +1,262 @@
+//! Exposes a plugin that applies, updates, and displays timed status effects on players and mobs
+use bevy::{
+    app::{App, Plugin, Update},
+    color::{Color, LinearRgba},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{EventReader, EventWriter},
+        query::With,
+        schedule::IntoSystemConfigs,
+        system::{Commands, Query, Res},
+    },
+    hierarchy::{BuildChildren, Children, DespawnRecursiveExt},
+    math::{Vec2, Vec3},
+    prelude::{default, in_state},
+    sprite::{Sprite, SpriteBundle},
+    time::Time,
+    transform::components::Transform,
+    utils::HashMap,
+};
+use bevy_rapier2d::dynamics::Velocity;
+use thetawave_interface::{
+    health::{DamageDealtEvent, DamageType, HealthComponent},
+    player::PlayerMovementComponent,
+    states::{AppStates, GameStates},
+    status_effect::{ApplyStatusEffectEvent, StatusEffectType, StatusEffectsComponent},
+};
+
+use crate::{spawnable::SpawnableComponent, GameUpdateSet};
+
+/// Size of status effect icons relative to the scale of the affected entity
+const ICON_SIZE: f32 = 1.5;
+/// Height of status effect icons above the center of the affected entity
+const ICON_HEIGHT: f32 = 7.0;
+
+/// Includes systems for applying status effects from events, dealing damage over time,
+/// slowing movement, and tinting/marking affected entities
+pub(super) struct StatusEffectPlugin;
+
+impl Plugin for StatusEffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ApplyStatusEffectEvent>().add_systems(
+            Update,
+            (
+                apply_status_effect_system,
+                update_status_effects_system,
+                status_effect_movement_system
+                    .after(GameUpdateSet::Movement)
+                    .after(GameUpdateSet::ExecuteBehavior),
+                status_effect_tint_system.after(GameUpdateSet::ExecuteBehavior),
+                status_effect_icon_system.after(GameUpdateSet::ExecuteBehavior),
+            )
+                .run_if(in_state(AppStates::Game))
+                .run_if(in_state(GameStates::Playing)),
+        );
+    }
+}
+
+/// Stores the color of a sprite before it was tinted by status effects
+#[derive(Component)]
+struct StatusEffectTintComponent {
+    base_color: Color,
+}
+
+/// Marks a child sprite used as an icon for a status effect
+#[derive(Component)]
+struct StatusEffectIconComponent(StatusEffectType);
+
+/// Color used for tints and icons of each type of status effect
+fn status_effect_color(effect_type: StatusEffectType) -> Color {
+    match effect_type {
+        StatusEffectType::Freeze => Color::srgb(0.4, 0.8, 1.0),
+        StatusEffectType::Stun => Color::srgb(1.0, 1.0, 0.4),
+        StatusEffectType::Burn => Color::srgb(1.0, 0.45, 0.2),
+        StatusEffectType::Slow => Color::srgb(0.5, 0.5, 1.0),
+        StatusEffectType::Vulnerable => Color::srgb(0.9, 0.3, 0.9),
+    }
+}
+
+/// Apply status effects to entities with health, adding a `StatusEffectsComponent` if needed
+fn apply_status_effect_system(
+    mut commands: Commands,
+    mut apply_status_effect_events: EventReader<ApplyStatusEffectEvent>,
+    mut status_effects_query: Query<Option<&mut StatusEffectsComponent>, With<HealthComponent>>,
+) {
+    // effects for entities that don't have a status effects component yet
+    let mut new_status_effects: HashMap<Entity, StatusEffectsComponent> = HashMap::new();
+
+    for event in apply_status_effect_events.read() {
+        match status_effects_query.get_mut(event.target) {
+            Ok(Some(mut status_effects)) => status_effects.apply(&event.status_effect),
+            Ok(None) => new_status_effects
+                .entry(event.target)
+                .or_default()
+                .apply(&event.status_effect),
+            Err(_) => {}
+        }
+    }
+
+    for (entity, status_effects) in new_status_effects {
+        commands.entity(entity).try_insert(status_effects);
+    }
+}
+
+/// Tick status effects and deal damage from damage over time effects
+/// Damage over time is treated as energy damage without a source
+fn update_status_effects_system(
+    mut status_effects_query: Query<(Entity, &mut StatusEffectsComponent)>,
+    mut damage_dealt_event_writer: EventWriter<DamageDealtEvent>,
+    time: Res<Time>,
+) {
+    for (entity, mut status_effects) in status_effects_query.iter_mut() {
+        let damage = status_effects.update(time.delta());
+
+        if damage > 0 {
+            damage_dealt_event_writer.send(DamageDealtEvent {
+                damage,
+                target: entity,
+                source: None,
+                damage_type: DamageType::Energy,
+                is_critical: false,
+            });
+        }
+    }
+}
+
+/// Limit the velocity of slowed and frozen players and mobs to a fraction of their maximum speed
+fn status_effect_movement_system(
+    mut status_effects_query: Query<(
+        &StatusEffectsComponent,
+        &mut Velocity,
+        Option<&PlayerMovementComponent>,
+        Option<&SpawnableComponent>,
+    )>,
+) {
+    for (status_effects, mut velocity, player_movement, spawnable) in
+        status_effects_query.iter_mut()
+    {
+        let multiplier = status_effects.movement_multiplier();
+
+        if multiplier >= 1.0 {
+            continue;
+        }
+
+        // players have their rotation locked, so only their linear speed is limited
+        let (max_speed, max_angular_speed) = if let Some(player_movement) = player_movement {
+            (player_movement.speed, None)
+        } else if let Some(spawnable) = spawnable {
+            (spawnable.speed, Some(spawnable.angular_speed))
+        } else {
+            continue;
+        };
+
+        let max_speed = max_speed * multiplier;
+        velocity.linvel = velocity.linvel.clamp(-max_speed, max_speed);
+
+        if let Some(max_angular_speed) = max_angular_speed {
+            let max_angular_speed = max_angular_speed * multiplier;
+            velocity.angvel = velocity.angvel.clamp(-max_angular_speed, max_angular_speed);
+        }
+    }
+}
+
+/// Tint the sprites of entities with active status effects, and restore them when the effects end
+fn status_effect_tint_system(
+    mut commands: Commands,
+    mut sprite_query: Query<(
+        Entity,
+        &StatusEffectsComponent,
+        &mut Sprite,
+        Option<&StatusEffectTintComponent>,
+    )>,
+) {
+    for (entity, status_effects, mut sprite, tint) in sprite_query.iter_mut() {
+        match (status_effects.active_types().first(), tint) {
+            (Some(effect_type), tint) => {
+                let base_color = match tint {
+                    Some(tint) => tint.base_color,
+                    None => {
+                        commands
+                            .entity(entity)
+                            .try_insert(StatusEffectTintComponent {
+                                base_color: sprite.color,
+                            });
+                        sprite.color
+                    }
+                };
+
+                // multiply so that bloom colors brighter than white are preserved
+                let base = base_color.to_linear();
+                let effect = status_effect_color(*effect_type).to_linear();
+                sprite.color = Color::LinearRgba(LinearRgba::new(
+                    base.red * effect.red,
+                    base.green * effect.green,
+                    base.blue * effect.blue,
+                    base.alpha,
+                ));
+            }
+            (None, Some(tint)) => {
+                sprite.color = tint.base_color;
+                commands
+                    .entity(entity)
+                    .remove::<StatusEffectTintComponent>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Display a row of icons above entities for each of their active status effects
+fn status_effect_icon_system(
+    mut commands: Commands,
+    status_effects_query: Query<(Entity, &StatusEffectsComponent, Option<&Children>)>,
+    icon_query: Query<&StatusEffectIconComponent>,
+) {
+    for (entity, status_effects, children) in status_effects_query.iter() {
+        let active_types = status_effects.active_types();
+
+        let icons: Vec<(Entity, StatusEffectType)> = children
+            .map(|children| {
+                children
+                    .iter()
+                    .filter_map(|child| icon_query.get(*child).ok().map(|icon| (*child, icon.0)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // only rebuild the icons when the active effects have changed
+        if icons
+            .iter()
+            .map(|(_, effect_type)| *effect_type)
+            .eq(active_types.iter().copied())
+        {
+            continue;
+        }
+
+        for (icon_entity, _) in icons {
+            commands.entity(icon_entity).despawn_recursive();
+        }
+
+        let offset = (active_types.len() as f32 - 1.0) / 2.0;
+        commands.entity(entity).with_children(|parent| {
+            for (i, effect_type) in active_types.iter().enumerate() {
+                parent
+                    .spawn(SpriteBundle {
+                        sprite: Sprite {
+                            color: status_effect_color(*effect_type),
+                            custom_size: Some(Vec2::splat(ICON_SIZE)),
+                            ..default()
+                        },
+                        transform: Transform::from_translation(Vec3::new(
+                            (i as f32 - offset) * ICON_SIZE * 1.5,
+                            ICON_HEIGHT,
+                            1.0,
+                        )),
+                        ..default()
+                    })
+                    .insert(StatusEffectIconComponent(*effect_type));
+            }
+        });
+    }
+}
@@ -25,7 +25,10 @@ use bevy::{
         system::{Commands, Local, Query, Res, ResMut},
     },
     hierarchy::{BuildChildren, ChildBuilder, Children, DespawnRecursiveExt},
-    input::gamepad::{Gamepad, GamepadButtonChangedEvent},
+    input::{
+        gamepad::{Gamepad, GamepadButton},
+        ButtonInput,
+    },
     prelude::{in_state, NextState, OnEnter},
     text::{Font, Text, TextStyle},
     ui::{
@@ -39,7 +42,7 @@ use bevy::{
 use leafwing_input_manager::{prelude::ActionState, InputManagerBundle};
 use strum::IntoEnumIterator;
 use thetawave_interface::{
-    abilities::AbilityDescriptionsResource,
+    abilities::{AbilitiesResource, AbilityDescriptionsResource, AbilityUpgradeLevel},
     character::{Character, CharacterStatType},
     input::{InputsResource, MainMenuExplorer, MenuAction, MenuExplorer},
     states::{self, AppStates},
@@ -149,11 +152,11 @@ struct PlayerReadyNode {
 }
 
 impl CharacterCarousel {
-    /// Creates a new `CharacterCarousel` for a given player index.
-    fn new(player_idx: u8) -> Self {
+    /// Creates a new `CharacterCarousel` for a given player index, with characters in their selection order.
+    fn new(player_idx: u8, characters_res: &CharactersResource) -> Self {
         CharacterCarousel {
             player_idx,
-            characters: CharacterType::iter().collect(),
+            characters: characters_res.selection_order.iter().cloned().collect(),
         }
     }
 
@@ -176,82 +179,86 @@ impl CharacterCarousel {
     }
 
     /// Gets the three visible characters in the carousel.
+    ///
+    /// The carousel wraps around, so with fewer than three characters the same character
+    /// can be visible in more than one slot.
     fn get_visible_characters(&self) -> Option<[CharacterType; 3]> {
-        if let (Some(left), Some(middle), Some(right)) = (
-            self.characters.back(),
-            self.characters.front(),
-            self.characters.get(1),
-        ) {
-            Some([*left, *middle, *right])
-        } else {
-            None
-        }
-    }
-}
+        let middle = self.characters.front()?;
+        let left = self.characters.back().unwrap_or(middle);
+        let right = self.characters.get(1).unwrap_or(middle);
 
-/// Extension trait for `CharacterStatType` to provide additional methods.
-trait CharacterStatTypeExt {
-    /// Returns the divisor associated with a particular `CharacterStatType`.
-    /// This is an arbitrary large value representing 100% skill in that stat.
-    fn get_divisor(&self) -> f32;
-}
-
-impl CharacterStatTypeExt for CharacterStatType {
-    fn get_divisor(&self) -> f32 {
-        match self {
-            CharacterStatType::Damage => 50.0,
-            CharacterStatType::Health => 160.0,
-            CharacterStatType::Range => 1.0,
-            CharacterStatType::FireRate => 5.0,
-            CharacterStatType::Size => 30.0,
-            CharacterStatType::Speed => 800.0,
-        }
+        Some([left.clone(), middle.clone(), right.clone()])
     }
 }
 
 /// Extension trait for `Character` to provide additional methods.
 trait CharacterExt {
-    /// Calculates the percentage value of a given character stat.
+    /// Calculates the value of a given character stat, higher values are better.
     ///
-    /// The percentage is calculated based on the specific formula for each `CharacterStatType`.
-    /// The result is capped at a maximum of 100.0.
-    fn get_stat_percent(&self, stat: &CharacterStatType) -> f32;
+    /// The value is calculated based on the specific formula for each `CharacterStatType`.
+    fn get_stat_value(&self, stat: &CharacterStatType) -> f32;
+
+    /// Calculates the percentage value of a given character stat, relative to the character
+    /// with the highest value for that stat.
+    fn get_stat_percent(
+        &self,
+        stat: &CharacterStatType,
+        characters_res: &CharactersResource,
+    ) -> f32;
 }
 
 impl CharacterExt for Character {
-    fn get_stat_percent(&self, stat: &CharacterStatType) -> f32 {
-        // Calculate the percentage value for each stat type.
-        100.0
-            * match stat {
-                CharacterStatType::Damage => {
-                    (self.collision_damage as f32
-                        + (self.weapon_damage as f32 * self.projectile_count as f32))
-                        / stat.get_divisor()
-                }
-                CharacterStatType::Health => {
-                    (self.health as f32 + self.shields as f32) / stat.get_divisor()
-                }
-                CharacterStatType::Range => self.projectile_despawn_time / stat.get_divisor(),
-                CharacterStatType::FireRate => {
-                    (stat.get_divisor() - self.cooldown_multiplier) / stat.get_divisor()
-                }
-                CharacterStatType::Size => {
-                    (self.collider_dimensions.x * self.collider_dimensions.y) / stat.get_divisor()
-                }
-                CharacterStatType::Speed => {
-                    (self.acceleration.x
-                        + self.acceleration.y
-                        + self.deceleration.x
-                        + self.deceleration.y
-                        + self.speed.x
-                        + self.speed.y)
-                        / stat.get_divisor()
-                }
+    fn get_stat_value(&self, stat: &CharacterStatType) -> f32 {
+        match stat {
+            CharacterStatType::Damage => {
+                self.collision_damage as f32
+                    + (self.weapon_damage as f32 * self.projectile_count as f32)
+            }
+            CharacterStatType::Health => self.health as f32 + self.shields as f32,
+            CharacterStatType::Range => self.projectile_despawn_time,
+            CharacterStatType::FireRate => 1.0 / self.cooldown_multiplier,
+            CharacterStatType::Size => self.collider_dimensions.x * self.collider_dimensions.y,
+            CharacterStatType::Speed => {
+                self.acceleration.x
+                    + self.acceleration.y
+                    + self.deceleration.x
+                    + self.deceleration.y
+                    + self.speed.x
+                    + self.speed.y
             }
-            .min(100.0)
+        }
+    }
+
+    fn get_stat_percent(
+        &self,
+        stat: &CharacterStatType,
+        characters_res: &CharactersResource,
+    ) -> f32 {
+        // Find the highest value of the stat among all characters
+        let max_value = characters_res
+            .characters
+            .values()
+            .map(|character| character.get_stat_value(stat))
+            .fold(0.0, f32::max);
+
+        if max_value > 0.0 {
+            (100.0 * self.get_stat_value(stat) / max_value).min(100.0)
+        } else {
+            0.0
+        }
     }
 }
 
+/// Ability description followed by the description of each level of the ability's upgrade tree
+fn describe_ability_upgrades(description: &str, upgrade_tree: &[AbilityUpgradeLevel]) -> String {
+    upgrade_tree
+        .iter()
+        .enumerate()
+        .fold(description.to_string(), |text, (idx, level)| {
+            format!("{text}\nLv {}: {}", idx + 2, level.description)
+        })
+}
+
 trait UiPlayerJoinChildBuilderExt {
     fn spawn_player_join_row(&mut self, ui_assets: &UiAssets, font: Handle<Font>, players: Vec<u8>);
     fn spawn_ability_descriptions(
@@ -260,12 +267,23 @@ trait UiPlayerJoinChildBuilderExt {
         font: Handle<Font>,
         character: &Character,
         abilities_desc_res: &AbilityDescriptionsResource,
+        abilities_res: &AbilitiesResource,
+    );
+    fn spawn_stats(
+        &mut self,
+        ui_assets: &UiAssets,
+        character: &Character,
+        characters_res: &CharactersResource,
     );
-    fn spawn_stats(&mut self, ui_assets: &UiAssets, character: &Character);
 }
 
 impl UiPlayerJoinChildBuilderExt for ChildBuilder<'_> {
-    fn spawn_stats(&mut self, ui_assets: &UiAssets, character: &Character) {
+    fn spawn_stats(
+        &mut self,
+        ui_assets: &UiAssets,
+        character: &Character,
+        characters_res: &CharactersResource,
+    ) {
         // Iterate over each character stat type
         for stat in CharacterStatType::iter() {
             // Spawn a node for the stat bar container
@@ -303,7 +321,9 @@ impl UiPlayerJoinChildBuilderExt for ChildBuilder<'_> {
                         // Spawn a node to represent the stat percentage
                         parent.spawn(NodeBundle {
                             style: Style {
-                                width: Val::Percent(character.get_stat_percent(&stat)),
+                                width: Val::Percent(
+                                    character.get_stat_percent(&stat, characters_res),
+                                ),
                                 height: Val::Percent(50.0),
                                 align_self: AlignSelf::Center,
                                 ..default()
@@ -322,6 +342,7 @@ impl UiPlayerJoinChildBuilderExt for ChildBuilder<'_> {
         font: Handle<Font>,
         character: &Character,
         abilities_desc_res: &AbilityDescriptionsResource,
+        abilities_res: &AbilitiesResource,
     ) {
         // Check if the character has a slot 1 ability
         if let Some(slot_1_ability_type) = &character.slot_1_ability {
@@ -355,10 +376,13 @@ impl UiPlayerJoinChildBuilderExt for ChildBuilder<'_> {
 
                 // Check if there is a description for the slot 1 ability
                 if let Some(ability_desc) = abilities_desc_res.slot_one.get(slot_1_ability_type) {
-                    // Spawn text for the slot 1 ability description
+                    // Spawn text for the slot 1 ability description and its upgrades
                     parent.spawn(TextBundle {
                         text: Text::from_section(
-                            ability_desc,
+                            describe_ability_upgrades(
+                                ability_desc,
+                                abilities_res.get_slot_1_upgrade_tree(slot_1_ability_type),
+                            ),
                             TextStyle {
                                 font: font.clone(),
                                 font_size: 16.0,
@@ -411,10 +435,13 @@ impl UiPlayerJoinChildBuilderExt for ChildBuilder<'_> {
 
                 // Check if there is a description for the slot 2 ability
                 if let Some(ability_desc) = abilities_desc_res.slot_two.get(slot_2_ability_type) {
-                    // Spawn text for the slot 2 ability description
+                    // Spawn text for the slot 2 ability description and its upgrades
                     parent.spawn(TextBundle {
                         text: Text::from_section(
-                            ability_desc,
+                            describe_ability_upgrades(
+                                ability_desc,
+                                abilities_res.get_slot_2_upgrade_tree(slot_2_ability_type),
+                            ),
                             TextStyle {
                                 font: font.clone(),
                                 font_size: 16.0,
@@ -631,6 +658,7 @@ fn setup_character_selection_system(
 ///
 /// This function detects player join actions through keyboard, gamepad, or mouse inputs,
 /// updates the players resource, and sends appropriate events.
+#[allow(clippy::too_many_arguments)]
 fn player_join_system(
     button_mouse_movements: Query<(&ButtonActionComponent, &Interaction, Entity), With<Button>>,
     menu_explorer_query: Query<&ActionState<MenuAction>, With<MainMenuExplorer>>,
@@ -638,7 +666,9 @@ fn player_join_system(
     mut mouse_interaction: Local<Interaction>,
     game_params_res: Res<GameParametersResource>,
     mut players_resource: ResMut<PlayersResource>,
-    mut gamepad_events: EventReader<GamepadButtonChangedEvent>,
+    characters_res: Res<CharactersResource>,
+    inputs_res: Res<InputsResource>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
     mut player_join_event: EventWriter<PlayerJoinEvent>,
 ) {
     // Check if the join button was pressed
@@ -672,7 +702,7 @@ fn player_join_system(
             } {
                 // Push the new player to the players resource
                 players_resource.player_data.push(Some(PlayerData {
-                    character: CharacterType::default(),
+                    character: characters_res.get_default_character(),
                     input: player_input,
                 }));
 
@@ -690,20 +720,21 @@ fn player_join_system(
             if let Some(player_input) = match menu_explorer_query.get_single() {
                 Err(_) => None,
                 Ok(action) => {
-                    if let Some(gamepad_event) = gamepad_events.read().next() {
-                        if action
+                    if action
+                        .get_just_released()
+                        .iter()
+                        .any(|action| matches!(action, MenuAction::JoinGamepad))
+                    {
+                        // Assign the gamepad that released the join button, if it hasn't already joined
+                        gamepad_buttons
                             .get_just_released()
-                            .iter()
-                            .any(|action| match action {
-                                MenuAction::JoinGamepad => !used_inputs
-                                    .contains(&PlayerInput::Gamepad(gamepad_event.gamepad.id)),
-                                _ => false,
+                            .filter(|button| {
+                                inputs_res
+                                    .join_gamepad_buttons
+                                    .contains(&button.button_type)
                             })
-                        {
-                            Some(PlayerInput::Gamepad(gamepad_event.gamepad.id))
-                        } else {
-                            None
-                        }
+                            .map(|button| PlayerInput::Gamepad(button.gamepad.id))
+                            .find(|player_input| !used_inputs.contains(player_input))
                     } else {
                         None
                     }
@@ -711,7 +742,7 @@ fn player_join_system(
             } {
                 // Push the new player to the players resource
                 players_resource.player_data.push(Some(PlayerData {
-                    character: CharacterType::default(),
+                    character: characters_res.get_default_character(),
                     input: player_input,
                 }));
 
@@ -743,7 +774,7 @@ fn player_join_system(
             } {
                 // Push the new player to the players resource
                 players_resource.player_data.push(Some(PlayerData {
-                    character: CharacterType::default(),
+                    character: characters_res.get_default_character(),
                     input: player_input,
                 }));
 
@@ -780,6 +811,7 @@ fn update_ui_system(
     buttons: Query<(&ButtonActionComponent, Entity), With<Button>>,
     ui_assets: Res<UiAssets>,
     inputs_res: Res<InputsResource>,
+    characters_res: Res<CharactersResource>,
 ) {
     let font: Handle<Font> = asset_server.load("fonts/Lunchds.ttf");
 
@@ -817,7 +849,7 @@ fn update_ui_system(
                             },
                             ..default()
                         })
-                        .insert(CharacterCarousel::new(*player_idx));
+                        .insert(CharacterCarousel::new(*player_idx, &characters_res));
 
                     parent
                         .spawn(NodeBundle {
@@ -1132,6 +1164,7 @@ fn init_carousel_ui_system(
     asset_server: Res<AssetServer>,
     mut players_res: ResMut<PlayersResource>,
     abilities_desc_res: Res<AbilityDescriptionsResource>,
+    abilities_res: Res<AbilitiesResource>,
 ) {
     // Load the font for UI text elements
     let font: Handle<Font> = asset_server.load("fonts/Lunchds.ttf");
@@ -1144,8 +1177,11 @@ fn init_carousel_ui_system(
             commands.entity(carousel_entity).with_children(|parent| {
                 parent
                     .spawn(ImageBundle {
-                        image: UiImage::new(player_assets.get_asset(&visible_characters[0]))
-                            .with_color(Color::srgba(0.60, 0.60, 0.60, 0.60)),
+                        image: UiImage::new(
+                            player_assets
+                                .get_asset(&characters_res.characters[&visible_characters[0]]),
+                        )
+                        .with_color(Color::srgba(0.60, 0.60, 0.60, 0.60)),
                         style: Style {
                             height: Val::Percent(80.0),
                             margin: UiRect {
@@ -1161,7 +1197,9 @@ fn init_carousel_ui_system(
 
                 parent
                     .spawn(ImageBundle {
-                        image: player_assets.get_asset(&visible_characters[1]).into(),
+                        image: player_assets
+                            .get_asset(&characters_res.characters[&visible_characters[1]])
+                            .into(),
                         style: Style {
                             height: Val::Percent(100.0),
                             margin: UiRect {
@@ -1177,8 +1215,11 @@ fn init_carousel_ui_system(
 
                 parent
                     .spawn(ImageBundle {
-                        image: UiImage::new(player_assets.get_asset(&visible_characters[2]))
-                            .with_color(Color::srgba(0.60, 0.60, 0.60, 0.60)),
+                        image: UiImage::new(
+                            player_assets
+                                .get_asset(&characters_res.characters[&visible_characters[2]]),
+                        )
+                        .with_color(Color::srgba(0.60, 0.60, 0.60, 0.60)),
                         style: Style {
                             height: Val::Percent(80.0),
                             margin: UiRect {
@@ -1231,6 +1272,7 @@ fn init_carousel_ui_system(
                                                 font.clone(),
                                                 character,
                                                 &abilities_desc_res,
+                                                &abilities_res,
                                             );
                                         });
                                 // Update character stat descriptions
@@ -1238,7 +1280,7 @@ fn init_carousel_ui_system(
                                     character_stats.get(*char_info_child)
                                 {
                                     commands.entity(char_stats_entity).with_children(|parent| {
-                                        parent.spawn_stats(&ui_assets, character);
+                                        parent.spawn_stats(&ui_assets, character, &characters_res);
                                     });
                                 }
                             }
@@ -1252,7 +1294,7 @@ fn init_carousel_ui_system(
                 .player_data
                 .get_mut(carousel.player_idx as usize)
             {
-                player_data.character = visible_characters[1];
+                player_data.character = visible_characters[1].clone();
             }
         }
     }
@@ -1277,6 +1319,7 @@ fn carousel_ui_system(
     mut players_res: ResMut<PlayersResource>,
     characters_res: Res<CharactersResource>,
     abilities_desc_res: Res<AbilityDescriptionsResource>,
+    abilities_res: Res<AbilitiesResource>,
     ui_assets: Res<UiAssets>,
     asset_server: Res<AssetServer>,
     player_ready_node: Query<&PlayerReadyNode>,
@@ -1320,7 +1363,10 @@ fn carousel_ui_system(
                     for (idx, carousel_child) in carousel_children.iter().enumerate() {
                         if let Ok((mut ui_image, slot)) = carousel_slots.get_mut(*carousel_child) {
                             *ui_image = player_assets
-                                .get_asset(&visible_characters[slot.0 as usize])
+                                .get_asset(
+                                    &characters_res.characters
+                                        [&visible_characters[slot.0 as usize]],
+                                )
                                 .into();
 
                             if idx != 1 {
@@ -1370,6 +1416,7 @@ fn carousel_ui_system(
                                                         font.clone(),
                                                         character,
                                                         &abilities_desc_res,
+                                                        &abilities_res,
                                                     );
                                                 },
                                             );
@@ -1384,7 +1431,11 @@ fn carousel_ui_system(
                                             // Spawn character stats as children
                                             commands.entity(char_stats_entity).with_children(
                                                 |parent| {
-                                                    parent.spawn_stats(&ui_assets, character);
+                                                    parent.spawn_stats(
+                                                        &ui_assets,
+                                                        character,
+                                                        &characters_res,
+                                                    );
                                                 },
                                             );
                                         }
@@ -1400,7 +1451,7 @@ fn carousel_ui_system(
                         .player_data
                         .get_mut(carousel.player_idx as usize)
                     {
-                        player_data.character = visible_characters[1];
+                        player_data.character = visible_characters[1].clone();
                     }
                 }
             }
@@ -0,0 +1,288 @@
+//! Systems that draw the health of the boss across the top of the arena.
+use bevy::{
+    color::{Alpha, Color, Mix, Srgba},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::With,
+        system::{Commands, Query, Res},
+    },
+    hierarchy::{BuildChildren, ChildBuilder, DespawnRecursiveExt},
+    text::{Text, TextStyle},
+    time::{Time, Timer, TimerMode},
+    ui::{
+        node_bundles::{NodeBundle, TextBundle},
+        AlignItems, FlexDirection, PositionType, Style, UiRect, Val,
+    },
+    utils::default,
+};
+use bevy_rapier2d::prelude::ImpulseJoint;
+use std::time::Duration;
+use thetawave_interface::health::HealthComponent;
+
+use crate::{
+    assets::UiAssets,
+    spawnable::{find_controlling_mob, BossComponent, MobComponent, MobSegmentComponent},
+};
+
+use super::parent::BossUiChildBuilderExt;
+
+const BOSS_UI_WIDTH: Val = Val::Percent(90.0);
+const BOSS_UI_LEFT: Val = Val::Percent(5.0);
+const BOSS_UI_TOP: Val = Val::Percent(1.0);
+const BOSS_NAME_FONT_SIZE: f32 = 32.0;
+const BOSS_NAME_COLOR: Srgba = Srgba::WHITE;
+const BOSS_HEALTH_HEIGHT: Val = Val::Px(20.0);
+const BOSS_HEALTH_COLOR: Srgba = Srgba::RED;
+const BOSS_HEALTH_FLASH_COLOR: Srgba = Srgba::WHITE;
+const BOSS_HEALTH_TRAIL_COLOR: Srgba = Srgba::new(1.0, 0.8, 0.3, 1.0);
+const BOSS_HEALTH_EMPTY_ALPHA: f32 = 0.1;
+const BOSS_HEALTH_FILLED_ALPHA: f32 = 0.85;
+const SEGMENT_HEALTH_HEIGHT: Val = Val::Px(6.0);
+const SEGMENT_HEALTH_GAP: Val = Val::Px(4.0);
+const SEGMENT_HEALTH_MARGIN: UiRect = UiRect::top(Val::Px(4.0));
+/// Time in seconds that the health bar flashes after taking damage
+const FLASH_TIME: f32 = 0.15;
+/// Time in seconds after taking damage before the trail starts shrinking
+const TRAIL_DELAY: f32 = 0.5;
+/// Fraction of the health bar that the trail shrinks per second
+const TRAIL_SPEED: f32 = 0.4;
+
+/// Tracks the boss being displayed, and the state of the health bar animations
+#[derive(Component)]
+pub(super) struct BossHealthUi {
+    /// Boss being displayed
+    boss: Option<Entity>,
+    /// Largest combined maximum health of the boss and its segments
+    max_health: usize,
+    /// Combined health in the previous frame, used to detect damage
+    last_health: usize,
+    /// Fraction of the health bar covered by the trail of recently lost health
+    trail_percentage: f32,
+    /// Tracks time until the trail starts shrinking
+    trail_delay_timer: Timer,
+    /// Tracks time until the health bar stops flashing
+    flash_timer: Timer,
+    /// Destructible segments that have been attached to the boss, in the order they were found
+    segments: Vec<Entity>,
+}
+
+impl Default for BossHealthUi {
+    fn default() -> Self {
+        let mut flash_timer = Timer::from_seconds(FLASH_TIME, TimerMode::Once);
+        flash_timer.set_elapsed(Duration::from_secs_f32(FLASH_TIME));
+
+        Self {
+            boss: None,
+            max_health: 0,
+            last_health: 0,
+            trail_percentage: 1.0,
+            trail_delay_timer: Timer::from_seconds(TRAIL_DELAY, TimerMode::Once),
+            flash_timer,
+            segments: vec![],
+        }
+    }
+}
+
+impl BossUiChildBuilderExt for ChildBuilder<'_> {
+    fn spawn_boss_ui(&mut self) {
+        self.spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: BOSS_UI_TOP,
+                left: BOSS_UI_LEFT,
+                width: BOSS_UI_WIDTH,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .insert(BossHealthUi::default());
+    }
+}
+
+/// Rebuilds the boss health ui using the combined health of the boss and its attached segments
+#[allow(clippy::too_many_arguments)]
+pub(super) fn update_boss_health_ui_system(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    mut boss_ui_query: Query<(Entity, &mut BossHealthUi)>,
+    boss_query: Query<(Entity, &MobComponent, &HealthComponent), With<BossComponent>>,
+    mob_segment_query: Query<(Entity, &MobSegmentComponent, &HealthComponent)>,
+    joint_query: Query<&ImpulseJoint>,
+    time: Res<Time>,
+) {
+    let Ok((ui_entity, mut boss_ui)) = boss_ui_query.get_single_mut() else {
+        return;
+    };
+
+    commands.entity(ui_entity).despawn_descendants();
+
+    let Some((boss_entity, mob, boss_health)) = boss_query.iter().next() else {
+        *boss_ui = BossHealthUi::default();
+        return;
+    };
+
+    let attached_segments: Vec<(Entity, &MobSegmentComponent, &HealthComponent)> =
+        mob_segment_query
+            .iter()
+            .filter(|(entity, _, _)| {
+                find_controlling_mob(*entity, &joint_query, |mob_entity| {
+                    mob_entity == boss_entity
+                })
+                .is_some()
+            })
+            .collect();
+
+    let health = boss_health.get_health()
+        + attached_segments
+            .iter()
+            .map(|(_, _, health)| health.get_health())
+            .sum::<usize>();
+    let max_health = boss_health.get_max_health()
+        + attached_segments
+            .iter()
+            .map(|(_, _, health)| health.get_max_health())
+            .sum::<usize>();
+
+    // start tracking a new boss
+    if boss_ui.boss != Some(boss_entity) {
+        *boss_ui = BossHealthUi {
+            boss: Some(boss_entity),
+            last_health: health,
+            ..default()
+        };
+    }
+
+    // destroyed segments are kept so that their empty bars are still shown
+    for (entity, mob_segment, _) in attached_segments.iter() {
+        if mob_segment.is_destructible() && !boss_ui.segments.contains(entity) {
+            boss_ui.segments.push(*entity);
+        }
+    }
+
+    // the maximum health doesn't shrink when segments are destroyed
+    boss_ui.max_health = boss_ui.max_health.max(max_health);
+    let percentage = health as f32 / boss_ui.max_health.max(1) as f32;
+
+    // flash the health bar and hold the trail in place when damage is taken
+    boss_ui.flash_timer.tick(time.delta());
+    boss_ui.trail_delay_timer.tick(time.delta());
+    if health < boss_ui.last_health {
+        boss_ui.flash_timer.reset();
+        boss_ui.trail_delay_timer.reset();
+    }
+    boss_ui.last_health = health;
+
+    if boss_ui.trail_delay_timer.finished() {
+        boss_ui.trail_percentage -= TRAIL_SPEED * time.delta_seconds();
+    }
+    boss_ui.trail_percentage = boss_ui.trail_percentage.max(percentage);
+
+    let health_color =
+        BOSS_HEALTH_FLASH_COLOR.mix(&BOSS_HEALTH_COLOR, boss_ui.flash_timer.fraction());
+
+    let segment_percentages: Vec<f32> = boss_ui
+        .segments
+        .iter()
+        .map(|segment_entity| {
+            attached_segments
+                .iter()
+                .find(|(entity, _, _)| entity == segment_entity)
+                .map_or(0.0, |(_, _, health)| health.get_health_percentage())
+        })
+        .collect();
+
+    let font = ui_assets.lunchds_font.clone();
+    let trail_percentage = boss_ui.trail_percentage;
+
+    commands.entity(ui_entity).with_children(|boss_health_ui| {
+        boss_health_ui.spawn(TextBundle {
+            style: Style::default(),
+            text: Text::from_section(
+                mob.mob_type.get_name(),
+                TextStyle {
+                    font,
+                    font_size: BOSS_NAME_FONT_SIZE,
+                    color: Color::Srgba(BOSS_NAME_COLOR),
+                },
+            ),
+            ..default()
+        });
+
+        // combined health bar, with the trail of recently lost health behind it
+        boss_health_ui
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: BOSS_HEALTH_HEIGHT,
+                    ..default()
+                },
+                background_color: BOSS_HEALTH_COLOR.with_alpha(BOSS_HEALTH_EMPTY_ALPHA).into(),
+                ..default()
+            })
+            .with_children(|health_bar| {
+                for (bar_percentage, color) in [
+                    (trail_percentage, BOSS_HEALTH_TRAIL_COLOR),
+                    (percentage, health_color),
+                ] {
+                    health_bar.spawn(NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            width: Val::Percent(100.0 * bar_percentage),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        background_color: color.with_alpha(BOSS_HEALTH_FILLED_ALPHA).into(),
+                        ..default()
+                    });
+                }
+            });
+
+        // sub-bars for each destructible segment
+        if !segment_percentages.is_empty() {
+            boss_health_ui
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.0),
+                        height: SEGMENT_HEALTH_HEIGHT,
+                        margin: SEGMENT_HEALTH_MARGIN,
+                        flex_direction: FlexDirection::Row,
+                        column_gap: SEGMENT_HEALTH_GAP,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|segment_bars| {
+                    for segment_percentage in segment_percentages {
+                        segment_bars
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    flex_grow: 1.0,
+                                    height: Val::Percent(100.0),
+                                    ..default()
+                                },
+                                background_color: BOSS_HEALTH_COLOR
+                                    .with_alpha(BOSS_HEALTH_EMPTY_ALPHA)
+                                    .into(),
+                                ..default()
+                            })
+                            .with_children(|segment_bar| {
+                                segment_bar.spawn(NodeBundle {
+                                    style: Style {
+                                        width: Val::Percent(100.0 * segment_percentage),
+                                        height: Val::Percent(100.0),
+                                        ..default()
+                                    },
+                                    background_color: BOSS_HEALTH_COLOR
+                                        .with_alpha(BOSS_HEALTH_FILLED_ALPHA)
+                                        .into(),
+                                    ..default()
+                                });
+                            });
+                    }
+                });
+        }
+    });
+}
@@ -8,6 +8,7 @@ use thetawave_interface::states;
 use crate::GameEnterSet;
 
 mod border_gradient;
+mod boss;
 mod game_center;
 mod level;
 mod parent;
@@ -31,8 +32,12 @@ impl Plugin for GameUiPlugin {
                 player::update_player_health_ui_system,
                 player::update_player_shields_ui_system,
                 player::update_player_armor_ui_system,
+                player::update_player_lives_ui_system,
                 player::update_player_abilities_ui_system,
+                player::update_player_ability_charge_ui_system,
+                player::update_player_ability_levels_ui_system,
                 phase::update_phase_ui_system,
+                boss::update_boss_health_ui_system,
                 level::update_level_ui_system,
                 game_center::update_center_text_ui_system,
                 game_center::text_fade_out_system,
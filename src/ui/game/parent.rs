@@ -42,6 +42,10 @@ pub(super) trait PhaseUiChildBuilderExt {
     fn spawn_phase_ui(&mut self, font: Handle<Font>);
 }
 
+pub(super) trait BossUiChildBuilderExt {
+    fn spawn_boss_ui(&mut self);
+}
+
 pub(super) trait LevelUiChildBuilderExt {
     fn spawn_level_ui(&mut self, font: Handle<Font>);
 }
@@ -73,7 +77,14 @@ pub(super) trait PlayerUiChildBuilderExt {
         is_flipped: bool,
         ui_assets: &UiAssets,
     );
+    fn spawn_player_ability_level_ui(
+        &mut self,
+        player_id: PlayerIDComponent,
+        ability_slot_id: AbilitySlotIDComponent,
+    );
+    fn spawn_player_ability_level_counter_ui(&mut self, is_reached: bool);
     fn spawn_player_armor_counter_ui(&mut self);
+    fn spawn_player_life_counter_ui(&mut self);
     fn spawn_player_ability_icon_ui(
         &mut self,
         player_id: PlayerIDComponent,
@@ -182,13 +193,15 @@ pub(super) fn setup_game_ui_system(
                         ..default()
                     })
                     .with_children(|middle_left| {
-                        // Player 1 Ui on the left
-                        middle_left.spawn_player_ui(
-                            &characters_resource,
-                            PlayerIDComponent::One,
-                            &players_resource,
-                            &ui_assets,
-                        );
+                        // Player 1 and 3 Uis on the left
+                        for id in [PlayerIDComponent::One, PlayerIDComponent::Three] {
+                            middle_left.spawn_player_ui(
+                                &characters_resource,
+                                id,
+                                &players_resource,
+                                &ui_assets,
+                            );
+                        }
                     });
 
                 // Middle column over the top of the arena
@@ -204,11 +217,14 @@ pub(super) fn setup_game_ui_system(
                         ..default()
                     })
                     .with_children(|middle_center| {
+                        // spawn the boss health ui across the top of the arena
+                        middle_center.spawn_boss_ui();
+
                         // spawn the ui for displaying messages in the center of the game
                         middle_center.spawn_game_center_ui(font.clone());
                     });
 
-                // Left column on the left side of window excluding the corners
+                // Right column on the right side of window excluding the corners
                 middle_row
                     .spawn(NodeBundle {
                         style: Style {
@@ -221,13 +237,15 @@ pub(super) fn setup_game_ui_system(
                         ..default()
                     })
                     .with_children(|middle_right| {
-                        // Player 1 Ui onm the left
-                        middle_right.spawn_player_ui(
-                            &characters_resource,
-                            PlayerIDComponent::Two,
-                            &players_resource,
-                            &ui_assets,
-                        );
+                        // Player 2 and 4 Uis on the right
+                        for id in [PlayerIDComponent::Two, PlayerIDComponent::Four] {
+                            middle_right.spawn_player_ui(
+                                &characters_resource,
+                                id,
+                                &players_resource,
+                                &ui_assets,
+                            );
+                        }
                     });
             });
 
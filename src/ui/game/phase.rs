@@ -1,6 +1,6 @@
 use bevy::{
     asset::Handle,
-    color::{Alpha, Color, Srgba},
+    color::{Color, Srgba},
     ecs::{
         component::Component,
         entity::Entity,
@@ -15,7 +15,6 @@ use bevy::{
     },
     utils::default,
 };
-use thetawave_interface::health::HealthComponent;
 
 use crate::run::tutorial::TutorialLesson;
 use crate::{assets::UiAssets, run::level_phase::LevelPhaseType};
@@ -30,11 +29,6 @@ const TUTORIAL_FONT_SIZE: f32 = 24.0;
 const FONT_SIZE: f32 = 48.0;
 const PHASE_DATA_PADDING: UiRect =
     UiRect::new(Val::Vw(1.0), Val::Vw(1.0), Val::Vh(2.0), Val::Vh(2.0));
-const BOSS_HEALTH_WIDTH: Val = Val::Percent(80.0);
-const BOSS_HEALTH_HEIGHT: Val = Val::Percent(60.0);
-const BOSS_HEALTH_COLOR: Srgba = Srgba::RED;
-const BOSS_HEALTH_EMPTY_ALPHA: f32 = 0.05;
-const BOSS_HEALTH_FILLED_ALPHA: f32 = 0.75;
 const TUTORIAL_TEXT_SECTION_HEIGHT: Val = Val::Px(30.0);
 
 /// Used for querying UI for displaying name
@@ -107,7 +101,7 @@ pub(super) fn update_phase_ui_system(
     mut phase_name_ui_query: Query<&mut Text, With<PhaseNameUi>>,
     phase_data_ui_query: Query<Entity, With<PhaseDataUi>>,
     run_resource: Res<CurrentRunProgressResource>,
-    boss_mobs_query: Query<&HealthComponent, With<BossComponent>>,
+    boss_mobs_query: Query<&BossComponent>,
 ) {
     if let Some(current_level) = &run_resource.current_level {
         if let Some(current_phase) = &current_level.current_phase {
@@ -156,37 +150,26 @@ pub(super) fn update_phase_ui_system(
                         });
                     }
                     LevelPhaseType::Boss { .. } => {
-                        if let Ok(health) = boss_mobs_query.get_single() {
-                            commands.entity(entity).with_children(|phase_data_ui| {
-                                phase_data_ui
-                                    .spawn(NodeBundle {
-                                        style: Style {
-                                            width: BOSS_HEALTH_WIDTH,
-                                            height: BOSS_HEALTH_HEIGHT,
-                                            flex_direction: FlexDirection::Row,
-                                            ..default()
-                                        },
-                                        background_color: BOSS_HEALTH_COLOR
-                                            .with_alpha(BOSS_HEALTH_EMPTY_ALPHA)
-                                            .into(),
-                                        ..default()
-                                    })
-                                    .with_children(|boss_health_ui| {
-                                        boss_health_ui.spawn(NodeBundle {
-                                            style: Style {
-                                                width: Val::Percent(
-                                                    100.0 * health.get_health_percentage(),
-                                                ),
-                                                height: Val::Percent(100.0),
-                                                ..default()
+                        // the health of the boss is shown in the boss ui, only show the phase here
+                        if let Ok(boss) = boss_mobs_query.get_single() {
+                            if boss.phase_count() > 1 {
+                                let font = ui_assets.lunchds_font.clone();
+
+                                commands.entity(entity).with_children(|phase_data_ui| {
+                                    phase_data_ui.spawn(TextBundle {
+                                        style: Style::default(),
+                                        text: Text::from_section(
+                                            format!("{}/{}", boss.phase + 1, boss.phase_count()),
+                                            TextStyle {
+                                                font,
+                                                font_size: FONT_SIZE,
+                                                color: Color::Srgba(NORMAL_TEXT_COLOR),
                                             },
-                                            background_color: BOSS_HEALTH_COLOR
-                                                .with_alpha(BOSS_HEALTH_FILLED_ALPHA)
-                                                .into(),
-                                            ..default()
-                                        });
+                                        ),
+                                        ..default()
                                     });
-                            });
+                                });
+                            }
                         }
                     }
                     LevelPhaseType::Tutorial {
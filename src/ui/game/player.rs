@@ -1,7 +1,7 @@
 use bevy::{
     asset::Handle,
     color::{
-        palettes::css::{AQUA, CRIMSON, GOLD},
+        palettes::css::{AQUA, CRIMSON, GOLD, WHITE},
         Alpha, Srgba,
     },
     ecs::{
@@ -10,19 +10,22 @@ use bevy::{
         query::{Changed, With},
         system::{Commands, Query},
     },
-    hierarchy::{BuildChildren, ChildBuilder, Children, DespawnRecursiveExt},
+    hierarchy::{BuildChildren, ChildBuilder, Children, DespawnRecursiveExt, Parent},
     render::texture::Image,
     ui::{
         node_bundles::{ImageBundle, NodeBundle},
-        FlexDirection, Style, UiRect, Val,
+        FlexDirection, FlexWrap, PositionType, Style, UiRect, Val,
     },
     utils::default,
 };
 use thetawave_interface::{
-    abilities::{AbilityCooldownComponent, AbilitySlotIDComponent},
+    abilities::{
+        AbilityCooldownComponent, AbilityInputComponent, AbilityLevelComponent,
+        AbilitySlotIDComponent,
+    },
     character::Character,
     health::HealthComponent,
-    player::{PlayerComponent, PlayerIDComponent, PlayersResource},
+    player::{PlayerComponent, PlayerIDComponent, PlayerInventoryComponent, PlayersResource},
 };
 
 use crate::{assets::UiAssets, player::CharactersResource};
@@ -53,7 +56,18 @@ const ARMOR_COUNTER_MARGIN: UiRect =
     UiRect::new(Val::Px(0.0), Val::Px(0.0), Val::Vh(0.1), Val::Vh(0.1));
 const ARMOR_COUNTER_COLOR: Srgba = GOLD;
 const ARMOR_COUNTER_ALPHA: f32 = 0.75;
+const LIFE_COUNTER_WIDTH: Val = Val::Percent(20.0);
+const LIFE_COUNTER_MARGIN: UiRect = UiRect::all(Val::Percent(2.5));
+const LIFE_COUNTER_COLOR: Srgba = WHITE;
+const LIFE_COUNTER_ALPHA: f32 = 0.75;
 const ABILITY_VALUE_COLOR: Srgba = Srgba::new(0.0, 0.0, 0.0, 0.85);
+const ABILITY_CHARGE_HEIGHT: Val = Val::Percent(12.0);
+const ABILITY_CHARGE_COLOR: Srgba = Srgba::new(1.0, 1.0, 1.0, 0.75);
+const ABILITY_LEVEL_COUNTER_WIDTH: Val = Val::Percent(15.0);
+const ABILITY_LEVEL_COUNTER_MARGIN: UiRect = UiRect::all(Val::Percent(2.5));
+const ABILITY_LEVEL_COUNTER_COLOR: Srgba = GOLD;
+const ABILITY_LEVEL_COUNTER_EMPTY_ALPHA: f32 = 0.05;
+const ABILITY_LEVEL_COUNTER_FILLED_ALPHA: f32 = 0.75;
 
 // Player data Uis
 #[derive(Component)]
@@ -74,6 +88,12 @@ pub(super) struct ArmorUi;
 #[derive(Component)]
 pub(super) struct ArmorCounterUi;
 
+#[derive(Component)]
+pub(super) struct LivesUi;
+
+#[derive(Component)]
+pub(super) struct LifeCounterUi;
+
 #[derive(Component)]
 pub(super) struct AbilitySlotUi;
 
@@ -83,6 +103,15 @@ pub(super) struct AbilityIconUi;
 #[derive(Component)]
 pub(super) struct AbilityValueUi;
 
+#[derive(Component)]
+pub(super) struct AbilityChargeUi;
+
+#[derive(Component)]
+pub(super) struct AbilityLevelUi;
+
+#[derive(Component)]
+pub(super) struct AbilityLevelCounterUi;
+
 #[derive(Component)]
 pub(super) struct PlayerUi;
 
@@ -100,8 +129,8 @@ impl PlayerIDComponentExt for PlayerIDComponent {
     /// Determines whether ui should be flipped based on the player ID
     fn has_flipped_ui(&self) -> bool {
         match self {
-            PlayerIDComponent::One => false,
-            PlayerIDComponent::Two => true,
+            PlayerIDComponent::One | PlayerIDComponent::Three => false,
+            PlayerIDComponent::Two | PlayerIDComponent::Four => true,
         }
     }
 }
@@ -122,11 +151,23 @@ impl PlayerUiChildBuilderExt for ChildBuilder<'_> {
                 .get(&player_data.character)
                 .unwrap();
 
+            // Players on the same side of the window split its height
+            let side_player_count = players_res
+                .player_data
+                .iter()
+                .enumerate()
+                .filter(|(idx, player_data)| {
+                    player_data.is_some()
+                        && PlayerIDComponent::try_from(*idx)
+                            .is_ok_and(|other_id| other_id.has_flipped_ui() == id.has_flipped_ui())
+                })
+                .count();
+
             // Parent player ui node
             self.spawn(NodeBundle {
                 style: Style {
                     width: Val::Percent(100.0),
-                    height: Val::Percent(100.0),
+                    height: Val::Percent(100.0 / side_player_count.max(1) as f32),
                     flex_direction: FlexDirection::Row,
                     ..default()
                 },
@@ -246,7 +287,7 @@ impl PlayerUiChildBuilderExt for ChildBuilder<'_> {
         })
         .insert(PlayerOuterUi)
         .with_children(|outer| {
-            // First and bottom ability slot
+            // First and bottom ability slot, with its level above it
             outer.spawn_player_ability_slot_ui(
                 character,
                 id,
@@ -254,8 +295,9 @@ impl PlayerUiChildBuilderExt for ChildBuilder<'_> {
                 id.has_flipped_ui(),
                 ui_assets,
             );
+            outer.spawn_player_ability_level_ui(id, AbilitySlotIDComponent::One);
 
-            // Second and top ability slot
+            // Second ability slot, with its level above it
             outer.spawn_player_ability_slot_ui(
                 character,
                 id,
@@ -263,6 +305,25 @@ impl PlayerUiChildBuilderExt for ChildBuilder<'_> {
                 id.has_flipped_ui(),
                 ui_assets,
             );
+            outer.spawn_player_ability_level_ui(id, AbilitySlotIDComponent::Two);
+
+            // Remaining lives above the ability slots
+            outer
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.0),
+                        flex_direction: if id.has_flipped_ui() {
+                            FlexDirection::RowReverse
+                        } else {
+                            FlexDirection::Row
+                        },
+                        flex_wrap: FlexWrap::Wrap,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .insert(LivesUi)
+                .insert(id);
         });
     }
 
@@ -343,9 +404,69 @@ impl PlayerUiChildBuilderExt for ChildBuilder<'_> {
                 .insert(player_id)
                 .insert(ability_slot_id)
                 .insert(AbilityValueUi);
+
+            // Meter along the bottom of the icon that fills while a hold-to-charge ability charges
+            ability_icon
+                .spawn(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        bottom: Val::Px(0.0),
+                        width: Val::Percent(0.0),
+                        height: ABILITY_CHARGE_HEIGHT,
+                        ..default()
+                    },
+                    background_color: ABILITY_CHARGE_COLOR.into(),
+                    ..default()
+                })
+                .insert(player_id)
+                .insert(ability_slot_id)
+                .insert(AbilityChargeUi);
         });
     }
 
+    fn spawn_player_ability_level_ui(
+        &mut self,
+        player_id: PlayerIDComponent,
+        ability_slot_id: AbilitySlotIDComponent,
+    ) {
+        self.spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                flex_direction: if player_id.has_flipped_ui() {
+                    FlexDirection::RowReverse
+                } else {
+                    FlexDirection::Row
+                },
+                flex_wrap: FlexWrap::Wrap,
+                ..default()
+            },
+            ..default()
+        })
+        .insert(AbilityLevelUi)
+        .insert(ability_slot_id)
+        .insert(player_id);
+    }
+
+    fn spawn_player_ability_level_counter_ui(&mut self, is_reached: bool) {
+        self.spawn(NodeBundle {
+            style: Style {
+                width: ABILITY_LEVEL_COUNTER_WIDTH,
+                aspect_ratio: Some(1.0),
+                margin: ABILITY_LEVEL_COUNTER_MARGIN,
+                ..default()
+            },
+            background_color: ABILITY_LEVEL_COUNTER_COLOR
+                .with_alpha(if is_reached {
+                    ABILITY_LEVEL_COUNTER_FILLED_ALPHA
+                } else {
+                    ABILITY_LEVEL_COUNTER_EMPTY_ALPHA
+                })
+                .into(),
+            ..default()
+        })
+        .insert(AbilityLevelCounterUi);
+    }
+
     fn spawn_player_armor_counter_ui(&mut self) {
         self.spawn(NodeBundle {
             style: Style {
@@ -359,6 +480,20 @@ impl PlayerUiChildBuilderExt for ChildBuilder<'_> {
         })
         .insert(ArmorCounterUi);
     }
+
+    fn spawn_player_life_counter_ui(&mut self) {
+        self.spawn(NodeBundle {
+            style: Style {
+                width: LIFE_COUNTER_WIDTH,
+                aspect_ratio: Some(1.0),
+                margin: LIFE_COUNTER_MARGIN,
+                ..default()
+            },
+            background_color: LIFE_COUNTER_COLOR.with_alpha(LIFE_COUNTER_ALPHA).into(),
+            ..default()
+        })
+        .insert(LifeCounterUi);
+    }
 }
 
 pub(super) fn update_player_abilities_ui_system(
@@ -388,6 +523,71 @@ pub(super) fn update_player_abilities_ui_system(
     }
 }
 
+/// Updates the charge meters of each player's hold-to-charge abilities
+pub(super) fn update_player_ability_charge_ui_system(
+    player_query: Query<(&Children, &PlayerIDComponent), With<PlayerComponent>>,
+    player_ability_query: Query<(&AbilityInputComponent, &AbilitySlotIDComponent)>,
+    mut ability_charge_ui_query: Query<
+        (&mut Style, &AbilitySlotIDComponent, &PlayerIDComponent),
+        With<AbilityChargeUi>,
+    >,
+) {
+    for (mut style, ui_ability_slot_id, ability_slot_player_id) in
+        ability_charge_ui_query.iter_mut()
+    {
+        for (player_children, player_id) in player_query.iter() {
+            if *player_id == *ability_slot_player_id {
+                for child in player_children.iter() {
+                    if let Ok((ability_input, ability_slot_id)) = player_ability_query.get(*child) {
+                        if *ability_slot_id == *ui_ability_slot_id {
+                            style.width = Val::Percent(100.0 * ability_input.get_charge_fraction());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Updates the level counters above each player's ability slots when their abilities level up
+/// Abilities without upgrades have no counters
+pub(super) fn update_player_ability_levels_ui_system(
+    mut commands: Commands,
+    player_query: Query<&PlayerIDComponent, With<PlayerComponent>>,
+    ability_query: Query<
+        (&AbilityLevelComponent, &AbilitySlotIDComponent, &Parent),
+        Changed<AbilityLevelComponent>,
+    >,
+    ability_level_ui: Query<
+        (Entity, &AbilitySlotIDComponent, &PlayerIDComponent),
+        With<AbilityLevelUi>,
+    >,
+) {
+    for (ability_level, ability_slot_id, parent) in ability_query.iter() {
+        let Ok(player_id) = player_query.get(parent.get()) else {
+            continue;
+        };
+
+        for (entity, ui_ability_slot_id, ui_player_id) in ability_level_ui.iter() {
+            if player_id == ui_player_id && ability_slot_id == ui_ability_slot_id {
+                // despawn all of the existing level counters
+                commands.entity(entity).despawn_descendants();
+
+                // spawn a counter for each level, filled for the levels reached
+                if ability_level.max_level() > 1 {
+                    commands.entity(entity).with_children(|ability_level_ui| {
+                        for level in 1..=ability_level.max_level() {
+                            ability_level_ui.spawn_player_ability_level_counter_ui(
+                                level <= ability_level.level,
+                            );
+                        }
+                    });
+                }
+            }
+        }
+    }
+}
+
 /// Updates each player's health bar ui
 pub(super) fn update_player_health_ui_system(
     player_query: Query<(&HealthComponent, &PlayerIDComponent), Changed<HealthComponent>>,
@@ -438,3 +638,29 @@ pub(super) fn update_player_armor_ui_system(
         }
     }
 }
+
+/// Updates each player's remaining lives ui
+pub(super) fn update_player_lives_ui_system(
+    mut commands: Commands,
+    player_query: Query<
+        (&PlayerInventoryComponent, &PlayerIDComponent),
+        Changed<PlayerInventoryComponent>,
+    >,
+    lives_ui: Query<(Entity, &PlayerIDComponent), With<LivesUi>>,
+) {
+    for (player_inventory, player_id) in player_query.iter() {
+        for (entity, lives_id) in lives_ui.iter() {
+            if player_id == lives_id {
+                // despawn all of the existing life counters
+                commands.entity(entity).despawn_descendants();
+
+                // spawn a counter for each remaining life
+                commands.entity(entity).with_children(|lives_ui| {
+                    for _ in 0..player_inventory.lives.unwrap_or(0) {
+                        lives_ui.spawn_player_life_counter_ui();
+                    }
+                });
+            }
+        }
+    }
+}
@@ -3,7 +3,9 @@ use bevy::{
     app::{App, Plugin, Update},
     prelude::{Component, OnEnter, Query, Res, Time, Timer, Transform},
 };
-use thetawave_interface::game::historical_metrics::{MobsKilledByPlayerCacheT, DEFAULT_USER_ID};
+use thetawave_interface::game::historical_metrics::{
+    MobsKilledByPlayerCacheT, UserStatsByPlayerCacheT, DEFAULT_USER_ID,
+};
 
 use thetawave_interface::states;
 
@@ -60,6 +62,17 @@ fn pprint_mob_kills_from_data(data: &MobsKilledByPlayerCacheT) -> String {
     }
 }
 
+/// Co-op downs and revives, empty if no players were downed
+fn pprint_downs_from_data(data: &UserStatsByPlayerCacheT) -> String {
+    match (*data).get(&DEFAULT_USER_ID) {
+        Some(user_stat) if user_stat.total_downs > 0 => format!(
+            "Players downed: {}\nRevives: {}\n",
+            user_stat.total_downs, user_stat.total_revives
+        ),
+        _ => String::new(),
+    }
+}
+
 /// A component that will cause the assocaited entity to bounce up and down at a rate determined by
 /// `Self::flash_timer.duration` while `Self::is_active`.
 #[derive(Component)]
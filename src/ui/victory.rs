@@ -114,9 +114,10 @@ pub fn setup_victory_system(
 
                                 text: Text::from_section(
                                     format!(
-                                        "Projectiles fired: {}\nAccuracy: {:.2}%\n\nEnemies destroyed:\n{}",
+                                        "Projectiles fired: {}\nAccuracy: {:.2}%\n{}\nEnemies destroyed:\n{}",
                                         total_shots_fired,
                                         accuracy_rate,
+                                        super::pprint_downs_from_data(&current_game_shot_counts),
                                         super::pprint_mob_kills_from_data(
                                             &current_game_enemy_mob_kill_counts
                                         ),
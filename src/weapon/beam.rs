@@ -0,0 +1,321 @@
+//! Beams fired by weapons, which are ray cast from their source and damage the first hostile target hit
+use bevy::{
+    color::{Alpha, Color},
+    core::Name,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventReader, EventWriter},
+        query::{With, Without},
+        system::{Commands, Query, Res},
+    },
+    hierarchy::DespawnRecursiveExt,
+    math::{EulerRot, Quat, Vec2, Vec3Swizzles},
+    prelude::default,
+    sprite::{Anchor, Sprite, SpriteBundle},
+    time::{Time, Timer, TimerMode},
+    transform::components::Transform,
+};
+use bevy_rapier2d::{pipeline::QueryFilter, plugin::RapierContext};
+use thetawave_interface::{
+    audio::PlaySoundEffectEvent,
+    health::DamageDealtEvent,
+    player::PlayerComponent,
+    spawnable::{Faction, SpawnPosition},
+    states::GameCleanup,
+    weapon::BeamData,
+};
+
+use crate::{
+    arena::ArenaBarrierComponent,
+    spawnable::{MobComponent, MobSegmentComponent},
+};
+
+/// Z level of beam sprites
+const BEAM_Z_LEVEL: f32 = 18.0;
+/// Width of the beam during warm up, relative to its full width
+const BEAM_WARMUP_WIDTH: f32 = 0.2;
+
+/// Event for firing a beam from a source entity
+#[derive(Event, Clone)]
+pub(crate) struct FireBeamEvent {
+    /// Data about the beam that is fired
+    pub beam_data: BeamData,
+    /// Entity that the beam is fired from, the beam follows it until it ends
+    pub source_entity: Entity,
+}
+
+/// Phases of a beam's lifetime
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum BeamPhase {
+    WarmUp,
+    Sustain,
+    CoolDown,
+}
+
+/// Core component for beams
+#[derive(Component)]
+pub(super) struct BeamComponent {
+    /// Data the beam was fired with
+    data: BeamData,
+    /// Entity that fired the beam
+    source: Entity,
+    /// Current phase of the beam
+    phase: BeamPhase,
+    /// Tracks the time remaining in the current phase
+    phase_timer: Timer,
+    /// Tracks time between damage ticks
+    tick_timer: Timer,
+}
+
+impl BeamComponent {
+    fn new(data: BeamData, source: Entity) -> Self {
+        BeamComponent {
+            phase: BeamPhase::WarmUp,
+            phase_timer: Timer::from_seconds(data.warmup_time, TimerMode::Once),
+            tick_timer: Timer::from_seconds(data.tick_period, TimerMode::Repeating),
+            data,
+            source,
+        }
+    }
+
+    /// Advance to the next phase when the current phase is finished
+    /// Returns false when the beam has finished cooling down
+    fn update_phase(&mut self) -> bool {
+        if !self.phase_timer.finished() {
+            return true;
+        }
+
+        match self.phase {
+            BeamPhase::WarmUp => {
+                self.phase = BeamPhase::Sustain;
+                self.phase_timer = Timer::from_seconds(self.data.sustain_time, TimerMode::Once);
+                true
+            }
+            BeamPhase::Sustain => {
+                self.phase = BeamPhase::CoolDown;
+                self.phase_timer = Timer::from_seconds(self.data.cooldown_time, TimerMode::Once);
+                true
+            }
+            BeamPhase::CoolDown => false,
+        }
+    }
+
+    /// Width of the beam for the current phase
+    fn current_width(&self) -> f32 {
+        match self.phase {
+            BeamPhase::WarmUp => self.data.width * BEAM_WARMUP_WIDTH,
+            BeamPhase::Sustain => self.data.width,
+            BeamPhase::CoolDown => self.data.width * (1.0 - self.phase_timer.fraction()),
+        }
+    }
+}
+
+/// Color of beams fired by each faction
+fn beam_color(faction: Faction) -> Color {
+    match faction {
+        Faction::Ally => Color::srgb(1.5, 3.0, 4.0),
+        Faction::Enemy => Color::srgb(4.0, 1.2, 1.2),
+        Faction::Neutral => Color::srgb(3.0, 3.0, 3.0),
+    }
+}
+
+/// Spawn beams from events
+pub(super) fn spawn_beam_system(
+    mut commands: Commands,
+    mut fire_beam_event_reader: EventReader<FireBeamEvent>,
+    mut sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+) {
+    for event in fire_beam_event_reader.read() {
+        sound_effect_event_writer.send(PlaySoundEffectEvent {
+            sound_effect_type: event.beam_data.sound,
+        });
+
+        let mut color = beam_color(event.beam_data.faction);
+        color.set_alpha(0.5);
+
+        // the transform and size of the beam are set every frame in `update_beam_system`
+        commands
+            .spawn(SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    anchor: Anchor::CenterLeft,
+                    custom_size: Some(Vec2::ZERO),
+                    ..default()
+                },
+                ..default()
+            })
+            .insert(BeamComponent::new(
+                event.beam_data.clone(),
+                event.source_entity,
+            ))
+            .insert(GameCleanup)
+            .insert(Name::new("Beam"));
+    }
+}
+
+/// Update the phases of beams, ray cast them from their source, and damage the first target hit
+#[allow(clippy::too_many_arguments)]
+pub(super) fn update_beam_system(
+    mut commands: Commands,
+    mut beam_query: Query<(Entity, &mut BeamComponent, &mut Transform, &mut Sprite)>,
+    source_query: Query<&Transform, Without<BeamComponent>>,
+    player_query: Query<(), With<PlayerComponent>>,
+    mob_query: Query<&MobComponent>,
+    mob_segment_query: Query<&MobSegmentComponent>,
+    barrier_query: Query<(), With<ArenaBarrierComponent>>,
+    rapier_context: Res<RapierContext>,
+    mut damage_dealt_event_writer: EventWriter<DamageDealtEvent>,
+    time: Res<Time>,
+) {
+    for (entity, mut beam, mut transform, mut sprite) in beam_query.iter_mut() {
+        beam.phase_timer.tick(time.delta());
+
+        // beams end when they finish cooling down or their source is destroyed
+        let Ok(source_transform) = source_query.get(beam.source) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+        if !beam.update_phase() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        // find the start and direction of the beam using the source's transform
+        let source_rotation = source_transform.rotation.to_euler(EulerRot::ZYX).0;
+        let origin = match beam.data.position {
+            SpawnPosition::Global(pos) => pos,
+            SpawnPosition::Local(pos) => {
+                source_transform.translation.xy()
+                    + (source_transform.rotation * pos.extend(0.0)).xy()
+            }
+        };
+        let angle = beam.data.direction + source_rotation;
+        let direction = Vec2::from_angle(angle);
+
+        // the beam passes through entities that its faction isn't hostile to, such as neutral mobs,
+        // and is stopped by hostile players and mobs, as well as arena barriers
+        let faction = beam.data.faction;
+        let blocks_beam = |hit_entity: Entity| {
+            if player_query.contains(hit_entity) {
+                faction.is_hostile_to(&Faction::Ally)
+            } else if let Ok(mob) = mob_query.get(hit_entity) {
+                faction.is_hostile_to(&mob.mob_type.get_faction())
+            } else if let Ok(mob_segment) = mob_segment_query.get(hit_entity) {
+                faction.is_hostile_to(&mob_segment.mob_segment_type.get_faction())
+            } else {
+                barrier_query.contains(hit_entity)
+            }
+        };
+        let filter = QueryFilter::new()
+            .exclude_sensors()
+            .exclude_rigid_body(beam.source)
+            .predicate(&blocks_beam);
+
+        let hit = rapier_context.cast_ray(origin, direction, beam.data.length, true, filter);
+        let length = hit.map_or(beam.data.length, |(_, distance)| distance);
+
+        // deal damage to the target each tick while the beam is sustained
+        if beam.phase == BeamPhase::Sustain {
+            beam.tick_timer.tick(time.delta());
+            let ticks = beam.tick_timer.times_finished_this_tick();
+
+            if let Some((target, _)) = hit {
+                if ticks > 0 && beam.data.damage > 0 && !barrier_query.contains(target) {
+                    damage_dealt_event_writer.send(DamageDealtEvent {
+                        damage: beam.data.damage * ticks as usize,
+                        target,
+                        source: Some(beam.source),
+                        damage_type: beam.data.damage_type,
+                        is_critical: false,
+                    });
+                }
+            }
+        }
+
+        // stretch the sprite from the origin of the beam to the hit point
+        *transform = Transform {
+            translation: origin.extend(BEAM_Z_LEVEL),
+            rotation: Quat::from_rotation_z(angle),
+            ..default()
+        };
+        sprite.custom_size = Some(Vec2::new(length, beam.current_width()));
+        sprite.color.set_alpha(match beam.phase {
+            BeamPhase::WarmUp => 0.5,
+            _ => 1.0,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::{ecs::entity::Entity, math::Vec2};
+    use std::time::Duration;
+    use thetawave_interface::{
+        audio::SoundEffectType,
+        health::DamageType,
+        spawnable::{Faction, SpawnPosition},
+        weapon::BeamData,
+    };
+
+    use super::{BeamComponent, BeamPhase, BEAM_WARMUP_WIDTH};
+
+    fn beam_data() -> BeamData {
+        BeamData {
+            faction: Faction::Enemy,
+            damage: 2,
+            damage_type: DamageType::Energy,
+            tick_period: 0.1,
+            position: SpawnPosition::Local(Vec2::ZERO),
+            direction: 0.0,
+            length: 800.0,
+            width: 6.0,
+            warmup_time: 1.0,
+            sustain_time: 2.0,
+            cooldown_time: 0.5,
+            sound: SoundEffectType::EnemyFireBlast,
+        }
+    }
+
+    /// Tick the beam's phase timer and advance its phase, as `update_beam_system` does
+    fn tick(beam: &mut BeamComponent, seconds: f32) -> bool {
+        beam.phase_timer.tick(Duration::from_secs_f32(seconds));
+        beam.update_phase()
+    }
+
+    #[test]
+    fn test_beam_progresses_through_phases() {
+        let mut beam = BeamComponent::new(beam_data(), Entity::PLACEHOLDER);
+        assert_eq!(beam.phase, BeamPhase::WarmUp);
+
+        assert!(tick(&mut beam, 0.75));
+        assert_eq!(beam.phase, BeamPhase::WarmUp);
+        assert!(tick(&mut beam, 0.25));
+        assert_eq!(beam.phase, BeamPhase::Sustain);
+
+        assert!(tick(&mut beam, 1.75));
+        assert_eq!(beam.phase, BeamPhase::Sustain);
+        assert!(tick(&mut beam, 0.25));
+        assert_eq!(beam.phase, BeamPhase::CoolDown);
+
+        assert!(tick(&mut beam, 0.25));
+        assert_eq!(beam.phase, BeamPhase::CoolDown);
+        assert!(!tick(&mut beam, 0.25));
+    }
+
+    #[test]
+    fn test_beam_width_follows_phase() {
+        let mut beam = BeamComponent::new(beam_data(), Entity::PLACEHOLDER);
+        assert_eq!(beam.current_width(), 6.0 * BEAM_WARMUP_WIDTH);
+
+        tick(&mut beam, 1.0);
+        assert_eq!(beam.current_width(), 6.0);
+
+        tick(&mut beam, 2.0);
+        assert_eq!(beam.current_width(), 6.0);
+
+        // the beam narrows as it cools down
+        tick(&mut beam, 0.25);
+        assert!((beam.current_width() - 3.0).abs() < 1e-4);
+    }
+}
@@ -13,23 +13,48 @@ use bevy::{
     transform::components::Transform,
 };
 use bevy_rapier2d::dynamics::Velocity;
-use std::time::Duration;
+use std::{f32::consts::TAU, time::Duration};
 use thetawave_interface::{
     states::{AppStates, GameStates},
+    status_effect::StatusEffectsComponent,
     weapon::{FireMode, SpreadPattern, Weapon, WeaponProjectileData, WeaponsComponent},
 };
 
 use crate::spawnable::{FireWeaponEvent, InitialMotion};
 use rand::{thread_rng, Rng};
+use ron::de::from_bytes;
 
-/// Emits `FireWeaponEvent`s at the proper times.
+mod beam;
+mod pattern;
+
+pub(crate) use self::beam::FireBeamEvent;
+use self::beam::{spawn_beam_system, update_beam_system};
+pub(crate) use self::pattern::AttackPatternComponent;
+use self::pattern::{attack_pattern_system, AttackPatternsResource};
+
+/// Emits `FireWeaponEvent`s and `FireBeamEvent`s at the proper times, and updates fired beams.
+/// Also runs the attack patterns defined in `attack_patterns.ron`.
 pub(super) struct WeaponPlugin;
 
 impl Plugin for WeaponPlugin {
     fn build(&self, app: &mut App) {
+        app.insert_resource(
+            from_bytes::<AttackPatternsResource>(include_bytes!(
+                "../../assets/data/attack_patterns.ron"
+            ))
+            .expect("Failed to parse AttackPatternsResource from 'attack_patterns.ron'"),
+        );
+
+        app.add_event::<FireBeamEvent>();
+
         app.add_systems(
             Update,
-            update_weapon_system
+            (
+                update_weapon_system,
+                attack_pattern_system,
+                spawn_beam_system.after(update_weapon_system),
+                update_beam_system.after(spawn_beam_system),
+            )
                 .run_if(in_state(AppStates::Game))
                 .run_if(in_state(GameStates::Playing)),
         );
@@ -64,12 +89,26 @@ impl WeaponExt for Weapon {
 }
 
 /// Update all weapons, and fire weapons with the automatic fire mode
+/// Weapons of stunned entities are not updated
 fn update_weapon_system(
-    mut weapon_query: Query<(Entity, &mut WeaponsComponent, &Transform, &Velocity)>,
+    mut weapon_query: Query<(
+        Entity,
+        &mut WeaponsComponent,
+        &Transform,
+        &Velocity,
+        Option<&StatusEffectsComponent>,
+    )>,
     time: Res<Time>,
     mut fire_weapon: EventWriter<FireWeaponEvent>,
+    mut fire_beam: EventWriter<FireBeamEvent>,
 ) {
-    for (entity, mut weapon_component, transform, velocity) in weapon_query.iter_mut() {
+    for (entity, mut weapon_component, transform, velocity, status_effects) in
+        weapon_query.iter_mut()
+    {
+        if status_effects.is_some_and(|status_effects| status_effects.is_stunned()) {
+            continue;
+        }
+
         for weapon in weapon_component.weapons.iter_mut() {
             if let Some(weapon_projectile_data) = weapon.update(time.delta()) {
                 // pass velocity into the spawned blast
@@ -86,6 +125,15 @@ fn update_weapon_system(
                 });
             }
         }
+
+        for beam_weapon in weapon_component.beam_weapons.iter_mut() {
+            if let Some(beam_data) = beam_weapon.update(time.delta()) {
+                fire_beam.send(FireBeamEvent {
+                    beam_data,
+                    source_entity: entity,
+                });
+            }
+        }
     }
 }
 
@@ -147,6 +195,150 @@ impl WeaponProjectileInitialVelocitiesExt for WeaponProjectileData {
 
                 linvels
             }
+            SpreadPattern::Ring | SpreadPattern::Spiral(_) => {
+                // leave a gap between the last and first projectiles so that they don't overlap
+                let spread = TAU * (self.count as f32 - 1.) / self.count.max(1) as f32;
+                evenly_spread_linvels(self.direction, self.speed, self.count, spread)
+            }
+            SpreadPattern::Wave(wave_pattern) => {
+                evenly_spread_linvels(self.direction, self.speed, self.count, wave_pattern.spread)
+            }
+            SpreadPattern::AimedBurst(aimed_burst_pattern) => evenly_spread_linvels(
+                self.direction,
+                self.speed,
+                self.count,
+                aimed_burst_pattern.spread,
+            ),
+        }
+    }
+}
+
+/// Velocities of `count` projectiles evenly spaced in an arc of `spread` radians centered on `direction`
+fn evenly_spread_linvels(direction: f32, speed: f32, count: usize, spread: f32) -> Vec<Vec2> {
+    let angle_segment = spread / (count as f32 - 1.).max(1.);
+
+    (0..count)
+        .map(|p| {
+            let angle_offset = (p as f32 - (count as f32 - 1.) / 2.) * angle_segment;
+            Vec2::from_angle(direction + angle_offset) * speed
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::math::Vec2;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+    use thetawave_interface::{
+        audio::SoundEffectType,
+        spawnable::{Faction, ProjectileType, SpawnPosition},
+        weapon::{
+            AimedBurstPatternData, SpiralPatternData, SpreadPattern, WavePatternData,
+            WeaponProjectileData,
+        },
+    };
+
+    use super::WeaponProjectileInitialVelocitiesExt;
+
+    fn projectile_data(spread_pattern: SpreadPattern, count: usize) -> WeaponProjectileData {
+        WeaponProjectileData {
+            ammunition: ProjectileType::Blast(Faction::Enemy),
+            damage: 5,
+            position: SpawnPosition::Local(Vec2::ZERO),
+            speed: 100.0,
+            direction: FRAC_PI_2,
+            despawn_time: 1.0,
+            count,
+            spread_pattern,
+            size: 1.0,
+            sound: SoundEffectType::EnemyFireBlast,
+            homing: None,
+            extra_pierce: 0,
+            extra_ricochet: 0,
+            status_effects: vec![],
         }
     }
+
+    /// Assert that projectiles are fired at the given angles with the given speed
+    fn assert_linvels(linvels: Vec<Vec2>, expected_angles: &[f32], speed: f32) {
+        assert_eq!(linvels.len(), expected_angles.len());
+        for (linvel, expected_angle) in linvels.iter().zip(expected_angles) {
+            let expected_linvel = Vec2::from_angle(*expected_angle) * speed;
+            assert!(
+                linvel.abs_diff_eq(expected_linvel, 1e-3),
+                "{linvel:?} is not {expected_linvel:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ring_spaces_projectiles_evenly_around_circle() {
+        let linvels = projectile_data(SpreadPattern::Ring, 4).get_linvels(10);
+
+        assert_linvels(
+            linvels,
+            &[
+                FRAC_PI_2 - 3.0 * FRAC_PI_4,
+                FRAC_PI_2 - FRAC_PI_4,
+                FRAC_PI_2 + FRAC_PI_4,
+                FRAC_PI_2 + 3.0 * FRAC_PI_4,
+            ],
+            100.0,
+        );
+    }
+
+    #[test]
+    fn test_ring_of_one_projectile_fires_in_direction() {
+        let linvels = projectile_data(SpreadPattern::Ring, 1).get_linvels(10);
+
+        assert_linvels(linvels, &[FRAC_PI_2], 100.0);
+    }
+
+    #[test]
+    fn test_spiral_spaces_projectiles_like_ring() {
+        let spiral = projectile_data(
+            SpreadPattern::Spiral(SpiralPatternData { angle_step: 0.3 }),
+            3,
+        );
+
+        assert_linvels(
+            spiral.get_linvels(10),
+            &[
+                FRAC_PI_2 - 2.0 * PI / 3.0,
+                FRAC_PI_2,
+                FRAC_PI_2 + 2.0 * PI / 3.0,
+            ],
+            100.0,
+        );
+    }
+
+    #[test]
+    fn test_wave_spreads_projectiles_across_arc() {
+        let wave = projectile_data(
+            SpreadPattern::Wave(WavePatternData {
+                spread: 1.0,
+                angle_amplitude: 0.5,
+                speed_amplitude: 0.5,
+                phase_step: 1.0,
+            }),
+            3,
+        );
+
+        assert_linvels(
+            wave.get_linvels(10),
+            &[FRAC_PI_2 - 0.5, FRAC_PI_2, FRAC_PI_2 + 0.5],
+            100.0,
+        );
+    }
+
+    #[test]
+    fn test_aimed_burst_spreads_projectiles_around_direction() {
+        let mut aimed_burst = projectile_data(
+            SpreadPattern::AimedBurst(AimedBurstPatternData { spread: 0.6 }),
+            2,
+        );
+        aimed_burst.direction = PI;
+
+        assert_linvels(aimed_burst.get_linvels(10), &[PI - 0.3, PI + 0.3], 100.0);
+    }
 }
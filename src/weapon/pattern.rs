@@ -0,0 +1,536 @@
+//! Attack patterns, a small command language loaded from RON that scripts when and in which
+//! directions mobs and mob segments fire the weapons of their `WeaponsComponent`.
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::EventWriter,
+        system::{Query, Res, Resource},
+    },
+    time::Time,
+    transform::components::Transform,
+};
+use bevy_rapier2d::{dynamics::Velocity, prelude::ImpulseJoint};
+use serde::Deserialize;
+use std::collections::HashMap;
+use thetawave_interface::{
+    health::HealthComponent, status_effect::StatusEffectsComponent, weapon::WeaponsComponent,
+};
+
+use crate::spawnable::{
+    find_controlling_mob, BossComponent, FireWeaponEvent, InitialMotion, MobComponent,
+};
+
+/// Maximum number of commands an attack pattern can execute in a single frame
+/// Prevents patterns without any waits from looping forever
+const MAX_COMMANDS_PER_UPDATE: usize = 256;
+
+/// Attack patterns mapped to their names
+#[derive(Deserialize, Resource)]
+pub(crate) struct AttackPatternsResource {
+    pub patterns: HashMap<String, AttackPattern>,
+}
+
+/// A list of commands that fire an entity's weapons, repeated for as long as the entity exists
+#[derive(Deserialize, Clone)]
+pub(crate) struct AttackPattern {
+    /// Time in seconds before the pattern first starts
+    #[serde(default)]
+    pub initial_time: f32,
+    pub commands: Vec<AttackPatternCommand>,
+}
+
+/// Commands of the attack pattern language
+#[derive(Deserialize, Clone)]
+pub(crate) enum AttackPatternCommand {
+    /// Fire the weapon at the given index of the entity's `WeaponsComponent`,
+    /// with the angle in radians added to the direction of the weapon
+    Fire {
+        weapon: usize,
+        #[serde(default)]
+        angle: f32,
+    },
+    /// Wait for the given time in seconds before running the next command
+    Wait(f32),
+    /// Fire a weapon `steps` times, sweeping the added angle from `from` to `to`,
+    /// waiting `interval` seconds after each shot
+    Sweep {
+        weapon: usize,
+        from: f32,
+        to: f32,
+        steps: usize,
+        interval: f32,
+    },
+    /// Run the commands `count` times
+    Repeat {
+        count: usize,
+        commands: Vec<AttackPatternCommand>,
+    },
+    /// Run `then` if the condition is met, otherwise run `otherwise`
+    If {
+        condition: AttackPatternCondition,
+        then: Vec<AttackPatternCommand>,
+        #[serde(default)]
+        otherwise: Vec<AttackPatternCommand>,
+    },
+}
+
+/// Conditions checked against the mob controlling the entity running the pattern
+#[derive(Deserialize, Clone)]
+pub(crate) enum AttackPatternCondition {
+    /// Health is below the given fraction of maximum health
+    HealthBelow(f32),
+    /// Health is at or above the given fraction of maximum health
+    HealthAbove(f32),
+    /// The mob's behavior sequence is in the named state
+    BehaviorState(String),
+    /// The mob is a boss in the given phase
+    BossPhase(usize),
+}
+
+/// State of the controlling mob used to check conditions
+struct AttackPatternContext {
+    health_percentage: f32,
+    behavior_state: Option<String>,
+    boss_phase: Option<usize>,
+}
+
+impl AttackPatternCondition {
+    fn is_met(&self, context: &AttackPatternContext) -> bool {
+        match self {
+            AttackPatternCondition::HealthBelow(fraction) => context.health_percentage < *fraction,
+            AttackPatternCondition::HealthAbove(fraction) => context.health_percentage >= *fraction,
+            AttackPatternCondition::BehaviorState(state) => {
+                context.behavior_state.as_ref() == Some(state)
+            }
+            AttackPatternCondition::BossPhase(phase) => context.boss_phase == Some(*phase),
+        }
+    }
+}
+
+/// A list of commands being run, along with the number of times it will run again
+struct AttackPatternFrame {
+    commands: Vec<AttackPatternCommand>,
+    index: usize,
+    repeats: usize,
+}
+
+impl AttackPatternFrame {
+    fn new(commands: Vec<AttackPatternCommand>, repeats: usize) -> Self {
+        AttackPatternFrame {
+            commands,
+            index: 0,
+            repeats,
+        }
+    }
+}
+
+/// A shot requested by an attack pattern
+struct AttackPatternShot {
+    weapon: usize,
+    angle: f32,
+}
+
+/// Runs an attack pattern using the entity's `WeaponsComponent`
+#[derive(Component)]
+pub(crate) struct AttackPatternComponent {
+    /// Name of the pattern in the `AttackPatternsResource`
+    pattern: String,
+    /// Stack of command lists being run, the last frame is run first
+    frames: Vec<AttackPatternFrame>,
+    /// Time in seconds until the next command is run
+    wait: Option<f32>,
+}
+
+impl AttackPatternComponent {
+    pub(crate) fn new(pattern: String) -> Self {
+        AttackPatternComponent {
+            pattern,
+            frames: vec![],
+            wait: None,
+        }
+    }
+
+    /// Run commands until the pattern waits, returning the shots that were fired
+    fn update(
+        &mut self,
+        pattern: &AttackPattern,
+        delta_seconds: f32,
+        context: &AttackPatternContext,
+    ) -> Vec<AttackPatternShot> {
+        let mut shots = vec![];
+        let mut wait = self.wait.unwrap_or(pattern.initial_time) - delta_seconds;
+        let mut executed = 0;
+
+        while wait <= 0.0 && executed < MAX_COMMANDS_PER_UPDATE {
+            executed += 1;
+
+            // restart the pattern after all of its commands have been run
+            let Some(frame) = self.frames.last_mut() else {
+                self.frames
+                    .push(AttackPatternFrame::new(pattern.commands.clone(), 0));
+                continue;
+            };
+
+            let Some(command) = frame.commands.get(frame.index).cloned() else {
+                if frame.repeats > 0 {
+                    frame.repeats -= 1;
+                    frame.index = 0;
+                } else {
+                    self.frames.pop();
+                }
+                continue;
+            };
+            frame.index += 1;
+
+            match command {
+                AttackPatternCommand::Fire { weapon, angle } => {
+                    shots.push(AttackPatternShot { weapon, angle });
+                }
+                AttackPatternCommand::Wait(time) => wait += time,
+                AttackPatternCommand::Sweep {
+                    weapon,
+                    from,
+                    to,
+                    steps,
+                    interval,
+                } => {
+                    let commands = (0..steps)
+                        .flat_map(|step| {
+                            let angle =
+                                from + (to - from) * step as f32 / (steps as f32 - 1.0).max(1.0);
+                            [
+                                AttackPatternCommand::Fire { weapon, angle },
+                                AttackPatternCommand::Wait(interval),
+                            ]
+                        })
+                        .collect();
+                    self.frames.push(AttackPatternFrame::new(commands, 0));
+                }
+                AttackPatternCommand::Repeat { count, commands } => {
+                    if count > 0 {
+                        self.frames
+                            .push(AttackPatternFrame::new(commands, count - 1));
+                    }
+                }
+                AttackPatternCommand::If {
+                    condition,
+                    then,
+                    otherwise,
+                } => {
+                    let commands = if condition.is_met(context) {
+                        then
+                    } else {
+                        otherwise
+                    };
+                    self.frames.push(AttackPatternFrame::new(commands, 0));
+                }
+            }
+        }
+
+        // don't carry over missed time if the command limit was reached
+        self.wait = Some(wait.max(0.0));
+
+        shots
+    }
+}
+
+/// Run attack patterns and fire the weapons of their entities
+/// Patterns of stunned entities are paused
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(super) fn attack_pattern_system(
+    mut pattern_query: Query<(
+        Entity,
+        &mut AttackPatternComponent,
+        &mut WeaponsComponent,
+        &Transform,
+        &Velocity,
+        Option<&HealthComponent>,
+        Option<&StatusEffectsComponent>,
+    )>,
+    mob_query: Query<(&MobComponent, &HealthComponent, Option<&BossComponent>)>,
+    joint_query: Query<&ImpulseJoint>,
+    attack_patterns_resource: Res<AttackPatternsResource>,
+    time: Res<Time>,
+    mut fire_weapon: EventWriter<FireWeaponEvent>,
+) {
+    for (entity, mut attack_pattern, mut weapons, transform, velocity, health, status_effects) in
+        pattern_query.iter_mut()
+    {
+        if status_effects.is_some_and(|status_effects| status_effects.is_stunned()) {
+            continue;
+        }
+
+        let Some(pattern) = attack_patterns_resource
+            .patterns
+            .get(&attack_pattern.pattern)
+        else {
+            continue;
+        };
+
+        // conditions are checked against the controlling mob, falling back to the entity itself
+        let context = match find_controlling_mob(entity, &joint_query, |mob_entity| {
+            mob_query.contains(mob_entity)
+        })
+        .and_then(|mob_entity| mob_query.get(mob_entity).ok())
+        {
+            Some((mob, mob_health, boss)) => AttackPatternContext {
+                health_percentage: mob_health.get_health_percentage(),
+                behavior_state: mob
+                    .behavior_sequence_tracker
+                    .as_ref()
+                    .map(|tracker| tracker.state.clone()),
+                boss_phase: boss.map(|boss| boss.phase),
+            },
+            None => AttackPatternContext {
+                health_percentage: health.map_or(1.0, |health| health.get_health_percentage()),
+                behavior_state: None,
+                boss_phase: None,
+            },
+        };
+
+        for shot in attack_pattern.update(pattern, time.delta_seconds(), &context) {
+            let Some(weapon) = weapons.weapons.get_mut(shot.weapon) else {
+                continue;
+            };
+            if !weapon.is_enabled {
+                continue;
+            }
+
+            let mut weapon_projectile_data = weapon.next_shot();
+            weapon_projectile_data.direction += shot.angle;
+
+            fire_weapon.send(FireWeaponEvent {
+                weapon_projectile_data,
+                source_transform: *transform,
+                source_entity: entity,
+                initial_motion: InitialMotion {
+                    linvel: Some(velocity.linvel),
+                    ..Default::default()
+                },
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        AttackPattern, AttackPatternCommand, AttackPatternComponent, AttackPatternCondition,
+        AttackPatternContext, MAX_COMMANDS_PER_UPDATE,
+    };
+
+    fn context() -> AttackPatternContext {
+        AttackPatternContext {
+            health_percentage: 1.0,
+            behavior_state: None,
+            boss_phase: None,
+        }
+    }
+
+    fn fire(weapon: usize) -> AttackPatternCommand {
+        AttackPatternCommand::Fire { weapon, angle: 0.0 }
+    }
+
+    /// Weapons and angles of the shots fired by a single update
+    fn update(
+        component: &mut AttackPatternComponent,
+        pattern: &AttackPattern,
+        delta_seconds: f32,
+        context: &AttackPatternContext,
+    ) -> Vec<(usize, f32)> {
+        component
+            .update(pattern, delta_seconds, context)
+            .into_iter()
+            .map(|shot| (shot.weapon, shot.angle))
+            .collect()
+    }
+
+    #[test]
+    fn test_pattern_waits_for_initial_time() {
+        let pattern = AttackPattern {
+            initial_time: 1.0,
+            commands: vec![fire(0), AttackPatternCommand::Wait(1.0)],
+        };
+        let mut component = AttackPatternComponent::new("test".to_string());
+
+        assert!(update(&mut component, &pattern, 0.5, &context()).is_empty());
+        assert_eq!(
+            update(&mut component, &pattern, 0.5, &context()),
+            vec![(0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn test_pattern_loops_after_waits() {
+        let pattern = AttackPattern {
+            initial_time: 0.0,
+            commands: vec![fire(0), AttackPatternCommand::Wait(1.0), fire(1)],
+        };
+        let mut component = AttackPatternComponent::new("test".to_string());
+
+        assert_eq!(
+            update(&mut component, &pattern, 0.0, &context()),
+            vec![(0, 0.0)]
+        );
+        assert!(update(&mut component, &pattern, 0.5, &context()).is_empty());
+        // the rest of the pattern runs, then it restarts without a wait
+        assert_eq!(
+            update(&mut component, &pattern, 0.5, &context()),
+            vec![(1, 0.0), (0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn test_sweep_spreads_angles_evenly() {
+        let pattern = AttackPattern {
+            initial_time: 0.0,
+            commands: vec![
+                AttackPatternCommand::Sweep {
+                    weapon: 2,
+                    from: -1.0,
+                    to: 1.0,
+                    steps: 3,
+                    interval: 0.0,
+                },
+                AttackPatternCommand::Wait(1.0),
+            ],
+        };
+        let mut component = AttackPatternComponent::new("test".to_string());
+
+        assert_eq!(
+            update(&mut component, &pattern, 0.0, &context()),
+            vec![(2, -1.0), (2, 0.0), (2, 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_sweep_waits_between_shots() {
+        let pattern = AttackPattern {
+            initial_time: 0.0,
+            commands: vec![AttackPatternCommand::Sweep {
+                weapon: 0,
+                from: 0.0,
+                to: 1.0,
+                steps: 2,
+                interval: 1.0,
+            }],
+        };
+        let mut component = AttackPatternComponent::new("test".to_string());
+
+        assert_eq!(
+            update(&mut component, &pattern, 0.0, &context()),
+            vec![(0, 0.0)]
+        );
+        assert_eq!(
+            update(&mut component, &pattern, 1.0, &context()),
+            vec![(0, 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_single_step_sweep_fires_from_angle() {
+        let pattern = AttackPattern {
+            initial_time: 0.0,
+            commands: vec![
+                AttackPatternCommand::Sweep {
+                    weapon: 0,
+                    from: 0.5,
+                    to: 1.0,
+                    steps: 1,
+                    interval: 0.0,
+                },
+                AttackPatternCommand::Wait(1.0),
+            ],
+        };
+        let mut component = AttackPatternComponent::new("test".to_string());
+
+        assert_eq!(
+            update(&mut component, &pattern, 0.0, &context()),
+            vec![(0, 0.5)]
+        );
+    }
+
+    #[test]
+    fn test_repeat_runs_commands_count_times() {
+        let repeat = |count| AttackPattern {
+            initial_time: 0.0,
+            commands: vec![
+                AttackPatternCommand::Repeat {
+                    count,
+                    commands: vec![fire(0)],
+                },
+                AttackPatternCommand::Wait(1.0),
+            ],
+        };
+
+        for count in [0, 1, 3] {
+            let mut component = AttackPatternComponent::new("test".to_string());
+            assert_eq!(
+                update(&mut component, &repeat(count), 0.0, &context()).len(),
+                count
+            );
+        }
+    }
+
+    #[test]
+    fn test_if_runs_branch_for_condition() {
+        let pattern = AttackPattern {
+            initial_time: 0.0,
+            commands: vec![
+                AttackPatternCommand::If {
+                    condition: AttackPatternCondition::HealthBelow(0.5),
+                    then: vec![fire(1)],
+                    otherwise: vec![fire(0)],
+                },
+                AttackPatternCommand::Wait(1.0),
+            ],
+        };
+
+        let mut component = AttackPatternComponent::new("test".to_string());
+        assert_eq!(
+            update(&mut component, &pattern, 0.0, &context()),
+            vec![(0, 0.0)]
+        );
+
+        let mut component = AttackPatternComponent::new("test".to_string());
+        let context = AttackPatternContext {
+            health_percentage: 0.25,
+            ..context()
+        };
+        assert_eq!(
+            update(&mut component, &pattern, 0.0, &context),
+            vec![(1, 0.0)]
+        );
+    }
+
+    #[test]
+    fn test_conditions() {
+        let mob_context = AttackPatternContext {
+            health_percentage: 0.5,
+            behavior_state: Some("attack".to_string()),
+            boss_phase: Some(1),
+        };
+
+        assert!(!AttackPatternCondition::HealthBelow(0.5).is_met(&mob_context));
+        assert!(AttackPatternCondition::HealthAbove(0.5).is_met(&mob_context));
+        assert!(AttackPatternCondition::BehaviorState("attack".to_string()).is_met(&mob_context));
+        assert!(!AttackPatternCondition::BehaviorState("idle".to_string()).is_met(&mob_context));
+        assert!(AttackPatternCondition::BossPhase(1).is_met(&mob_context));
+        assert!(!AttackPatternCondition::BossPhase(0).is_met(&context()));
+    }
+
+    #[test]
+    fn test_pattern_without_waits_is_limited() {
+        let pattern = AttackPattern {
+            initial_time: 0.0,
+            commands: vec![fire(0)],
+        };
+        let mut component = AttackPatternComponent::new("test".to_string());
+
+        let shots = update(&mut component, &pattern, 0.0, &context());
+        assert!(!shots.is_empty());
+        assert!(shots.len() <= MAX_COMMANDS_PER_UPDATE);
+        assert_eq!(component.wait, Some(0.0));
+    }
+}